@@ -0,0 +1,57 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use exms::exam::{Exam, Student};
+
+// Deterministic pseudo-random grades between 0 and 10
+fn students(count: usize) -> Vec<Student> {
+    let mut state: u64 = 42;
+
+    (0..count)
+        .map(|i| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            Student::new(
+                format!("Student {i}"),
+                ((state >> 33) % 1001) as f32 / 100.0,
+            )
+        })
+        .collect()
+}
+
+fn statistics_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Statistics");
+    for count in [1_000, 100_000] {
+        let students = students(count);
+
+        group.bench_function(format!("new + setters/{count}"), |b| {
+            b.iter_batched(
+                || students.clone(),
+                |students| {
+                    let mut exam = Exam::new(students);
+                    exam.set_max_grade(12.0).unwrap();
+                    exam.set_pass_threshold(5.0).unwrap();
+                    exam
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_function(format!("builder/{count}"), |b| {
+            b.iter_batched(
+                || students.clone(),
+                |students| {
+                    Exam::builder(students)
+                        .max_grade(12.0)
+                        .pass_threshold(5.0)
+                        .build()
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, statistics_bench);
+criterion_main!(benches);
@@ -0,0 +1,400 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::mpsc;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use colored::Colorize;
+use exms::exam::{
+    output_style, set_output_style, ColorMode, Exam, OutputStyle, ParseOptions, Pipeline, Severity,
+    SortBy, TableStyle,
+};
+use prettytable::{row, Table};
+
+/// Get statistics and compare exam results from the shell.
+///
+/// Each file is read as described in the exms documentation. By default the
+/// students table of every exam is printed. When several files are given, a
+/// table comparing their statistics is printed after them. With `--watch`,
+/// everything is printed again whenever one of the files changes.
+#[derive(Parser, Debug)]
+#[command(
+    name = "exms",
+    version,
+    about,
+    after_help = EXIT_STATUS_HELP,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Exam files to read
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Order of the students table
+    #[arg(long, value_enum)]
+    sort: Option<Sort>,
+
+    /// Print the students of each group together, with a subtotal row per
+    /// group, a histogram series per group along with --histogram, or the
+    /// statistics and pass rate of each group along with --summary
+    #[arg(long, conflicts_with = "edges")]
+    grouped: bool,
+
+    /// Only keep the students whose name contains any of the given names
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    filter: Vec<String>,
+
+    /// Maximum achievable grade, overriding the one in the files
+    #[arg(long, value_name = "GRADE", value_parser = positive_number::<f32>)]
+    max_grade: Option<f32>,
+
+    /// Print the summary statistics instead of the students table
+    #[arg(long)]
+    summary: bool,
+
+    /// Print a histogram of the grades instead of the students table
+    #[arg(long)]
+    histogram: bool,
+
+    /// Width of each bucket of the histogram
+    #[arg(long, requires = "histogram", value_parser = positive_number::<f64>)]
+    step: Option<f64>,
+
+    /// Edges of the buckets of the histogram, instead of buckets of equal width
+    #[arg(
+        long,
+        requires = "histogram",
+        conflicts_with = "step",
+        value_delimiter = ',',
+        value_name = "GRADES"
+    )]
+    edges: Vec<f64>,
+
+    /// Keep running, printing everything again whenever a file changes
+    #[arg(long)]
+    watch: bool,
+
+    /// Directory where the parsed files are cached, so unchanged files are not
+    /// parsed again on the next run. Not used with --watch
+    #[arg(long, env = "EXMS_CACHE_DIR", value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Disable colored output
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Draw the tables with plain ASCII characters
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+const EXIT_STATUS_HELP: &str = "\
+Exit status:
+  0  Everything went fine
+  1  Warnings were found in the exam files or while rendering the reports
+  2  An exam file couldn't be read or a report couldn't be written
+  3  Invalid arguments or pipeline spec file";
+
+/// Exit status of the command, from least to most severe. The most severe
+/// status found while running is the one returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    Ok = 0,
+    Warnings = 1,
+    DataError = 2,
+    UsageError = 3,
+}
+
+impl From<Status> for ExitCode {
+    fn from(status: Status) -> Self {
+        ExitCode::from(status as u8)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the analysis described in a pipeline spec file
+    Run {
+        /// TOML file describing the pipeline
+        spec: PathBuf,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Sort {
+    Grade,
+    Name,
+    Rank,
+    Percentile,
+    Group,
+    Delta,
+}
+
+impl From<Sort> for SortBy {
+    fn from(sort: Sort) -> Self {
+        match sort {
+            Sort::Grade => SortBy::Grade,
+            Sort::Name => SortBy::Name,
+            Sort::Rank => SortBy::Rank,
+            Sort::Percentile => SortBy::Percentile,
+            Sort::Group => SortBy::Group,
+            Sort::Delta => SortBy::Delta,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        Err(err) => {
+            // Help and version requests are also reported as errors by clap
+            let status = if err.use_stderr() {
+                Status::UsageError
+            } else {
+                Status::Ok
+            };
+            let _ = err.print();
+            return status.into();
+        }
+    };
+
+    set_output_style(OutputStyle {
+        color: if args.no_color {
+            ColorMode::Never
+        } else {
+            ColorMode::Auto
+        },
+        table: if args.ascii {
+            TableStyle::Ascii
+        } else {
+            TableStyle::Box
+        },
+    });
+
+    match &args.command {
+        Some(Command::Run { spec }) => run_pipeline(spec),
+        None => run(&args),
+    }
+    .into()
+}
+
+fn run(args: &Args) -> Status {
+    if args.watch {
+        return watch(args);
+    }
+
+    let mut exams = Vec::new();
+    for path in &args.files {
+        let exam = match &args.cache_dir {
+            Some(cache_dir) => Exam::from_file_cached(path, cache_dir, &parse_options()),
+            None => Exam::from_file_with_options(path, &parse_options()),
+        };
+        match exam {
+            Ok(mut exam) => {
+                prepare(args, &mut exam);
+                exams.push(exam);
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                return Status::DataError;
+            }
+        }
+    }
+
+    render(args, &args.files.iter().zip(&exams).collect::<Vec<_>>())
+}
+
+// Parses the files again and prints everything whenever one of them changes.
+// Only returns if the files can't be watched.
+fn watch(args: &Args) -> Status {
+    let (sender, receiver) = mpsc::channel();
+
+    let mut watchers = Vec::new();
+    for (index, path) in args.files.iter().enumerate() {
+        let sender = sender.clone();
+        match Exam::watch_with_options(path, &parse_options(), move |exam| {
+            let _ = sender.send((index, exam));
+        }) {
+            Ok(watcher) => watchers.push(watcher),
+            Err(err) => {
+                eprintln!("Error while watching {}: {err}", path.display());
+                return Status::DataError;
+            }
+        }
+    }
+
+    let mut exams: Vec<Option<Exam>> = vec![None; args.files.len()];
+    for (index, exam) in receiver {
+        match exam {
+            Ok(mut exam) => {
+                prepare(args, &mut exam);
+                exams[index] = Some(exam);
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                continue;
+            }
+        }
+
+        // Wait until every file has been read once
+        let loaded: Option<Vec<_>> = args
+            .files
+            .iter()
+            .zip(&exams)
+            .map(|(path, exam)| exam.as_ref().map(|exam| (path, exam)))
+            .collect();
+
+        if let Some(loaded) = loaded {
+            // Clear the terminal before printing everything again
+            print!("\x1B[2J\x1B[H");
+            render(args, &loaded);
+        }
+    }
+
+    Status::Ok
+}
+
+// Checks done on every file, reported along with the rest of warnings
+fn parse_options() -> ParseOptions {
+    ParseOptions {
+        validate_grades: true,
+        check_names: true,
+        ..Default::default()
+    }
+}
+
+fn prepare(args: &Args, exam: &mut Exam) {
+    if let Some(max_grade) = args.max_grade {
+        exam.set_max_grade(max_grade)
+            .expect("parsed exams are not published");
+    }
+
+    // The statistics are the ones of the remaining students
+    if !args.filter.is_empty() {
+        *exam = exam.filtered_by_name(&args.filter);
+    }
+}
+
+// Parses an option that must be a finite number greater than 0
+fn positive_number<T>(value: &str) -> Result<T, String>
+where
+    T: FromStr + Into<f64> + Copy,
+{
+    let number: T = value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a number"))?;
+
+    if number.into().is_finite() && number.into() > 0.0 {
+        Ok(number)
+    } else {
+        Err(format!("`{value}` is not a number greater than 0"))
+    }
+}
+
+fn render(args: &Args, exams: &[(&PathBuf, &Exam)]) -> Status {
+    let mut status = Status::Ok;
+    for (_, exam) in exams {
+        let mut warnings = Vec::new();
+        if args.summary {
+            exam.summary();
+        }
+        if args.summary && args.grouped {
+            exam.group_summary();
+            exam.pass_rate_chart();
+        }
+        if args.histogram && !args.edges.is_empty() {
+            let histogram = exam.histogram_builder().edges(args.edges.clone()).build();
+            histogram.print();
+            warnings.extend(histogram.warnings());
+        } else if args.histogram && args.grouped {
+            warnings.extend(exam.grouped_histogram(args.step));
+        } else if args.histogram {
+            warnings.extend(exam.histogram(args.step));
+        }
+        if !args.summary && !args.histogram {
+            let sort = args.sort.map(SortBy::from);
+            warnings.extend(if args.grouped {
+                exam.print_students_grouped(sort)
+            } else {
+                exam.print_students(sort)
+            });
+        }
+
+        status = status.max(print_warnings(&exam.warnings()));
+        status = status.max(print_warnings(&warnings));
+
+        // Repeated students or grades out of range
+        if !exam.data_quality(None).is_clean(Severity::High) {
+            status = status.max(Status::Warnings);
+        }
+    }
+
+    if exams.len() > 1 {
+        comparison_table(exams).printstd();
+    }
+
+    status
+}
+
+fn run_pipeline(spec: &Path) -> Status {
+    let pipeline = match Pipeline::from_file(spec) {
+        Ok(pipeline) => pipeline,
+        Err(err) => {
+            eprintln!("{err}");
+            return Status::UsageError;
+        }
+    };
+
+    match pipeline.execute() {
+        Ok(warnings) => print_warnings(&warnings.exam).max(print_warnings(&warnings.render)),
+        Err(err) => {
+            eprintln!("{err}");
+            Status::DataError
+        }
+    }
+}
+
+fn print_warnings<W: fmt::Display>(warnings: &[W]) -> Status {
+    for warning in warnings {
+        eprintln!("{} {warning}", "warning:".yellow());
+    }
+
+    if warnings.is_empty() {
+        Status::Ok
+    } else {
+        Status::Warnings
+    }
+}
+
+// Table with the main statistics of each exam, one exam per row
+fn comparison_table(exams: &[(&PathBuf, &Exam)]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(row![
+        c->"File",
+        c->"Students",
+        c->"Pass Rate",
+        c->"Mean",
+        c->"Median",
+        c->"Standard Deviation"
+    ]);
+
+    for (path, exam) in exams {
+        let statistics = exam.statistics();
+        table.add_row(row![
+            path.display(),
+            c->statistics.total_students(),
+            c->format!("{}%", statistics.pass_rate()),
+            c->statistics.mean(),
+            c->statistics.median(),
+            c->statistics.std_dev()
+        ]);
+    }
+
+    table.set_format(output_style().table.format());
+    table
+}
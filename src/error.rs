@@ -5,6 +5,8 @@ use std::path::{Path, PathBuf};
 
 use colored::Colorize;
 
+use crate::exam::LimitExceeded;
+
 pub(crate) trait WithPath<T, E> {
     fn with_path<P: AsRef<Path>>(self, path: P) -> Result<T, (E, P)>;
 }
@@ -30,13 +32,115 @@ impl ParseError {
             path: path.as_ref().to_owned(),
         }
     }
+
+    /// Returns the path of the file that could not be parsed, or `<input>` if
+    /// the exam was not read from a file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if the file could not be read, e.g. because it doesn't
+    /// exist or it's not valid UTF-8.
+    pub fn is_io(&self) -> bool {
+        matches!(self.kind, ParseErrorKind::Io(_))
+    }
+
+    /// Returns `true` if the content of the file is not valid in its format,
+    /// or doesn't have the structure of an exam file, e.g. a missing bracket
+    /// or an unknown field.
+    pub fn is_syntax(&self) -> bool {
+        match self.kind {
+            ParseErrorKind::Toml { .. }
+            | ParseErrorKind::Json(_)
+            | ParseErrorKind::Yaml(_)
+            | ParseErrorKind::Csv { .. } => true,
+            #[cfg(feature = "xlsx")]
+            ParseErrorKind::Xlsx(_) | ParseErrorKind::XlsxRow { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the line and column, starting at 1, where a syntax error was
+    /// found in a TOML, JSON or YAML file, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{parse_bytes, FileFormat};
+    ///
+    /// let content = b"[students]\nJoan = 7.5\nJose = \n";
+    /// let error = parse_bytes(FileFormat::Toml, content).unwrap_err();
+    ///
+    /// assert!(error.is_syntax());
+    /// assert_eq!(error.location(), Some((3, 8)));
+    /// assert_eq!(error.path().to_str(), Some("<input>"));
+    /// ```
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match &self.kind {
+            ParseErrorKind::Toml { location, .. } => *location,
+            ParseErrorKind::Json(err) if err.line() > 0 => Some((err.line(), err.column())),
+            ParseErrorKind::Yaml(err) => err
+                .location()
+                .map(|location| (location.line(), location.column())),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the file was rejected for exceeding one of the
+    /// [Limits](crate::exam::Limits) it was parsed with.
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(self.kind, ParseErrorKind::LimitExceeded(_))
+    }
+
+    /// Returns the name of the student that appears more than once in the
+    /// file, if it was rejected for that reason, see
+    /// [ParseOptions::reject_duplicates](crate::exam::ParseOptions::reject_duplicates).
+    pub fn duplicate_student(&self) -> Option<&str> {
+        match &self.kind {
+            ParseErrorKind::DuplicateStudent { student } => Some(student),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub(crate) enum ParseErrorKind {
     Io(io::Error),
-    Toml(toml::de::Error),
+    Toml {
+        err: Box<toml::de::Error>,
+        location: Option<(usize, usize)>,
+    },
     Json(serde_json::Error),
+    Yaml(serde_yaml::Error),
+    Csv {
+        line: usize,
+        reason: String,
+    },
+    #[cfg(feature = "xlsx")]
+    Xlsx(calamine::XlsxError),
+    #[cfg(feature = "xlsx")]
+    XlsxRow {
+        sheet: String,
+        row: usize,
+        reason: String,
+    },
+    NonFinite {
+        field: String,
+    },
+    InvalidMaxGrade {
+        max_grade: f32,
+    },
+    InvalidValue {
+        field: String,
+        expected: &'static str,
+    },
+    MissingGrade {
+        student: String,
+    },
+    DuplicateStudent {
+        student: String,
+    },
+    LimitExceeded(LimitExceeded),
     UnsupportedFormat,
     MissingFormat,
 }
@@ -56,7 +160,7 @@ impl fmt::Display for ParseError {
                 write!(f, "Error while reading {colored_path}: {err}",)
             }
 
-            ParseErrorKind::Toml(err) => {
+            ParseErrorKind::Toml { err, .. } => {
                 write!(f, "Error while parsing file {colored_path}: {err}",)
             }
 
@@ -64,6 +168,71 @@ impl fmt::Display for ParseError {
                 write!(f, "Error while parsing file {colored_path}: {err}",)
             }
 
+            ParseErrorKind::Yaml(err) => {
+                write!(f, "Error while parsing file {colored_path}: {err}",)
+            }
+
+            ParseErrorKind::Csv { line, reason } => {
+                write!(
+                    f,
+                    "Error while parsing file {colored_path}: line {line}: {reason}",
+                )
+            }
+
+            #[cfg(feature = "xlsx")]
+            ParseErrorKind::Xlsx(err) => {
+                write!(f, "Error while parsing file {colored_path}: {err}",)
+            }
+
+            #[cfg(feature = "xlsx")]
+            ParseErrorKind::XlsxRow { sheet, row, reason } => {
+                write!(
+                    f,
+                    "Error while parsing file {colored_path}: sheet {sheet}, row {row}: {reason}",
+                )
+            }
+
+            ParseErrorKind::NonFinite { field } => {
+                write!(
+                    f,
+                    "Error while parsing file {colored_path}: the {field} is not a finite number",
+                )
+            }
+
+            ParseErrorKind::InvalidMaxGrade { max_grade } => {
+                write!(
+                    f,
+                    "Error while parsing file {colored_path}: the max_grade must be greater than 0, not {}",
+                    max_grade.to_string().yellow(),
+                )
+            }
+
+            ParseErrorKind::InvalidValue { field, expected } => {
+                write!(
+                    f,
+                    "Error while parsing file {colored_path}: the {} must be {expected}",
+                    field.yellow(),
+                )
+            }
+
+            ParseErrorKind::MissingGrade { student } => {
+                write!(
+                    f,
+                    "Error while parsing file {colored_path}: `{student}` has neither a grade nor question scores",
+                )
+            }
+
+            ParseErrorKind::DuplicateStudent { student } => {
+                write!(
+                    f,
+                    "Error while parsing file {colored_path}: `{student}` appears more than once",
+                )
+            }
+
+            ParseErrorKind::LimitExceeded(limit) => {
+                write!(f, "Error while parsing file {colored_path}: {limit}",)
+            }
+
             ParseErrorKind::MissingFormat => write!(
                 f,
                 "Error while parsing file {colored_path}: Unable to recognize file extension",
@@ -88,7 +257,11 @@ impl<P: AsRef<Path>> From<(io::Error, P)> for ParseError {
 
 impl<P: AsRef<Path>> From<(toml::de::Error, P)> for ParseError {
     fn from((toml_err, path): (toml::de::Error, P)) -> Self {
-        ParseError::new(ParseErrorKind::Toml(toml_err), path)
+        let kind = ParseErrorKind::Toml {
+            err: Box::new(toml_err),
+            location: None,
+        };
+        ParseError::new(kind, path)
     }
 }
 
@@ -98,4 +271,226 @@ impl<P: AsRef<Path>> From<(serde_json::Error, P)> for ParseError {
     }
 }
 
+impl<P: AsRef<Path>> From<(serde_yaml::Error, P)> for ParseError {
+    fn from((yaml_err, path): (serde_yaml::Error, P)) -> Self {
+        ParseError::new(ParseErrorKind::Yaml(yaml_err), path)
+    }
+}
+
 impl std::error::Error for ParseError {}
+
+// Error of a TOML file, along with the line and column where it was found in
+// its content
+pub(crate) fn toml_error(err: toml::de::Error, content: &str) -> ParseErrorKind {
+    let location = err.span().and_then(|span| {
+        let before = content.get(..span.start)?;
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        Some((line, column))
+    });
+
+    ParseErrorKind::Toml {
+        err: Box::new(err),
+        location,
+    }
+}
+
+/// This type represents the errors that can occur while running a
+/// [Pipeline](crate::exam::Pipeline)
+#[derive(Debug)]
+pub enum PipelineError {
+    /// One of the input files, or of the files used to filter the students,
+    /// could not be parsed.
+    Parse(ParseError),
+
+    /// One of the reports could not be written.
+    Output { path: PathBuf, err: io::Error },
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineError::Parse(err) => err.fmt(f),
+
+            PipelineError::Output { path, err } => write!(
+                f,
+                "Error while writing {}: {err}",
+                path.to_str().unwrap_or_default().yellow()
+            ),
+        }
+    }
+}
+
+impl From<ParseError> for PipelineError {
+    fn from(err: ParseError) -> Self {
+        PipelineError::Parse(err)
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+/// This type represents the error returned when changing an exam that is
+/// published, see [Exam::publish](crate::exam::Exam::publish)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedError {
+    change: String,
+}
+
+impl PublishedError {
+    pub(crate) fn new(change: &str) -> Self {
+        Self {
+            change: change.to_string(),
+        }
+    }
+
+    /// Returns the change that was rejected, e.g. `"filtering students by
+    /// file"`.
+    pub fn change(&self) -> &str {
+        &self.change
+    }
+}
+
+impl fmt::Display for PublishedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Rejected {}: the exam is published",
+            self.change.yellow()
+        )
+    }
+}
+
+impl std::error::Error for PublishedError {}
+
+/// This type represents the errors that can occur while filtering the students
+/// of an exam by the students of other files, see
+/// [Exam::filter_by_file](crate::exam::Exam::filter_by_file)
+#[derive(Debug)]
+pub enum FilterError {
+    /// One of the files could not be parsed.
+    Parse(ParseError),
+
+    /// The exam is published, so its students can't be filtered.
+    Published(PublishedError),
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterError::Parse(err) => err.fmt(f),
+            FilterError::Published(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<ParseError> for FilterError {
+    fn from(err: ParseError) -> Self {
+        FilterError::Parse(err)
+    }
+}
+
+impl From<PublishedError> for FilterError {
+    fn from(err: PublishedError) -> Self {
+        FilterError::Published(err)
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+/// This type represents the errors that can occur while computing the
+/// anonymous ranking of an exam, see
+/// [Exam::anonymous_ranking](crate::exam::Exam::anonymous_ranking)
+#[derive(Debug)]
+pub enum AnonymousError {
+    /// The secret used to compute the IDs of the students is empty.
+    EmptySecret,
+
+    /// Two students got the same ID, e.g. because they have the same name.
+    DuplicateId {
+        id: String,
+        first: String,
+        second: String,
+    },
+
+    /// The ranking could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for AnonymousError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnonymousError::EmptySecret => {
+                write!(f, "The secret of the anonymous IDs can't be empty")
+            }
+
+            AnonymousError::DuplicateId { id, first, second } => write!(
+                f,
+                "Students {} and {} have the same anonymous ID {}",
+                first.yellow(),
+                second.yellow(),
+                id.yellow()
+            ),
+
+            AnonymousError::Io(err) => write!(f, "Could not write the ranking: {err}"),
+        }
+    }
+}
+
+impl From<io::Error> for AnonymousError {
+    fn from(err: io::Error) -> Self {
+        AnonymousError::Io(err)
+    }
+}
+
+impl std::error::Error for AnonymousError {}
+
+/// This type represents the errors that can occur when a student is given an
+/// invalid grade
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradeError {
+    /// The grade is not a number.
+    NaN,
+
+    /// The grade is lower than zero.
+    Negative(f32),
+
+    /// The grade is greater than the maximum grade of the exam.
+    AboveMax { grade: f32, max_grade: f32 },
+}
+
+impl fmt::Display for GradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GradeError::NaN => write!(f, "Invalid grade: {}", "NaN".yellow()),
+
+            GradeError::Negative(grade) => {
+                write!(
+                    f,
+                    "Invalid grade {}: grades can't be negative",
+                    grade.to_string().yellow()
+                )
+            }
+
+            GradeError::AboveMax { grade, max_grade } => write!(
+                f,
+                "Invalid grade {}: grades can't be greater than the maximum grade ({})",
+                grade.to_string().yellow(),
+                max_grade.to_string().yellow(),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GradeError {}
+
+pub(crate) fn validate_grade(grade: f32, max_grade: f32) -> Result<f32, GradeError> {
+    if grade.is_nan() {
+        Err(GradeError::NaN)
+    } else if grade < 0.0 {
+        Err(GradeError::Negative(grade))
+    } else if grade > max_grade {
+        Err(GradeError::AboveMax { grade, max_grade })
+    } else {
+        Ok(grade)
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use indexmap::map::IndexMap;
+use serde::Serialize;
+
+use crate::exam::export::{csv_field, export_time_series, ExportFormat};
+use crate::exam::statistics::Statistics;
+use crate::exam::student::serialize_rank;
+use crate::exam::{Exam, QuestionStatistics};
+
+#[derive(Serialize)]
+struct ArchivedExam<'a> {
+    title: Option<&'a str>,
+    date: Option<&'a str>,
+    course: Option<&'a str>,
+    term: Option<&'a str>,
+    instructor: Option<&'a str>,
+    max_grade: f32,
+    statistics: &'a Statistics,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    questions: Vec<QuestionStatistics>,
+    students: Vec<ArchivedStudent<'a>>,
+}
+
+#[derive(Serialize)]
+struct ArchivedStudent<'a> {
+    name: &'a str,
+    grade: f32,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    questions: &'a IndexMap<String, f32>,
+    #[serde(serialize_with = "serialize_rank")]
+    rank: Option<f32>,
+    percentile: Option<f32>,
+    quartile: Option<u8>,
+    decile: Option<u8>,
+    band: Option<&'a str>,
+}
+
+// Writes the archive in a new timestamped folder inside `dir` and returns the
+// path of that folder.
+pub fn archive(exam: &Exam, dir: &Path) -> io::Result<PathBuf> {
+    let name: String = exam
+        .title
+        .as_deref()
+        .unwrap_or("exam")
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let folder = dir.join(format!("{name}-{}", timestamp()));
+    fs::create_dir_all(dir)?;
+    fs::create_dir(&folder)?;
+
+    if let Some(source) = &exam.source {
+        let file_name = source.file_name().unwrap_or_default();
+        fs::copy(source, folder.join(file_name))?;
+    }
+
+    let archived = ArchivedExam {
+        title: exam.title.as_deref(),
+        date: exam.date.as_deref(),
+        course: exam.course.as_deref(),
+        term: exam.term.as_deref(),
+        instructor: exam.instructor.as_deref(),
+        max_grade: exam.max_grade,
+        statistics: &exam.statistics,
+        questions: exam.question_statistics(),
+        students: exam
+            .students
+            .iter()
+            .map(|student| ArchivedStudent {
+                name: &student.name,
+                grade: student.grade,
+                questions: &student.questions,
+                rank: student.rank,
+                percentile: student.percentile,
+                quartile: student.quartile(),
+                decile: student.decile(),
+                band: exam
+                    .grade_scale
+                    .as_ref()
+                    .and_then(|scale| scale.band(student.grade))
+                    .map(|band| band.label.as_str()),
+            })
+            .collect(),
+    };
+
+    let statistics = BufWriter::new(File::create(folder.join("statistics.json"))?);
+    serde_json::to_writer_pretty(statistics, &archived)?;
+
+    let mut students = BufWriter::new(File::create(folder.join("students.csv"))?);
+    writeln!(students, "name,grade,rank,percentile,quartile,decile")?;
+    for student in &archived.students {
+        writeln!(
+            students,
+            "{},{},{},{},{},{}",
+            csv_field(student.name),
+            student.grade,
+            student
+                .rank
+                .map(|rank| rank.to_string())
+                .unwrap_or_default(),
+            student
+                .percentile
+                .map(|percentile| percentile.to_string())
+                .unwrap_or_default(),
+            student
+                .quartile
+                .map(|quartile| quartile.to_string())
+                .unwrap_or_default(),
+            student
+                .decile
+                .map(|decile| decile.to_string())
+                .unwrap_or_default()
+        )?;
+    }
+    students.flush()?;
+
+    let summary = File::create(folder.join("summary.csv"))?;
+    export_time_series(std::slice::from_ref(exam), summary, ExportFormat::Csv)?;
+
+    let mut log = BufWriter::new(File::create(folder.join("transformations.log"))?);
+    for transformation in &exam.transformations {
+        writeln!(log, "{transformation}")?;
+    }
+    log.flush()?;
+
+    Ok(folder)
+}
+
+// Current UTC time formatted as `YYYY-MM-DDTHH-MM-SS`, which is safe to use in
+// file names.
+fn timestamp() -> String {
+    format_utc(SystemTime::now(), '-')
+}
+
+// UTC time formatted as `YYYY-MM-DDTHH:MM:SS`, with the given separator between
+// the hours, minutes and seconds
+pub(crate) fn format_utc(time: SystemTime, separator: char) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let (days, seconds) = ((seconds / 86400) as i64, seconds % 86400);
+
+    // Civil date from the number of days since 1970-01-01, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}{separator}{:02}{separator}{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
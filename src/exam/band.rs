@@ -0,0 +1,232 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ptr;
+
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::exam::{table_format, Student};
+
+/// A labeled range of grades, e.g. "Notable" for grades from 7 to 9.
+///
+/// The band covers every grade from `min_grade` up to the `min_grade` of the
+/// next band of the [GradeScale] it belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GradeBand {
+    /// Label of the band.
+    pub label: String,
+
+    /// Lowest grade included in the band.
+    pub min_grade: f32,
+}
+
+/// Set of labeled bands used to classify the exam grades.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct GradeScale {
+    // Sorted by `min_grade` in ascending order
+    bands: Vec<GradeBand>,
+}
+
+impl GradeScale {
+    /// Creates a new scale from a list of `(label, min_grade)` pairs. The
+    /// bands don't need to be given in any particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::GradeScale;
+    ///
+    /// let scale = GradeScale::new([("Fail", 0.0), ("Distinction", 8.0), ("Pass", 5.0)]);
+    ///
+    /// assert_eq!(scale.band(9.1).unwrap().label, "Distinction");
+    /// assert_eq!(scale.band(5.0).unwrap().label, "Pass");
+    /// assert_eq!(scale.band(4.9).unwrap().label, "Fail");
+    /// ```
+    pub fn new<S: Into<String>>(bands: impl IntoIterator<Item = (S, f32)>) -> Self {
+        let mut bands: Vec<GradeBand> = bands
+            .into_iter()
+            .map(|(label, min_grade)| GradeBand {
+                label: label.into(),
+                min_grade,
+            })
+            .collect();
+
+        bands.sort_by(|a, b| {
+            a.min_grade
+                .partial_cmp(&b.min_grade)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        Self { bands }
+    }
+
+    /// Creates the scale used in Spanish universities for grades out of 10:
+    /// "Suspenso" (0-5), "Aprobado" (5-7), "Notable" (7-9) and "Sobresaliente"
+    /// (9-10).
+    pub fn spanish() -> Self {
+        Self::new([
+            ("Suspenso", 0.0),
+            ("Aprobado", 5.0),
+            ("Notable", 7.0),
+            ("Sobresaliente", 9.0),
+        ])
+    }
+
+    /// Returns the bands of the scale, sorted by their lowest grade.
+    pub fn bands(&self) -> &[GradeBand] {
+        &self.bands
+    }
+
+    /// Returns the band the given grade belongs to, or `None` if the grade is
+    /// lower than the lowest band.
+    pub fn band(&self, grade: f32) -> Option<&GradeBand> {
+        self.bands.iter().rev().find(|band| grade >= band.min_grade)
+    }
+
+    // Histogram bucket edges matching the bands, from 0 to the maximum grade
+    pub(crate) fn edges(&self, max_grade: f32) -> Vec<f64> {
+        let mut edges = vec![0.0];
+        edges.extend(
+            self.bands
+                .iter()
+                .map(|band| band.min_grade as f64)
+                .filter(|&min_grade| min_grade > 0.0 && min_grade < max_grade as f64),
+        );
+        edges.push(max_grade as f64);
+
+        edges
+    }
+
+    // Number of students in each band, in the same order as the bands
+    pub(crate) fn counts(&self, students: &[Student]) -> Vec<(&str, usize)> {
+        self.bands
+            .iter()
+            .map(|band| {
+                let count = students
+                    .iter()
+                    .filter(|s| self.band(s.grade).is_some_and(|b| ptr::eq(b, band)))
+                    .count();
+
+                (band.label.as_str(), count)
+            })
+            .collect()
+    }
+}
+
+/// Number of students that moved from each band of a [GradeScale] in an exam
+/// to each band in a later exam, see
+/// [Exam::band_transitions](crate::exam::Exam::band_transitions).
+///
+/// The [Display](fmt::Display) implementation renders the matrix as a table
+/// with a row per band in the earlier exam and a column per band in the later
+/// one. Students that moved to a higher band are counted in green, and the
+/// ones that moved to a lower band in red.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BandTransitions {
+    labels: Vec<String>,
+    // Indexed by the band in the earlier exam and then by the band in the
+    // later one
+    counts: Vec<Vec<usize>>,
+}
+
+impl BandTransitions {
+    // Pairs of grades of the same student in the earlier and the later exam.
+    // Grades lower than the lowest band are not counted.
+    pub(crate) fn new(scale: &GradeScale, grades: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        let bands = scale.bands();
+        let position = |grade: f32| {
+            let band = scale.band(grade)?;
+            bands.iter().position(|b| ptr::eq(b, band))
+        };
+
+        let mut counts = vec![vec![0; bands.len()]; bands.len()];
+        for (from, to) in grades {
+            if let (Some(from), Some(to)) = (position(from), position(to)) {
+                counts[from][to] += 1;
+            }
+        }
+
+        Self {
+            labels: bands.iter().map(|band| band.label.clone()).collect(),
+            counts,
+        }
+    }
+
+    /// Returns the labels of the bands, from the lowest to the highest.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Returns the number of students that moved from each band to each
+    /// band, indexed first by the band in the earlier exam and then by the
+    /// band in the later exam, in the same order as the
+    /// [labels](BandTransitions::labels).
+    pub fn counts(&self) -> &[Vec<usize>] {
+        &self.counts
+    }
+
+    /// Returns the number of students that moved from the band labeled `from`
+    /// to the band labeled `to`, or `None` if there is no band with any of
+    /// the labels.
+    pub fn count(&self, from: &str, to: &str) -> Option<usize> {
+        let from = self.labels.iter().position(|label| label == from)?;
+        let to = self.labels.iter().position(|label| label == to)?;
+
+        Some(self.counts[from][to])
+    }
+
+    /// Prints the matrix in a well formatted table.
+    pub fn print(&self) {
+        self.table().printstd();
+    }
+
+    fn table(&self) -> Table {
+        let mut table = Table::new();
+
+        let mut titles = vec![Cell::new("From \\ To").style_spec("c")];
+        titles.extend(
+            self.labels
+                .iter()
+                .map(|label| Cell::new(label).style_spec("c")),
+        );
+        titles.push(Cell::new("Total").style_spec("c"));
+        table.set_titles(Row::new(titles));
+
+        for (from, (label, counts)) in self.labels.iter().zip(&self.counts).enumerate() {
+            let mut cells = vec![Cell::new(label).style_spec("b")];
+            cells.extend(counts.iter().enumerate().map(|(to, &count)| {
+                let text = count.to_string();
+                let text = match to.cmp(&from) {
+                    _ if count == 0 => text,
+                    Ordering::Greater => text.green().to_string(),
+                    Ordering::Less => text.red().to_string(),
+                    Ordering::Equal => text,
+                };
+                Cell::new(&text).style_spec("c")
+            }));
+            cells.push(Cell::new(&counts.iter().sum::<usize>().to_string()).style_spec("c"));
+            table.add_row(Row::new(cells));
+        }
+
+        table.set_format(table_format());
+        table
+    }
+}
+
+impl fmt::Display for BandTransitions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.table())
+    }
+}
+
+// The bands are sorted again when deserializing, as they may come in any order
+impl<'de> Deserialize<'de> for GradeScale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bands = Vec::<GradeBand>::deserialize(deserializer)?;
+        Ok(Self::new(
+            bands.into_iter().map(|band| (band.label, band.min_grade)),
+        ))
+    }
+}
@@ -0,0 +1,232 @@
+use std::path::PathBuf;
+
+use indexmap::map::IndexMap;
+
+use crate::exam::{Exam, ExamWarning, GradeScale, GradeUnit, RankMethod, Statistics, Student};
+
+/// Builder used to create an [Exam] with all its settings at once.
+///
+/// Every setter of [Exam] that affects the statistics, like
+/// [set_max_grade](Exam::set_max_grade), recomputes them. Building the exam
+/// computes the statistics exactly once, which is noticeably faster when
+/// creating many exams, e.g. one per section of a course.
+///
+/// A builder can be obtained with [Exam::builder].
+#[derive(Debug, Clone)]
+pub struct ExamBuilder {
+    students: Vec<Student>,
+    title: Option<String>,
+    date: Option<String>,
+    course: Option<String>,
+    term: Option<String>,
+    instructor: Option<String>,
+    max_grade: Option<f32>,
+    infer_max_grade: bool,
+    pass_threshold: Option<f32>,
+    unit: GradeUnit,
+    grade_scale: Option<GradeScale>,
+    question_max_scores: IndexMap<String, f32>,
+    answer_key: IndexMap<String, String>,
+    rank_method: RankMethod,
+    warnings: Vec<ExamWarning>,
+    source: Option<PathBuf>,
+}
+
+impl ExamBuilder {
+    pub(crate) fn new(students: Vec<Student>) -> Self {
+        Self {
+            students,
+            title: None,
+            date: None,
+            course: None,
+            term: None,
+            instructor: None,
+            max_grade: None,
+            infer_max_grade: false,
+            pass_threshold: None,
+            unit: GradeUnit::Points,
+            grade_scale: None,
+            question_max_scores: IndexMap::new(),
+            answer_key: IndexMap::new(),
+            rank_method: RankMethod::Dense,
+            warnings: Vec::new(),
+            source: None,
+        }
+    }
+
+    /// Sets the title of the exam.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the date the exam took place.
+    pub fn date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Sets the code of the course the exam belongs to.
+    pub fn course(mut self, course: impl Into<String>) -> Self {
+        self.course = Some(course.into());
+        self
+    }
+
+    /// Sets the academic term in which the exam took place.
+    pub fn term(mut self, term: impl Into<String>) -> Self {
+        self.term = Some(term.into());
+        self
+    }
+
+    /// Sets the name of the instructor of the exam.
+    pub fn instructor(mut self, instructor: impl Into<String>) -> Self {
+        self.instructor = Some(instructor.into());
+        self
+    }
+
+    /// Sets the maximum achievable grade in the exam. Defaults to 10, or to
+    /// 100 if the grades are percentages.
+    pub fn max_grade(mut self, max_grade: f32) -> Self {
+        self.max_grade = Some(max_grade);
+        self
+    }
+
+    /// Sets whether the maximum grade is inferred from the grades when it's not
+    /// set, instead of defaulting to 10, by rounding the highest grade up to
+    /// the closest common scale: 10, 20, 30, 40, 50, 60 or 100, or the next
+    /// multiple of 100 above that. The inferred grade is reported as an
+    /// [ExamWarning::InferredMaxGrade]. Grades that are percentages always
+    /// have a maximum grade of 100.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, ExamWarning, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 24.5),
+    ///     Student::new("Jose Abad Martínez", 13.0),
+    /// ];
+    ///
+    /// let exam = Exam::builder(students).infer_max_grade(true).build();
+    ///
+    /// assert_eq!(exam.statistics().max_grade(), 30.0);
+    /// assert_eq!(exam.warnings(), &[ExamWarning::InferredMaxGrade(30.0)]);
+    /// ```
+    pub fn infer_max_grade(mut self, infer: bool) -> Self {
+        self.infer_max_grade = infer;
+        self
+    }
+
+    /// Sets the minimum grade needed to pass the exam. Defaults to half the
+    /// maximum grade.
+    pub fn pass_threshold(mut self, pass_grade: f32) -> Self {
+        self.pass_threshold = Some(pass_grade);
+        self
+    }
+
+    /// Sets the unit in which the exam grades are expressed. Defaults to
+    /// [GradeUnit::Points].
+    pub fn unit(mut self, unit: GradeUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Sets the [GradeScale] used to classify the exam grades.
+    pub fn grade_scale(mut self, scale: GradeScale) -> Self {
+        self.grade_scale = Some(scale);
+        self
+    }
+
+    /// Sets how students with the same grade are ranked. Defaults to
+    /// [RankMethod::Dense].
+    pub fn rank_method(mut self, method: RankMethod) -> Self {
+        self.rank_method = method;
+        self
+    }
+
+    /// Sets the maximum achievable score in the given question.
+    pub fn question_max_score(mut self, question: impl Into<String>, max_score: f32) -> Self {
+        self.question_max_scores.insert(question.into(), max_score);
+        self
+    }
+
+    /// Sets the correct option of the given multiple-choice question.
+    pub fn answer_key(mut self, question: impl Into<String>, option: impl Into<String>) -> Self {
+        self.answer_key.insert(question.into(), option.into());
+        self
+    }
+
+    // Sets the file the exam was parsed from
+    pub(crate) fn source(mut self, source: PathBuf) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    // Adds warnings found before the students were created, e.g. while
+    // parsing them
+    pub(crate) fn warnings(mut self, warnings: Vec<ExamWarning>) -> Self {
+        self.warnings.extend(warnings);
+        self
+    }
+
+    /// Creates the exam and computes its statistics.
+    pub fn build(self) -> Exam {
+        let mut students = self.students;
+        let mut warnings = self.warnings;
+        let max_grade = match (self.max_grade, self.unit) {
+            (Some(max_grade), _) => max_grade,
+            (None, GradeUnit::Points) if self.infer_max_grade => {
+                let max_grade = inferred_max_grade(&students);
+                warnings.push(ExamWarning::InferredMaxGrade(max_grade));
+                max_grade
+            }
+            (None, GradeUnit::Points) => 10.0,
+            (None, GradeUnit::Percentage) => 100.0,
+        };
+        let pass_grade = self.pass_threshold.unwrap_or(max_grade / 2.0);
+        let statistics = Statistics::new(&mut students, max_grade, pass_grade, self.rank_method);
+
+        Exam {
+            title: self.title,
+            date: self.date,
+            course: self.course,
+            term: self.term,
+            instructor: self.instructor,
+            max_grade,
+            pass_threshold: self.pass_threshold,
+            unit: self.unit,
+            normalized_scale: None,
+            outlier_marker: None,
+            format_rules: Vec::new(),
+            grade_scale: self.grade_scale,
+            question_max_scores: self.question_max_scores,
+            answer_key: self.answer_key,
+            rank_method: self.rank_method,
+            students,
+            statistics,
+            source: self.source,
+            transformations: Vec::new(),
+            warnings,
+            validate_grades: false,
+            check_names: false,
+            published: false,
+            pinned_standings: None,
+        }
+    }
+}
+
+// Highest grade rounded up to the closest common scale
+fn inferred_max_grade(students: &[Student]) -> f32 {
+    const SCALES: [f32; 7] = [10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 100.0];
+
+    let highest = students
+        .iter()
+        .map(|student| student.grade)
+        .fold(0.0, f32::max);
+
+    SCALES
+        .into_iter()
+        .find(|&scale| highest <= scale)
+        .unwrap_or_else(|| (highest / 100.0).ceil() * 100.0)
+}
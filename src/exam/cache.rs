@@ -0,0 +1,113 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{ParseError, WithPath};
+use crate::exam::digest::{sha256, to_hex};
+use crate::exam::parse::parse_exam_file;
+use crate::exam::{Exam, ParseOptions};
+
+// Version of the format of the snapshots, increased whenever it changes so
+// snapshots in an older format are parsed again instead of misread
+const FORMAT_VERSION: u32 = 1;
+
+// Exam as it was right after being parsed, including its computed statistics
+// and warnings, along with what it was parsed from.
+#[derive(Serialize, Deserialize)]
+struct Snapshot<E> {
+    format: u32,
+    // Snapshots written by other versions of the crate are ignored, as their
+    // statistics may differ
+    version: String,
+    key: String,
+    content_length: u64,
+    content_sha256: String,
+    exam: E,
+}
+
+// Loads the exam from its snapshot in `cache_dir` if the file hasn't changed
+// since the snapshot was taken with the same options. Otherwise the file is
+// parsed and a new snapshot is written.
+pub fn from_file_cached(
+    path: &Path,
+    cache_dir: &Path,
+    options: &ParseOptions,
+) -> Result<Exam, ParseError> {
+    let content = fs::read(path).with_path(path)?;
+    let content_sha256 = to_hex(&sha256(&content));
+    let key = key(path, &content, options);
+    let snapshot_path = cache_dir.join(format!("{key}.json"));
+
+    if let Some(mut exam) = read_snapshot(&snapshot_path, &key, &content, &content_sha256) {
+        exam.source = Some(path.to_owned());
+        return Ok(exam);
+    }
+
+    let exam = parse_exam_file(path, options)?;
+
+    // The cache is only an optimization, so failing to write the snapshot
+    // shouldn't prevent the exam from being loaded
+    let snapshot = Snapshot {
+        format: FORMAT_VERSION,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        key,
+        content_length: content.len() as u64,
+        content_sha256,
+        exam: &exam,
+    };
+    let _ = write_snapshot(&snapshot, cache_dir, &snapshot_path);
+
+    Ok(exam)
+}
+
+// Snapshot at the given path, if it was taken by this version of the crate
+// from the same content and options
+fn read_snapshot(
+    snapshot_path: &Path,
+    key: &str,
+    content: &[u8],
+    content_sha256: &str,
+) -> Option<Exam> {
+    let snapshot: Snapshot<Exam> = serde_json::from_slice(&fs::read(snapshot_path).ok()?).ok()?;
+
+    let matches = snapshot.format == FORMAT_VERSION
+        && snapshot.version == env!("CARGO_PKG_VERSION")
+        && snapshot.key == key
+        && snapshot.content_length == content.len() as u64
+        && snapshot.content_sha256 == content_sha256;
+
+    matches.then_some(snapshot.exam)
+}
+
+fn write_snapshot(
+    snapshot: &Snapshot<&Exam>,
+    cache_dir: &Path,
+    snapshot_path: &Path,
+) -> io::Result<()> {
+    // Write to a temporary file first so other processes never read a
+    // half-written snapshot
+    fs::create_dir_all(cache_dir)?;
+    let temporary_path = snapshot_path.with_extension("tmp");
+    fs::write(&temporary_path, serde_json::to_vec(snapshot)?)?;
+    fs::rename(temporary_path, snapshot_path)
+}
+
+// SHA-256 of the format version, the options, the file name and the content,
+// each of them preceded by its length. The file name is part of the key
+// because the exam title defaults to it and the format depends on it.
+fn key(path: &Path, content: &[u8], options: &ParseOptions) -> String {
+    let options = serde_json::to_vec(options).unwrap_or_default();
+    let file_name = path.file_name().unwrap_or_default().as_encoded_bytes();
+
+    let mut hasher = Sha256::new();
+    hasher.update(FORMAT_VERSION.to_be_bytes());
+    for part in [&options[..], file_name, content] {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+
+    to_hex(&hasher.finalize())
+}
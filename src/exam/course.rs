@@ -0,0 +1,1432 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+use std::ptr;
+use std::time::SystemTime;
+
+use colored::{Color, Colorize};
+use prettytable::{row, Table};
+use serde::Serialize;
+
+use crate::exam::archive::format_utc;
+use crate::exam::statistics::slope;
+use crate::exam::student::serialize_rank;
+use crate::exam::{
+    normalized_name, student_key, table_format, Exam, GradeScale, MatchBy, Statistics, Student,
+};
+
+// Width, in characters, of the bar of each exam in the band chart
+const BAND_CHART_WIDTH: usize = 50;
+
+// Number of buckets of equal width the final grades are split into to compute
+// the overlap of the distributions of two courses
+const OVERLAP_BUCKETS: usize = 10;
+
+// Character and color used to draw each band of the band chart, repeated if
+// there are more bands. Characters tell the bands apart without colors.
+const BAND_STYLES: [(char, Color); 4] = [
+    ('█', Color::Red),
+    ('▓', Color::Yellow),
+    ('▒', Color::Green),
+    ('░', Color::Blue),
+];
+
+/// Series of exams of a course, in chronological order, used to follow the
+/// progress of each student across them.
+///
+/// Students are matched across exams by name, ignoring case, accents and
+/// surrounding whitespace, or by ID, see [set_match_by](Course::set_match_by).
+/// Grades of exams with different maximum grades are rescaled to the
+/// [maximum grade of the course](Course::max_grade) before comparing or
+/// averaging them. Exams with a maximum grade of 0 can't be rescaled, so they
+/// are left out of the changes, averages and final grades.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{Course, Exam, Student, Trend};
+///
+/// let first = Exam::new(vec![
+///     Student::new("Joan Beltrán Peris", 4.5),
+///     Student::new("David Jiménez Hidalgo", 7.9),
+/// ]);
+/// let second = Exam::new(vec![
+///     Student::new("Joan Beltrán Peris", 6.5),
+///     Student::new("David Jiménez Hidalgo", 7.4),
+/// ]);
+///
+/// let mut course = Course::new(vec![first, second]);
+/// course.set_weights(&[1.0, 3.0]);
+///
+/// let history = course.history("joan beltran peris").unwrap();
+///
+/// assert_eq!(history.entries[1].grade, Some(6.5));
+/// assert_eq!(history.entries[1].delta, Some(2.0));
+/// assert_eq!(history.entries[1].rank, Some(2.0));
+/// assert_eq!(history.entries[1].percentile, Some(0.0));
+/// assert_eq!(history.trend(), Some(Trend::Up));
+/// assert_eq!(history.trend_slope, Some(2.0));
+/// assert_eq!(history.average(), Some(6.0));
+///
+/// let json = serde_json::to_string(&history).unwrap();
+/// assert!(json.contains(r#""rank":2,"percentile":0.0"#));
+/// assert!(json.ends_with(r#""trend_slope":2.0}"#));
+///
+/// course.student_report("Joan Beltrán Peris");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Course {
+    exams: Vec<Exam>,
+    weights: Vec<f32>,
+    match_by: MatchBy,
+    aggregation: Aggregation,
+    missing_exams: MissingExams,
+    // Snapshots of each exam, in the order they were taken
+    snapshots: Vec<Vec<ExamSnapshot>>,
+}
+
+/// Copy of an exam of a [Course] taken when it was published, which can't be
+/// changed afterwards, see [Course::publish_exam].
+#[derive(Debug, Clone)]
+pub struct ExamSnapshot {
+    published_at: SystemTime,
+    exam: Exam,
+}
+
+/// Grades, ranks and percentiles of a student across the exams of a
+/// [Course], which can be serialized to pull their full record at once.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StudentHistory {
+    /// Name of the student, as written in the first exam they appear in.
+    pub name: String,
+
+    /// One entry per exam of the course, in the same order.
+    pub entries: Vec<HistoryEntry>,
+
+    /// Least-squares slope of the grades of the student against the position
+    /// of the exams in the course, on the scale of the course, in grade
+    /// points per exam. `None` if they took less than two exams.
+    pub trend_slope: Option<f32>,
+}
+
+/// Result of a student in one exam of a [Course].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryEntry {
+    /// Title of the exam, or its position in the course if it has none.
+    pub exam: String,
+
+    /// Grade of the student, or `None` if they didn't take the exam.
+    pub grade: Option<f32>,
+
+    /// Rank of the student in the exam, see [Student::rank].
+    #[serde(serialize_with = "serialize_rank")]
+    pub rank: Option<f32>,
+
+    /// Percentile of the student in the exam, see [Student::percentile].
+    pub percentile: Option<f32>,
+
+    /// Difference with the grade of the previous exam the student took, on the
+    /// scale of the course.
+    pub delta: Option<f32>,
+
+    /// Weighted average of the grades of the student up to this exam, on the
+    /// scale of the course. Exams the student didn't take are not counted.
+    pub cumulative_average: Option<f32>,
+}
+
+/// Share of the students of each exam of a [Course] in each band of a
+/// [GradeScale], drawn as a percent stacked bar per exam.
+///
+/// The [Display](fmt::Display) implementation renders the chart for the
+/// terminal, followed by a legend of the bands.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BandChart {
+    labels: Vec<String>,
+    rows: Vec<(String, Vec<f32>)>,
+}
+
+/// How the grades of the exams of a [Course] are combined into its final
+/// grades, see [Course::set_aggregation].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Aggregation {
+    /// Grades are rescaled to the maximum grade of the course and averaged as
+    /// they are.
+    #[default]
+    Grades,
+
+    /// The [percentiles](Student::percentile) of each student in the exams
+    /// are averaged and rescaled to the maximum grade of the course, so every
+    /// exam ranks the students in the same way no matter how spread out its
+    /// grades are. Exams the student didn't take count as a percentile of 0
+    /// with [MissingExams::Zero].
+    Percentiles,
+
+    /// The z-scores of each student in the exams, that is, how many standard
+    /// deviations their grade is from the mean, are averaged, so an exam with
+    /// grades very spread out doesn't weigh more than one with grades close
+    /// together. The average is turned back into a grade with the weighted
+    /// average of the means and standard deviations of the exams, on the
+    /// scale of the course, and kept between 0 and its maximum grade.
+    ZScores,
+}
+
+/// Final grade of a student of a [Course] along with their rank in its first
+/// and last exams, see [Course::final_standings].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FinalStanding {
+    /// Name of the student, as written in the first exam they appear in.
+    pub name: String,
+
+    /// Final grade of the student, computed like in [Course::final_grades].
+    pub grade: f32,
+
+    /// Rank of the student in the first exam of the course, or `None` if they
+    /// didn't take it.
+    pub first_rank: Option<f32>,
+
+    /// Rank of the student in the last exam of the course, or `None` if they
+    /// didn't take it.
+    pub last_rank: Option<f32>,
+}
+
+/// Comparison of the final grades of two courses, e.g. the same course in two
+/// different years, see [Course::compare].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CourseComparison {
+    /// Statistics of the final grades of the course.
+    pub current: Statistics,
+
+    /// Statistics of the final grades of the course it was compared to,
+    /// rescaled to the maximum grade of the first course.
+    pub other: Statistics,
+
+    /// Overlap of the distributions of the final grades of both courses,
+    /// ranging from 0, when no bucket of the grade range has students of
+    /// both courses, to 1, when every bucket has the same share of students
+    /// in both. The grade range is split into 10 buckets of equal width.
+    pub overlap: f32,
+}
+
+/// How the final grade of a student that didn't take every exam of a [Course]
+/// is computed, see [Course::set_missing_exams].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingExams {
+    /// Exams the student didn't take count as a 0.
+    #[default]
+    Zero,
+
+    /// Students that didn't take every exam are left out of the final grades,
+    /// so they don't count in the statistics of the course.
+    Exclude,
+
+    /// The weights of the exams the student took are scaled up to make up for
+    /// the ones they didn't take, so the final grade is the weighted average
+    /// of the exams they took.
+    ScaleWeights,
+}
+
+// Document written by Course::to_json
+#[derive(Serialize)]
+struct CourseDocument<'a> {
+    max_grade: f32,
+    exams: Vec<CourseExam<'a>>,
+    final_grades: FinalGrades<'a>,
+    histories: Vec<StudentHistory>,
+}
+
+#[derive(Serialize)]
+struct CourseExam<'a> {
+    title: String,
+    date: Option<&'a str>,
+    weight: f32,
+    max_grade: f32,
+    statistics: &'a Statistics,
+    students: &'a [Student],
+}
+
+#[derive(Serialize)]
+struct FinalGrades<'a> {
+    statistics: &'a Statistics,
+    students: &'a [Student],
+}
+
+/// Direction of the last change in the grades of a student.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trend {
+    Up,
+    Down,
+    Steady,
+}
+
+impl Course {
+    /// Creates a new course from its exams, in chronological order. Every exam
+    /// has a weight of 1. A [snapshot](Course::snapshots) of the exams that
+    /// are already published is taken.
+    pub fn new(exams: impl Into<Vec<Exam>>) -> Self {
+        let exams: Vec<Exam> = exams.into();
+        let weights = vec![1.0; exams.len()];
+        let snapshots = exams
+            .iter()
+            .map(|exam| {
+                exam.is_published()
+                    .then(|| ExamSnapshot::new(exam))
+                    .into_iter()
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            exams,
+            weights,
+            match_by: MatchBy::Name,
+            aggregation: Aggregation::Grades,
+            missing_exams: MissingExams::Zero,
+            snapshots,
+        }
+    }
+
+    /// Sets the weight of each exam in the cumulative average, in the same
+    /// order as the exams. Exams without a given weight keep their current
+    /// one, and so do the exams whose weight is not a finite number greater
+    /// than or equal to 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, Student};
+    ///
+    /// let first = Exam::new(vec![Student::new("Joan Beltrán Peris", 4.0)]);
+    /// let second = Exam::new(vec![Student::new("Joan Beltrán Peris", 8.0)]);
+    ///
+    /// let mut course = Course::new(vec![first, second]);
+    /// course.set_weights(&[f32::NAN, 3.0]);
+    /// assert_eq!(course.final_grades().students()[0].grade, 7.0);
+    ///
+    /// course.set_weights(&[-1.0, f32::INFINITY]);
+    /// assert_eq!(course.final_grades().students()[0].grade, 7.0);
+    /// ```
+    pub fn set_weights(&mut self, weights: &[f32]) {
+        for (current, &weight) in self.weights.iter_mut().zip(weights) {
+            if weight.is_finite() && weight >= 0.0 {
+                *current = weight;
+            }
+        }
+    }
+
+    /// Sets how the students are matched across the exams of the course, by
+    /// name by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, MatchBy, Student};
+    ///
+    /// let mut joan = Student::new("Joan Beltrán Peris", 4.5);
+    /// joan.id = Some("A001".to_string());
+    /// let first = Exam::new(vec![joan.clone()]);
+    ///
+    /// joan.name = "Joan Beltran".to_string();
+    /// joan.grade = 6.5;
+    /// let second = Exam::new(vec![joan]);
+    ///
+    /// let mut course = Course::new(vec![first, second]);
+    /// assert_eq!(course.histories().len(), 2);
+    ///
+    /// course.set_match_by(MatchBy::Id);
+    /// let histories = course.histories();
+    ///
+    /// assert_eq!(histories.len(), 1);
+    /// assert_eq!(histories[0].entries[1].grade, Some(6.5));
+    /// ```
+    pub fn set_match_by(&mut self, match_by: MatchBy) {
+        self.match_by = match_by;
+    }
+
+    /// Sets how the grades of the exams are combined into the final grades,
+    /// see [final_grades](Course::final_grades). Grades are averaged as they
+    /// are by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Aggregation, Course, Exam, Student};
+    ///
+    /// // A tight quiz and a spread-out final
+    /// let quiz = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 6.0),
+    ///     Student::new("David Jiménez Hidalgo", 5.0),
+    /// ]);
+    /// let exam = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 2.0),
+    ///     Student::new("David Jiménez Hidalgo", 8.0),
+    /// ]);
+    ///
+    /// let mut course = Course::new(vec![quiz, exam]);
+    /// let grades = course.final_grades();
+    ///
+    /// assert_eq!(grades.students()[0].grade, 4.0);
+    /// assert_eq!(grades.students()[1].grade, 6.5);
+    ///
+    /// course.set_aggregation(Aggregation::ZScores);
+    /// let grades = course.final_grades();
+    ///
+    /// assert_eq!(grades.students()[0].grade, 5.25);
+    /// assert_eq!(grades.students()[1].grade, 5.25);
+    ///
+    /// course.set_aggregation(Aggregation::Percentiles);
+    /// let grades = course.final_grades();
+    ///
+    /// assert_eq!(grades.students()[0].grade, 5.0);
+    /// assert_eq!(grades.students()[1].grade, 5.0);
+    /// ```
+    pub fn set_aggregation(&mut self, aggregation: Aggregation) {
+        self.aggregation = aggregation;
+    }
+
+    /// Sets how the final grade of the students that didn't take every exam
+    /// of the course is computed, see [final_grades](Course::final_grades).
+    /// Missing exams count as a 0 by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, MissingExams, Student};
+    ///
+    /// let first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.0),
+    ///     Student::new("David Jiménez Hidalgo", 8.0),
+    /// ]);
+    /// let second = Exam::new(vec![Student::new("Joan Beltrán Peris", 6.0)]);
+    ///
+    /// let mut course = Course::new(vec![first, second]);
+    /// course.set_weights(&[1.0, 3.0]);
+    ///
+    /// let grades = course.final_grades();
+    /// assert_eq!(grades.students()[1].grade, 2.0);
+    ///
+    /// course.set_missing_exams(MissingExams::ScaleWeights);
+    /// let grades = course.final_grades();
+    /// assert_eq!(grades.students()[1].grade, 8.0);
+    ///
+    /// course.set_missing_exams(MissingExams::Exclude);
+    /// let grades = course.final_grades();
+    /// assert_eq!(grades.students().len(), 1);
+    /// assert_eq!(grades.statistics().mean(), 5.5);
+    /// ```
+    pub fn set_missing_exams(&mut self, missing_exams: MissingExams) {
+        self.missing_exams = missing_exams;
+    }
+
+    /// Returns the exams of the course.
+    pub fn exams(&self) -> &[Exam] {
+        &self.exams
+    }
+
+    /// Publishes the exam at the given position of the course, see
+    /// [Exam::publish], and takes a [snapshot](Course::snapshots) of it, so
+    /// its results as published can be reported later on even if they are
+    /// corrected. Returns `false` if there's no exam at that position or it
+    /// was already published.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, CurveMethod, Exam, Student};
+    ///
+    /// let exam = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.5),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ]);
+    ///
+    /// let mut course = Course::new(vec![exam]);
+    /// assert!(course.publish_exam(0));
+    /// assert!(!course.publish_exam(0));
+    ///
+    /// course.correct_exam(0, |exam| {
+    ///     exam.apply_curve(CurveMethod::Bonus(0.5)).unwrap();
+    /// });
+    ///
+    /// let snapshots = course.snapshots(0);
+    ///
+    /// assert_eq!(snapshots.len(), 2);
+    /// assert_eq!(snapshots[0].exam().find("joan")[0].grade, 4.5);
+    /// assert_eq!(snapshots[1].exam().find("joan")[0].grade, 5.0);
+    /// assert!(course.exams()[0].is_published());
+    ///
+    /// course.publication_report(0);
+    /// ```
+    pub fn publish_exam(&mut self, index: usize) -> bool {
+        let Some(exam) = self.exams.get_mut(index) else {
+            return false;
+        };
+        if exam.is_published() {
+            return false;
+        }
+
+        exam.publish();
+        self.snapshots[index].push(ExamSnapshot::new(exam));
+        true
+    }
+
+    /// Corrects the exam at the given position of the course. A published
+    /// exam is unpublished, corrected and published again, taking a new
+    /// [snapshot](Course::snapshots) of it, while the earlier snapshots are
+    /// kept as they were. Returns `false` if there's no exam at that
+    /// position.
+    pub fn correct_exam(&mut self, index: usize, correction: impl FnOnce(&mut Exam)) -> bool {
+        let Some(exam) = self.exams.get_mut(index) else {
+            return false;
+        };
+
+        let published = exam.is_published();
+        exam.unpublish();
+        correction(exam);
+        if published {
+            self.publish_exam(index);
+        }
+
+        true
+    }
+
+    /// Returns the snapshots taken of the exam at the given position of the
+    /// course each time it was published, oldest first. Empty if the exam
+    /// was never published or there's no exam at that position.
+    pub fn snapshots(&self, index: usize) -> &[ExamSnapshot] {
+        self.snapshots.get(index).map_or(&[], Vec::as_slice)
+    }
+
+    /// Prints a table comparing the grade of each student in the exam at the
+    /// given position of the course as it was first published with its
+    /// current grade, followed by the mean and pass rate of both. Returns
+    /// `false` if the exam was never published or there's no exam at that
+    /// position.
+    pub fn publication_report(&self, index: usize) -> bool {
+        match self.publication_table(index) {
+            Some(table) => {
+                table.printstd();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Writes the table printed by
+    /// [publication_report](Course::publication_report) to the given writer
+    /// instead of the standard output.
+    pub fn publication_report_to<W: Write>(&self, mut writer: W, index: usize) -> io::Result<bool> {
+        match self.publication_table(index) {
+            Some(table) => {
+                table.print(&mut writer)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn publication_table(&self, index: usize) -> Option<Table> {
+        let snapshot = self.snapshots(index).first()?;
+        let (published, current) = (&snapshot.exam, &self.exams[index]);
+
+        let mut current_grades = HashMap::new();
+        for student in &current.students {
+            if let Some(key) = student_key(student, self.match_by) {
+                current_grades.entry(key).or_insert(student.grade);
+            }
+        }
+
+        let mut table = Table::new();
+        table.set_titles(row![
+            c->format!("Published {}", format_utc(snapshot.published_at, ':')),
+            c->"As Published",
+            c->"Current",
+            c->"Change"
+        ]);
+
+        let mut seen = HashSet::new();
+        for student in &published.students {
+            let key = student_key(student, self.match_by);
+            let grade = key
+                .as_ref()
+                .filter(|key| seen.insert(key.to_string()))
+                .and_then(|key| current_grades.get(key).copied());
+            let change = match grade.map(|grade| grade - student.grade) {
+                Some(change) if change > 0.0 => format!("↑ {change}").green().to_string(),
+                Some(change) if change < 0.0 => format!("↓ {}", -change).red().to_string(),
+                Some(_) => "=".to_string(),
+                None => "-".to_string(),
+            };
+
+            table.add_row(row![
+                student.name,
+                c->student.grade,
+                c->grade.map_or("-".to_string(), |grade| grade.to_string()),
+                c->change
+            ]);
+        }
+
+        let (published, current) = (&published.statistics, &current.statistics);
+        table.add_row(row![b->"Mean", c->published.mean, c->current.mean, c->""]);
+        table.add_row(row![
+            b->"Pass Rate",
+            c->format!("{}%", published.pass_rate),
+            c->format!("{}%", current.pass_rate),
+            c->""
+        ]);
+
+        table.set_format(table_format());
+        Some(table)
+    }
+
+    /// Returns the maximum grade of the course, the one of its first exam with
+    /// a maximum grade greater than 0, or 10 if there is none.
+    pub fn max_grade(&self) -> f32 {
+        self.exams
+            .iter()
+            .find(|exam| is_rescalable(exam))
+            .map_or(10.0, |exam| exam.max_grade)
+    }
+
+    /// Returns the history of every student of the course, in the order they
+    /// first appear in its exams.
+    pub fn histories(&self) -> Vec<StudentHistory> {
+        self.matched_students()
+            .iter()
+            .map(|entries| self.build_history(entries))
+            .collect()
+    }
+
+    /// Returns the history of the student with the given name, or `None` if
+    /// they don't appear in any exam of the course.
+    pub fn history(&self, name: &str) -> Option<StudentHistory> {
+        let normalized = normalized_name(name);
+        let entries = self.matched_students().into_iter().find(|entries| {
+            entries
+                .iter()
+                .flatten()
+                .any(|student| normalized_name(&student.name) == normalized)
+        })?;
+
+        Some(self.build_history(&entries))
+    }
+
+    /// Prints a table with the grade, the rank, the change from the previous
+    /// exam and the cumulative average of the given student in each exam of
+    /// the course. Returns `false` if the student doesn't appear in any exam.
+    pub fn student_report(&self, name: &str) -> bool {
+        match self.history(name) {
+            Some(history) => {
+                history.print();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Writes the table printed by [student_report](Course::student_report)
+    /// to the given writer instead of the standard output.
+    pub fn student_report_to<W: Write>(&self, mut writer: W, name: &str) -> io::Result<bool> {
+        match self.history(name) {
+            Some(history) => {
+                history.table().print(&mut writer)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Returns a chart with the share of the students of each exam in each
+    /// band of the given scale, showing how the band composition of the
+    /// cohort evolved during the course. Grades are rescaled to the maximum
+    /// grade of the course before classifying them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, GradeScale, Student};
+    ///
+    /// let first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.5),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ]);
+    /// let second = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 6.5),
+    ///     Student::new("David Jiménez Hidalgo", 9.4),
+    /// ]);
+    ///
+    /// let course = Course::new(vec![first, second]);
+    /// let chart = course.band_chart(&GradeScale::spanish());
+    ///
+    /// assert_eq!(chart.labels()[0], "Suspenso");
+    /// assert_eq!(chart.rows()[0].1, vec![50.0, 0.0, 50.0, 0.0]);
+    /// assert_eq!(chart.rows()[1].1, vec![0.0, 50.0, 0.0, 50.0]);
+    ///
+    /// chart.print();
+    /// ```
+    pub fn band_chart(&self, scale: &GradeScale) -> BandChart {
+        let max_grade = self.max_grade();
+        let bands = scale.bands();
+
+        let rows = self
+            .exams
+            .iter()
+            .enumerate()
+            .map(|(i, exam)| {
+                let mut counts = vec![0; bands.len()];
+                for student in exam.students.iter().filter(|_| is_rescalable(exam)) {
+                    let grade = student.grade / exam.max_grade * max_grade;
+                    if let Some(band) = scale.band(grade) {
+                        if let Some(position) = bands.iter().position(|b| ptr::eq(b, band)) {
+                            counts[position] += 1;
+                        }
+                    }
+                }
+
+                let total = exam.students.len().max(1) as f32;
+                let shares = counts
+                    .iter()
+                    .map(|&count| count as f32 / total * 100.0)
+                    .collect();
+
+                (exam_name(i, exam), shares)
+            })
+            .collect();
+
+        BandChart {
+            labels: bands.iter().map(|band| band.label.clone()).collect(),
+            rows,
+        }
+    }
+
+    /// Combines the exams of the course into a final exam, where the grade of
+    /// each student is the weighted average of their grades in every exam,
+    /// rescaled to the maximum grade of the course and rounded to two
+    /// decimals, or of their percentiles or z-scores, see
+    /// [set_aggregation](Course::set_aggregation). How the exams a student
+    /// didn't take are handled is set with
+    /// [set_missing_exams](Course::set_missing_exams). The ID, group and email
+    /// of each student are taken from the first exam they appear in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, MissingExams, Student};
+    ///
+    /// let first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.0),
+    ///     Student::new("David Jiménez Hidalgo", 8.0),
+    /// ]);
+    /// let second = Exam::builder(vec![Student::new("Joan Beltrán Peris", 16.0)])
+    ///     .max_grade(20.0)
+    ///     .build();
+    ///
+    /// let mut course = Course::new(vec![first, second]);
+    /// course.set_weights(&[1.0, 3.0]);
+    ///
+    /// let grades = course.final_grades();
+    ///
+    /// assert_eq!(grades.students()[0].grade, 7.0);
+    /// assert_eq!(grades.students()[1].grade, 2.0);
+    ///
+    /// course.set_missing_exams(MissingExams::ScaleWeights);
+    /// let grades = course.final_grades();
+    ///
+    /// assert_eq!(grades.students()[1].grade, 8.0);
+    /// ```
+    ///
+    /// Exams with a maximum grade of 0 are left out:
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, Student};
+    ///
+    /// let ungraded = Exam::builder(vec![Student::new("Joan Beltrán Peris", 0.0)])
+    ///     .max_grade(0.0)
+    ///     .build();
+    /// let graded = Exam::new(vec![Student::new("Joan Beltrán Peris", 6.5)]);
+    ///
+    /// let course = Course::new(vec![ungraded, graded]);
+    /// let grades = course.final_grades();
+    ///
+    /// assert_eq!(course.max_grade(), 10.0);
+    /// assert_eq!(grades.students()[0].grade, 6.5);
+    /// ```
+    pub fn final_grades(&self) -> Exam {
+        let students = self
+            .final_students()
+            .into_iter()
+            .map(|(_, student)| student)
+            .collect::<Vec<Student>>();
+
+        Exam::builder(students)
+            .title("Final grades")
+            .max_grade(self.max_grade())
+            .build()
+    }
+
+    // Final grade of every student of the course along with their entry in
+    // each exam, leaving out the students with missing exams if they are
+    // excluded
+    fn final_students(&self) -> Vec<(Vec<Option<&Student>>, Student)> {
+        let total_weight: f32 = self
+            .exams
+            .iter()
+            .zip(&self.weights)
+            .filter(|(exam, _)| is_rescalable(exam))
+            .map(|(_, weight)| weight)
+            .sum();
+
+        self.matched_students()
+            .into_iter()
+            .filter_map(|entries| {
+                let mut weighted_sum = 0.0;
+                let mut taken_weight = 0.0;
+                for ((student, exam), weight) in entries.iter().zip(&self.exams).zip(&self.weights)
+                {
+                    if !is_rescalable(exam) {
+                        continue;
+                    }
+                    match (student, self.missing_exams) {
+                        (Some(student), _) => {
+                            weighted_sum += weight * self.component(exam, Some(student));
+                            taken_weight += weight;
+                        }
+                        (None, MissingExams::Zero) => {
+                            weighted_sum += weight * self.component(exam, None);
+                        }
+                        (None, MissingExams::Exclude) => return None,
+                        (None, MissingExams::ScaleWeights) => (),
+                    }
+                }
+
+                let weight = match self.missing_exams {
+                    MissingExams::Zero | MissingExams::Exclude => total_weight,
+                    MissingExams::ScaleWeights => taken_weight,
+                };
+                let component = if weight > 0.0 {
+                    weighted_sum / weight
+                } else {
+                    0.0
+                };
+                let grade = self.component_to_grade(component);
+
+                let name = entries
+                    .iter()
+                    .flatten()
+                    .next()
+                    .map(|student| student.name.clone())
+                    .unwrap_or_default();
+                let mut student = Student::new(name, (grade * 100.0).round() / 100.0);
+                for s in entries.iter().flatten() {
+                    student.id = student.id.or_else(|| s.id.clone());
+                    student.group = student.group.or_else(|| s.group.clone());
+                    student.email = student.email.or_else(|| s.email.clone());
+                }
+
+                Some((entries, student))
+            })
+            .collect()
+    }
+
+    /// Returns the final grade of every student of the course, computed like
+    /// in [final_grades](Course::final_grades), along with their rank in the
+    /// first and last exams of the course, sorted by final grade, highest
+    /// first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, Student};
+    ///
+    /// let first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.5),
+    ///     Student::new("Jose Abad Martínez", 6.0),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ]);
+    /// let second = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 9.5),
+    ///     Student::new("Jose Abad Martínez", 5.0),
+    ///     Student::new("David Jiménez Hidalgo", 7.4),
+    /// ]);
+    ///
+    /// let course = Course::new(vec![first, second]);
+    /// let standings = course.final_standings();
+    ///
+    /// assert_eq!(standings[0].name, "David Jiménez Hidalgo");
+    /// assert_eq!(standings[1].name, "Joan Beltrán Peris");
+    /// assert_eq!(standings[1].first_rank, Some(3.0));
+    /// assert_eq!(standings[1].last_rank, Some(1.0));
+    /// assert_eq!(standings[1].movement(), Some(2.0));
+    /// assert_eq!(standings[2].movement(), Some(-1.0));
+    ///
+    /// course.final_table();
+    /// ```
+    pub fn final_standings(&self) -> Vec<FinalStanding> {
+        let rank = |entry: Option<&Option<&Student>>| entry.copied().flatten()?.rank();
+
+        let mut standings: Vec<FinalStanding> = self
+            .final_students()
+            .into_iter()
+            .map(|(entries, student)| FinalStanding {
+                name: student.name,
+                grade: student.grade,
+                first_rank: rank(entries.first()),
+                last_rank: rank(entries.last()),
+            })
+            .collect();
+
+        standings.sort_by(|a, b| b.grade.total_cmp(&a.grade));
+        standings
+    }
+
+    /// Prints a table with the final grade of every student, see
+    /// [final_standings](Course::final_standings), along with their rank in
+    /// the first and last exams and how many positions they moved, the
+    /// students that climbed in green and the ones that fell in red.
+    pub fn final_table(&self) {
+        self.standings_table().printstd();
+    }
+
+    /// Writes the table printed by [final_table](Course::final_table) to the
+    /// given writer instead of the standard output.
+    pub fn final_table_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.standings_table().print(&mut writer)?;
+        Ok(())
+    }
+
+    fn standings_table(&self) -> Table {
+        let format_rank = |rank: Option<f32>| rank.map_or("-".to_string(), |r| r.to_string());
+
+        let mut table = Table::new();
+        table.set_titles(row![
+            c->"Name",
+            c->"Final Grade",
+            c->"First Rank",
+            c->"Last Rank",
+            c->"Movement"
+        ]);
+
+        for standing in self.final_standings() {
+            let movement = match standing.movement() {
+                Some(movement) if movement > 0.0 => format!("↑ {movement}").green().to_string(),
+                Some(movement) if movement < 0.0 => format!("↓ {}", -movement).red().to_string(),
+                Some(_) => "=".to_string(),
+                None => "-".to_string(),
+            };
+
+            table.add_row(row![
+                standing.name,
+                c->standing.grade,
+                c->format_rank(standing.first_rank),
+                c->format_rank(standing.last_rank),
+                c->movement
+            ]);
+        }
+
+        table.set_format(table_format());
+        table
+    }
+
+    /// Compares the final grades of the course, computed like in
+    /// [final_grades](Course::final_grades), with the ones of another course,
+    /// e.g. the same course of the previous year, to review how the course
+    /// evolved. The final grades of the other course are rescaled to the
+    /// maximum grade of this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, Student};
+    ///
+    /// let this_year = Course::new(vec![Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.5),
+    ///     Student::new("David Jiménez Hidalgo", 7.5),
+    /// ])]);
+    /// let last_year = Course::new(vec![Exam::builder(vec![
+    ///     Student::new("Irene Alcántara Campillo", 13.0),
+    ///     Student::new("Jose Abad Martínez", 15.0),
+    /// ])
+    /// .max_grade(20.0)
+    /// .build()]);
+    ///
+    /// let comparison = this_year.compare(&last_year);
+    ///
+    /// assert_eq!(comparison.current.mean(), 6.0);
+    /// assert_eq!(comparison.other.mean(), 7.0);
+    /// assert_eq!(comparison.current.pass_rate(), 50.0);
+    /// assert_eq!(comparison.other.pass_rate(), 100.0);
+    /// assert_eq!(comparison.overlap, 0.5);
+    ///
+    /// comparison.print();
+    /// ```
+    pub fn compare(&self, other: &Course) -> CourseComparison {
+        let current = self.final_grades();
+        let mut other = other.final_grades();
+        if other.max_grade != current.max_grade {
+            other.rescale(
+                current.max_grade,
+                "grades to the maximum grade of the course",
+            );
+        }
+
+        let current_shares = bucket_shares(&current);
+        let other_shares = bucket_shares(&other);
+        let overlap = current_shares
+            .iter()
+            .zip(&other_shares)
+            .map(|(a, b)| a.min(*b))
+            .sum();
+
+        CourseComparison {
+            current: current.statistics,
+            other: other.statistics,
+            overlap,
+        }
+    }
+
+    /// Returns the whole course as a single JSON document, to exchange it with
+    /// other systems. It contains the maximum grade of the course, every exam
+    /// with its weight, statistics and students, the final grades, computed
+    /// like [final_grades](Course::final_grades),
+    /// and the [history](Course::history) of every student. Fails if the
+    /// document can't be serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Course, Exam, Student};
+    ///
+    /// let first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.5),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ]);
+    /// let second = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 6.5),
+    ///     Student::new("David Jiménez Hidalgo", 7.4),
+    /// ]);
+    ///
+    /// let mut course = Course::new(vec![first, second]);
+    /// course.set_weights(&[1.0, 3.0]);
+    ///
+    /// let json: serde_json::Value = serde_json::from_str(&course.to_json().unwrap()).unwrap();
+    ///
+    /// assert_eq!(json["exams"][1]["title"], "Exam 2");
+    /// assert_eq!(json["exams"][1]["weight"], 3.0);
+    /// assert_eq!(json["final_grades"]["students"][1]["grade"], 7.53);
+    /// assert_eq!(json["histories"][0]["name"], "Joan Beltrán Peris");
+    /// ```
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let final_grades = self.final_grades();
+
+        let document = CourseDocument {
+            max_grade: self.max_grade(),
+            exams: self
+                .exams
+                .iter()
+                .zip(&self.weights)
+                .enumerate()
+                .map(|(i, (exam, &weight))| CourseExam {
+                    title: exam_name(i, exam),
+                    date: exam.date.as_deref(),
+                    weight,
+                    max_grade: exam.max_grade,
+                    statistics: &exam.statistics,
+                    students: &exam.students,
+                })
+                .collect(),
+            final_grades: FinalGrades {
+                statistics: &final_grades.statistics,
+                students: &final_grades.students,
+            },
+            histories: self.histories(),
+        };
+
+        serde_json::to_string_pretty(&document)
+    }
+
+    // Every student of the course, in the order they first appear in its
+    // exams, with their entry in each exam. The key of each student is
+    // computed once and every exam is indexed by it, so matching a student
+    // doesn't go through the students of every exam.
+    fn matched_students(&self) -> Vec<Vec<Option<&Student>>> {
+        let keys: Vec<Vec<Option<String>>> = self
+            .exams
+            .iter()
+            .map(|exam| {
+                exam.students
+                    .iter()
+                    .map(|student| student_key(student, self.match_by))
+                    .collect()
+            })
+            .collect();
+
+        let index: Vec<HashMap<&str, &Student>> = self
+            .exams
+            .iter()
+            .zip(&keys)
+            .map(|(exam, keys)| {
+                let mut students = HashMap::new();
+                for (student, key) in exam.students.iter().zip(keys) {
+                    if let Some(key) = key {
+                        students.entry(key.as_str()).or_insert(student);
+                    }
+                }
+                students
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut matched = Vec::new();
+        for (i, (exam, keys)) in self.exams.iter().zip(&keys).enumerate() {
+            for (student, key) in exam.students.iter().zip(keys) {
+                let entries = match key {
+                    Some(key) if seen.insert(key.as_str()) => index
+                        .iter()
+                        .map(|students| students.get(key.as_str()).copied())
+                        .collect(),
+                    Some(_) => continue,
+
+                    // Students without a key only match themselves
+                    None => (0..self.exams.len())
+                        .map(|j| (j == i).then_some(student))
+                        .collect(),
+                };
+                matched.push(entries);
+            }
+        }
+
+        matched
+    }
+
+    // Value of the student in the exam averaged into their final grade, as
+    // given by the aggregation. Students that didn't take the exam count as a
+    // grade of 0.
+    fn component(&self, exam: &Exam, student: Option<&Student>) -> f32 {
+        let grade = student.map_or(0.0, |student| student.grade);
+        match self.aggregation {
+            Aggregation::Grades => grade / exam.max_grade * self.max_grade(),
+            Aggregation::Percentiles => {
+                let percentile = student.and_then(Student::percentile).unwrap_or(0.0);
+                percentile / 100.0 * self.max_grade()
+            }
+            Aggregation::ZScores => {
+                let statistics = &exam.statistics;
+                if statistics.std_dev > 0.0 {
+                    (grade - statistics.mean) / statistics.std_dev
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    // Final grade from the weighted average of the components of a student
+    fn component_to_grade(&self, component: f32) -> f32 {
+        if self.aggregation != Aggregation::ZScores {
+            return component;
+        }
+
+        // Weighted average of the mean and standard deviation of the exams on
+        // the scale of the course
+        let (mut mean, mut std_dev, mut total_weight) = (0.0, 0.0, 0.0);
+        for (exam, weight) in self.exams.iter().zip(&self.weights) {
+            if is_rescalable(exam) {
+                let factor = self.max_grade() / exam.max_grade;
+                mean += weight * exam.statistics.mean * factor;
+                std_dev += weight * exam.statistics.std_dev * factor;
+                total_weight += weight;
+            }
+        }
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+
+        let grade = (mean + component * std_dev) / total_weight;
+        grade.clamp(0.0, self.max_grade())
+    }
+
+    // History of a student from their entry in each exam
+    fn build_history(&self, students: &[Option<&Student>]) -> StudentHistory {
+        let max_grade = self.max_grade();
+
+        let mut previous: Option<f32> = None;
+        let mut points = Vec::new();
+        let mut weighted_sum = 0.0;
+        let mut total_weight = 0.0;
+
+        let entries = self
+            .exams
+            .iter()
+            .zip(&self.weights)
+            .zip(students)
+            .enumerate()
+            .map(|(i, ((exam, &weight), student))| {
+                let grade = student.map(|student| student.grade);
+
+                // Grade on the scale of the course
+                let scaled = grade
+                    .filter(|_| is_rescalable(exam))
+                    .map(|grade| grade / exam.max_grade * max_grade);
+                let delta = scaled
+                    .zip(previous)
+                    .map(|(grade, previous)| grade - previous);
+                if let Some(scaled) = scaled {
+                    previous = Some(scaled);
+                    points.push((i as f64, scaled as f64));
+                    weighted_sum += weight * scaled;
+                    total_weight += weight;
+                }
+
+                HistoryEntry {
+                    exam: exam_name(i, exam),
+                    grade,
+                    rank: student.and_then(|student| student.rank()),
+                    percentile: student.and_then(|student| student.percentile()),
+                    delta,
+                    cumulative_average: (total_weight > 0.0).then(|| weighted_sum / total_weight),
+                }
+            })
+            .collect();
+
+        let name = students
+            .iter()
+            .flatten()
+            .next()
+            .map(|student| student.name.clone())
+            .unwrap_or_default();
+
+        StudentHistory {
+            name,
+            entries,
+            trend_slope: slope(&points).map(|slope| slope as f32),
+        }
+    }
+}
+
+impl StudentHistory {
+    /// Returns the direction of the change between the last two exams the
+    /// student took, or `None` if they took less than two.
+    pub fn trend(&self) -> Option<Trend> {
+        let delta = self.entries.iter().rev().find_map(|entry| entry.delta)?;
+
+        Some(if delta > 0.0 {
+            Trend::Up
+        } else if delta < 0.0 {
+            Trend::Down
+        } else {
+            Trend::Steady
+        })
+    }
+
+    /// Returns the weighted average of the grades of the student across the
+    /// whole course, or `None` if they didn't take any exam.
+    pub fn average(&self) -> Option<f32> {
+        self.entries
+            .iter()
+            .rev()
+            .find_map(|entry| entry.cumulative_average)
+    }
+
+    /// Prints the history of the student in a well formatted table.
+    pub fn print(&self) {
+        self.table().printstd();
+    }
+
+    fn table(&self) -> Table {
+        let format_grade = |grade: Option<f32>| grade.map_or("-".to_string(), |g| g.to_string());
+
+        let mut table = Table::new();
+        table.set_titles(row![Fc->self.name, c->"Grade", c->"Rank", c->"Change", c->"Average"]);
+
+        for entry in &self.entries {
+            let change = match entry.delta {
+                Some(delta) if delta > 0.0 => format!("↑ {delta}"),
+                Some(delta) if delta < 0.0 => format!("↓ {}", -delta),
+                Some(_) => "=".to_string(),
+                None => "-".to_string(),
+            };
+
+            table.add_row(row![
+                entry.exam,
+                c->format_grade(entry.grade),
+                c->format_grade(entry.rank),
+                c->change,
+                c->format_grade(entry.cumulative_average)
+            ]);
+        }
+
+        table.set_format(table_format());
+        table
+    }
+}
+
+impl FinalStanding {
+    /// Returns how many positions the student climbed from the first to the
+    /// last exam of the course, negative if they fell, or `None` if they
+    /// didn't take both.
+    pub fn movement(&self) -> Option<f32> {
+        Some(self.first_rank? - self.last_rank?)
+    }
+}
+
+impl CourseComparison {
+    /// Prints the comparison in a well formatted table, with the difference
+    /// of each statistic between both courses.
+    pub fn print(&self) {
+        self.table().printstd();
+    }
+
+    /// Writes the table printed by [print](CourseComparison::print) to the
+    /// given writer instead of the standard output.
+    pub fn print_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.table().print(&mut writer)?;
+        Ok(())
+    }
+
+    fn table(&self) -> Table {
+        let mut table = Table::new();
+        table.set_titles(row![c->"", c->"Course", c->"Other Course", c->"Difference"]);
+
+        let (current, other) = (&self.current, &self.other);
+        let rows = [
+            (
+                "Total Students",
+                current.total_students as f32,
+                other.total_students as f32,
+            ),
+            ("Mean", current.mean, other.mean),
+            ("Median", current.median, other.median),
+            ("Standard Deviation", current.std_dev, other.std_dev),
+            ("Pass Rate", current.pass_rate, other.pass_rate),
+        ];
+        for (name, current, other) in rows {
+            let difference = current - other;
+            let difference = if difference < 0.0 {
+                difference.to_string().red()
+            } else {
+                format!("+{difference}").green()
+            };
+            table.add_row(row![b->name, c->current, c->other, c->difference]);
+        }
+        table.add_row(row![
+            b->"Distribution Overlap",
+            H3c->format!("{}%", (self.overlap * 100.0).round())
+        ]);
+
+        table.set_format(table_format());
+        table
+    }
+}
+
+impl ExamSnapshot {
+    fn new(exam: &Exam) -> Self {
+        Self {
+            published_at: SystemTime::now(),
+            exam: exam.clone(),
+        }
+    }
+
+    /// Returns when the snapshot was taken.
+    pub fn published_at(&self) -> SystemTime {
+        self.published_at
+    }
+
+    /// Returns the exam as it was when the snapshot was taken.
+    pub fn exam(&self) -> &Exam {
+        &self.exam
+    }
+}
+
+impl BandChart {
+    /// Returns the labels of the bands, from the lowest to the highest.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Returns the name of each exam with the percentage of its students in
+    /// each band, in the same order as the [labels](BandChart::labels).
+    /// Students with a grade lower than the lowest band are not in any band.
+    pub fn rows(&self) -> &[(String, Vec<f32>)] {
+        &self.rows
+    }
+
+    /// Prints the chart for the terminal.
+    pub fn print(&self) {
+        println!("{self}");
+    }
+}
+
+impl fmt::Display for BandChart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_width = self
+            .rows
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (name, shares) in &self.rows {
+            write!(f, "{name:<name_width$} │")?;
+
+            // Segments end where the cumulative share does, so rounding never
+            // makes the bar longer than the chart
+            let mut cumulative = 0.0;
+            let mut drawn = 0;
+            for (i, share) in shares.iter().enumerate() {
+                cumulative += share;
+                let end = (cumulative / 100.0 * BAND_CHART_WIDTH as f32).round() as usize;
+                let (symbol, color) = BAND_STYLES[i % BAND_STYLES.len()];
+
+                let segment = symbol.to_string().repeat(end.saturating_sub(drawn));
+                write!(f, "{}", segment.color(color))?;
+                drawn = drawn.max(end);
+            }
+            write!(f, "{}│", " ".repeat(BAND_CHART_WIDTH.saturating_sub(drawn)))?;
+
+            for share in shares {
+                write!(f, " {share:.0}%")?;
+            }
+            writeln!(f)?;
+        }
+
+        for (i, label) in self.labels.iter().enumerate() {
+            let (symbol, color) = BAND_STYLES[i % BAND_STYLES.len()];
+            if i > 0 {
+                write!(f, "  ")?;
+            }
+            write!(f, "{} {label}", symbol.to_string().color(color))?;
+        }
+
+        Ok(())
+    }
+}
+
+// Whether the grades of the exam can be rescaled to the maximum grade of the
+// course, which a maximum grade of 0 would turn into infinite or NaN
+fn is_rescalable(exam: &Exam) -> bool {
+    exam.max_grade > 0.0 && exam.max_grade.is_finite()
+}
+
+// Share of the students of the exam in each of the buckets of equal width of
+// its grade range
+fn bucket_shares(exam: &Exam) -> [f32; OVERLAP_BUCKETS] {
+    let mut shares = [0.0; OVERLAP_BUCKETS];
+    if exam.students.is_empty() || !is_rescalable(exam) {
+        return shares;
+    }
+
+    let share = 1.0 / exam.students.len() as f32;
+    for student in &exam.students {
+        let position = (student.grade / exam.max_grade).clamp(0.0, 1.0);
+        let bucket = (position * OVERLAP_BUCKETS as f32) as usize;
+        shares[bucket.min(OVERLAP_BUCKETS - 1)] += share;
+    }
+
+    shares
+}
+
+// Title of the exam, or its position in the course if it has none
+fn exam_name(i: usize, exam: &Exam) -> String {
+    exam.title
+        .clone()
+        .unwrap_or_else(|| format!("Exam {}", i + 1))
+}
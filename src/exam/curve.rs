@@ -0,0 +1,108 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::exam::Student;
+
+/// Transformation applied to the grades of an exam to curve them, see
+/// [Exam::apply_curve](crate::exam::Exam::apply_curve).
+///
+/// Curved grades are always clamped between 0 and the maximum grade of the
+/// exam.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CurveMethod {
+    /// Scales every grade by the same factor so the highest grade of the exam
+    /// becomes the given grade.
+    ScaleHighestTo(f32),
+
+    /// Adds the given number of points to every grade.
+    Bonus(f32),
+
+    /// Shifts and stretches the grades so their mean and standard deviation
+    /// become the given ones, keeping the position of each student relative to
+    /// the rest.
+    Normalize { mean: f32, std_dev: f32 },
+}
+
+impl CurveMethod {
+    /// Returns `true` if every parameter of the curve is a finite number and
+    /// the standard deviation of [Normalize](CurveMethod::Normalize) is not
+    /// negative. Invalid curves are not applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::CurveMethod;
+    ///
+    /// assert!(CurveMethod::Bonus(1.0).is_valid());
+    /// assert!(!CurveMethod::Normalize {
+    ///     mean: f32::NAN,
+    ///     std_dev: 1.0
+    /// }
+    /// .is_valid());
+    /// assert!(!CurveMethod::Normalize {
+    ///     mean: 6.0,
+    ///     std_dev: -1.0
+    /// }
+    /// .is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            CurveMethod::ScaleHighestTo(grade) => grade.is_finite(),
+            CurveMethod::Bonus(points) => points.is_finite(),
+            CurveMethod::Normalize { mean, std_dev } => {
+                mean.is_finite() && std_dev.is_finite() && std_dev >= 0.0
+            }
+        }
+    }
+}
+
+impl fmt::Display for CurveMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveMethod::ScaleHighestTo(grade) => write!(f, "scaled highest grade to {grade}"),
+            CurveMethod::Bonus(points) => write!(f, "added {points} bonus points"),
+            CurveMethod::Normalize { mean, std_dev } => {
+                write!(
+                    f,
+                    "normalized to mean {mean} and standard deviation {std_dev}"
+                )
+            }
+        }
+    }
+}
+
+// Applies the curve to the grades of the students, given the current mean and
+// standard deviation of the grades.
+pub fn apply_curve(
+    students: &mut [Student],
+    method: CurveMethod,
+    max_grade: f32,
+    mean: f32,
+    std_dev: f32,
+) {
+    let highest_grade = students.iter().map(|s| s.grade).fold(0.0, f32::max);
+
+    let curve = |grade: f32| match method {
+        CurveMethod::ScaleHighestTo(target) if highest_grade > 0.0 => {
+            grade * target / highest_grade
+        }
+        CurveMethod::ScaleHighestTo(_) => grade,
+        CurveMethod::Bonus(points) => grade + points,
+        CurveMethod::Normalize {
+            mean: target_mean,
+            std_dev: target_std_dev,
+        } if std_dev > 0.0 => target_mean + (grade - mean) / std_dev * target_std_dev,
+
+        // Every student has the same grade, so they all get the target mean
+        CurveMethod::Normalize { mean, .. } => mean,
+    };
+
+    // A negative or NaN maximum grade clamps every grade to 0, instead of
+    // making `clamp` panic
+    let max_grade = max_grade.max(0.0);
+    for student in students {
+        student.grade = curve(student.grade).clamp(0.0, max_grade);
+    }
+}
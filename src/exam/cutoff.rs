@@ -0,0 +1,67 @@
+use std::cmp::Ordering;
+
+use crate::exam::Student;
+
+/// Size of the group of best students to select, e.g. for a scholarship.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Top {
+    /// A fixed number of students.
+    Students(usize),
+
+    /// A percentage of the students, ranging from 0 to 100. The number of
+    /// students is rounded up.
+    Percent(f32),
+}
+
+/// Lowest grade needed to be among the best students of an exam.
+#[derive(Debug, Clone)]
+pub struct Cutoff<'a> {
+    /// Minimum grade needed to be in the selected group.
+    pub grade: f32,
+
+    /// Students with a grade strictly greater than the cutoff, which are
+    /// always in the selected group. Sorted by grade, highest first.
+    pub above: Vec<&'a Student>,
+
+    /// Students with exactly the cutoff grade. If there are more of them than
+    /// `spots_left`, the tie has to be broken by other means.
+    pub tied: Vec<&'a Student>,
+
+    /// Number of spots of the group left for the tied students.
+    pub spots_left: usize,
+}
+
+pub fn cutoff(students: &[Student], top: Top) -> Option<Cutoff<'_>> {
+    let size = group_size(top, students.len());
+    if size == 0 {
+        return None;
+    }
+
+    let mut sorted: Vec<&Student> = students.iter().collect();
+    sorted.sort_by(|a, b| b.grade.partial_cmp(&a.grade).unwrap_or(Ordering::Equal));
+
+    let grade = sorted[size - 1].grade;
+    let (above, rest): (Vec<&Student>, Vec<&Student>) =
+        sorted.into_iter().partition(|s| s.grade > grade);
+    let tied = rest.into_iter().filter(|s| s.grade == grade).collect();
+    let spots_left = size - above.len();
+
+    Some(Cutoff {
+        grade,
+        above,
+        tied,
+        spots_left,
+    })
+}
+
+// Number of students in the group, never greater than the number of students
+fn group_size(top: Top, total_students: usize) -> usize {
+    let size = match top {
+        Top::Students(size) => size,
+        Top::Percent(percent) => {
+            (percent.clamp(0.0, 100.0) / 100.0 * total_students as f32).ceil() as usize
+        }
+    };
+
+    size.min(total_students)
+}
@@ -0,0 +1,22 @@
+// SHA-256 digests, used to key the cached snapshots of the exams, and
+// HMAC-SHA256 (RFC 2104), used to compute the anonymous IDs of the students
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+// SHA-256 digest of the data
+pub(crate) fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+// HMAC-SHA256 of the message with the given key
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+// Lowercase hexadecimal representation of the bytes
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
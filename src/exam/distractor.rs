@@ -0,0 +1,76 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::exam::Student;
+
+/// Wrong options chosen in a multiple-choice question of an exam, see
+/// [Exam::distractor_analysis](crate::exam::Exam::distractor_analysis).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuestionDistractors {
+    /// Name of the question.
+    pub name: String,
+
+    /// Correct option of the question, as given by the answer key.
+    pub key: String,
+
+    /// Wrong options chosen by at least one student, sorted by option.
+    pub distractors: Vec<Distractor>,
+}
+
+/// Wrong option of a multiple-choice question, along with how many students of
+/// each ability quartile chose it. A useful distractor is chosen more often by
+/// the students with the lowest grades than by the ones with the highest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Distractor {
+    /// The option, e.g. `"C"`.
+    pub option: String,
+
+    /// Number of students that chose the option in each
+    /// [quartile](Student::quartile) of the exam, from the one with the lowest
+    /// grades to the one with the highest.
+    pub by_quartile: [u32; 4],
+}
+
+impl Distractor {
+    /// Returns the number of students that chose the option.
+    pub fn total(&self) -> u32 {
+        self.by_quartile.iter().sum()
+    }
+}
+
+// Wrong options chosen in every question of the answer key, in the order of
+// the key. Students without a quartile are not counted.
+pub fn distractor_analysis(
+    students: &[Student],
+    answer_key: &IndexMap<String, String>,
+) -> Vec<QuestionDistractors> {
+    answer_key
+        .iter()
+        .map(|(question, key)| {
+            let mut counts: IndexMap<&str, [u32; 4]> = IndexMap::new();
+            for student in students {
+                let (Some(option), Some(quartile)) =
+                    (student.answers.get(question), student.quartile())
+                else {
+                    continue;
+                };
+                if option != key {
+                    counts.entry(option).or_default()[quartile as usize - 1] += 1;
+                }
+            }
+            counts.sort_keys();
+
+            QuestionDistractors {
+                name: question.clone(),
+                key: key.clone(),
+                distractors: counts
+                    .into_iter()
+                    .map(|(option, by_quartile)| Distractor {
+                        option: option.to_string(),
+                        by_quartile,
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,543 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+
+use indexmap::map::IndexMap;
+use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::ParseError;
+use crate::exam::export::csv_field;
+use crate::exam::parse::{build_exam, parse_content, INPUT_PATH};
+use crate::exam::{Exam, FileFormat, GradeUnit, LimitExceeded, Limits, ParseOptions};
+
+/// Content of an exam file, independent of its format.
+///
+/// Every supported format is parsed into an `ExamDocument` before building
+/// the [Exam], and exams are written back to any format through it, so the
+/// same exam is read and written the same way in all of them. See
+/// [exms](crate) for the meaning of each field.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{Exam, ExamDocument, FileFormat, Student};
+///
+/// let students = &[
+///     Student::new("Joan Beltrán Peris", 4.6),
+///     Student::new("Jose Abad Martínez", 3.6),
+///     Student::new("David Jiménez Hidalgo", 7.94),
+/// ];
+///
+/// let mut exam = Exam::new(students);
+/// exam.set_title("Econometrics").unwrap();
+///
+/// let document = exam.to_document();
+///
+/// for format in [FileFormat::Json, FileFormat::Toml, FileFormat::Yaml] {
+///     let content = document.to_string(format).unwrap();
+///     let parsed = ExamDocument::from_bytes(format, content.as_bytes()).unwrap();
+///
+///     assert_eq!(parsed, document);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct ExamDocument {
+    /// Details of the exam, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<ExamDetails>,
+
+    /// Entry of each student by their name, in the order of the file.
+    pub students: IndexMap<String, StudentEntry>,
+
+    /// Names that appear more than once in the file, in the order their
+    /// repetitions were found. Only the last entry of each of them is kept in
+    /// [students](ExamDocument::students).
+    #[serde(skip)]
+    pub duplicates: Vec<String>,
+}
+
+// Document as read from the content of a file, with the names of the students
+// borrowed from the content whenever the format allows it, e.g. JSON names
+// without escape sequences or CSV lines without quotes. Names are only
+// allocated once the exam is built, see `build_exam`.
+pub(crate) struct BorrowedDocument<'a> {
+    pub details: Option<ExamDetails>,
+    pub students: IndexMap<Cow<'a, str>, StudentEntry>,
+    pub duplicates: Vec<String>,
+}
+
+// Students of a document, keeping track of the repeated students that would
+// otherwise silently overwrite each other
+#[derive(Default)]
+pub(crate) struct StudentMap<'a> {
+    pub students: IndexMap<Cow<'a, str>, StudentEntry>,
+    pub duplicates: Vec<String>,
+    max_students: Option<usize>,
+    max_name_length: Option<usize>,
+}
+
+// Name of a student, borrowed from the content being deserialized if possible
+struct Name<'a>(Cow<'a, str>);
+
+// Reads a document checking the limits on its students while they are read,
+// so huge files are rejected as soon as they exceed them. The exceeded limit
+// is stored in `exceeded`, as the deserializers only keep the message of
+// custom errors.
+pub(crate) struct DocumentSeed<'a> {
+    pub limits: &'a Limits,
+    pub exceeded: &'a Cell<Option<LimitExceeded>>,
+}
+
+struct StudentMapSeed<'a> {
+    limits: &'a Limits,
+    exceeded: &'a Cell<Option<LimitExceeded>>,
+}
+
+/// The `details` field of an [ExamDocument].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExamDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_number"
+    )]
+    pub max_grade: Option<f32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_number"
+    )]
+    pub pass_grade: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<GradeUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub course: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructor: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_numbers"
+    )]
+    pub questions: Option<IndexMap<String, f32>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_numbers"
+    )]
+    pub scale: Option<IndexMap<String, f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer_key: Option<IndexMap<String, String>>,
+}
+
+/// Value of a student in an [ExamDocument], either their grade or a table
+/// with their grade or their scores in each question, and optionally the
+/// options they chose in multiple-choice questions, their ID, group and
+/// email.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    untagged,
+    expecting = "a grade or a table with a `grade` or `questions`, and optionally `answers`, \
+                 an `id`, `group` or `email`"
+)]
+pub enum StudentEntry {
+    Grade(#[serde(serialize_with = "serialize_number")] f32),
+    Detailed(Box<StudentRecord>),
+}
+
+/// Grade or scores in each question of a student, along with their optional
+/// metadata. If no grade is given, the grade is the sum of the scores.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StudentRecord {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_number"
+    )]
+    pub grade: Option<f32>,
+    #[serde(
+        default,
+        skip_serializing_if = "IndexMap::is_empty",
+        serialize_with = "serialize_numbers"
+    )]
+    pub questions: IndexMap<String, f32>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub answers: IndexMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl ExamDocument {
+    /// Parses the content of an exam file in the given format, without
+    /// building the exam.
+    pub fn from_bytes(format: FileFormat, bytes: &[u8]) -> Result<Self, ParseError> {
+        parse_content(format, bytes, &Limits::default())
+            .map(ExamDocument::from)
+            .map_err(|kind| ParseError::new(kind, INPUT_PATH))
+    }
+
+    /// Builds the exam described by the document and computes its statistics.
+    /// Fails if any of its numbers is not finite.
+    pub fn into_exam(self) -> Result<Exam, ParseError> {
+        build_exam(self.into(), None, &ParseOptions::default())
+            .map_err(|kind| ParseError::new(kind, INPUT_PATH))
+    }
+
+    /// Writes the document in the given format.
+    ///
+    /// CSV files can only contain the name and the grade of each student, so
+    /// the details and the question scores are left out. Writing Excel
+    /// workbooks is not supported.
+    pub fn write<W: Write>(&self, mut writer: W, format: FileFormat) -> io::Result<()> {
+        match format {
+            FileFormat::Csv => {
+                writeln!(writer, "name,grade")?;
+                for (name, entry) in &self.students {
+                    writeln!(writer, "{},{}", csv_field(name), entry.grade())?;
+                }
+            }
+            #[cfg(feature = "xlsx")]
+            FileFormat::Xlsx => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "writing Excel workbooks is not supported",
+                ))
+            }
+            _ => writer.write_all(self.to_string(format)?.as_bytes())?,
+        }
+
+        writer.flush()
+    }
+
+    /// Returns the document written in the given format, see
+    /// [write](ExamDocument::write).
+    pub fn to_string(&self, format: FileFormat) -> io::Result<String> {
+        let content = match format {
+            FileFormat::Toml => toml::to_string(self).map_err(io::Error::other)?,
+            FileFormat::Json => serde_json::to_string_pretty(self)?,
+            FileFormat::Yaml => serde_yaml::to_string(self).map_err(io::Error::other)?,
+            _ => {
+                let mut content = Vec::new();
+                self.write(&mut content, format)?;
+                String::from_utf8(content).map_err(io::Error::other)?
+            }
+        };
+
+        Ok(content)
+    }
+}
+
+impl<'a> StudentMap<'a> {
+    pub fn with_limits(limits: &Limits) -> Self {
+        Self {
+            max_students: limits.max_students,
+            max_name_length: limits.max_name_length,
+            ..Default::default()
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        name: impl Into<Cow<'a, str>>,
+        entry: StudentEntry,
+    ) -> Result<(), LimitExceeded> {
+        let name = name.into();
+        if let Some(max) = self.max_name_length {
+            if name.chars().count() > max {
+                return Err(LimitExceeded::NameLength { max });
+            }
+        }
+
+        if self.students.contains_key(&name) {
+            self.duplicates.push(name.to_string());
+        } else if let Some(max) = self.max_students {
+            if self.students.len() >= max {
+                return Err(LimitExceeded::Students { max });
+            }
+        }
+        self.students.insert(name, entry);
+
+        Ok(())
+    }
+}
+
+impl From<BorrowedDocument<'_>> for ExamDocument {
+    fn from(document: BorrowedDocument<'_>) -> Self {
+        Self {
+            details: document.details,
+            students: owned_names(document.students),
+            duplicates: document.duplicates,
+        }
+    }
+}
+
+impl From<ExamDocument> for BorrowedDocument<'static> {
+    fn from(document: ExamDocument) -> Self {
+        Self {
+            details: document.details,
+            students: document
+                .students
+                .into_iter()
+                .map(|(name, entry)| (Cow::Owned(name), entry))
+                .collect(),
+            duplicates: document.duplicates,
+        }
+    }
+}
+
+// Same map, with the names that were borrowed allocated
+fn owned_names(students: IndexMap<Cow<'_, str>, StudentEntry>) -> IndexMap<String, StudentEntry> {
+    students
+        .into_iter()
+        .map(|(name, entry)| (name.into_owned(), entry))
+        .collect()
+}
+
+impl<'de> Deserialize<'de> for ExamDocument {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let seed = DocumentSeed {
+            limits: &Limits::default(),
+            exceeded: &Cell::new(None),
+        };
+
+        seed.deserialize(deserializer).map(ExamDocument::from)
+    }
+}
+
+impl<'de> Deserialize<'de> for Name<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+struct NameVisitor;
+
+impl<'de> Visitor<'de> for NameVisitor {
+    type Value = Name<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the name of a student")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, name: &'de str) -> Result<Name<'de>, E> {
+        Ok(Name(Cow::Borrowed(name)))
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Name<'de>, E> {
+        Ok(Name(Cow::Owned(name.to_string())))
+    }
+
+    fn visit_string<E: de::Error>(self, name: String) -> Result<Name<'de>, E> {
+        Ok(Name(Cow::Owned(name)))
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for StudentMapSeed<'_> {
+    type Value = StudentMap<'de>;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<StudentMap<'de>, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StudentMapSeed<'_> {
+    type Value = StudentMap<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a table with the entry of each student by their name")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<StudentMap<'de>, A::Error> {
+        let mut students = StudentMap::with_limits(self.limits);
+        while let Some((Name(name), entry)) = map.next_entry()? {
+            if let Err(limit) = students.insert(name, entry) {
+                let err = de::Error::custom(&limit);
+                self.exceeded.set(Some(limit));
+                return Err(err);
+            }
+        }
+
+        Ok(students)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for DocumentSeed<'_> {
+    type Value = BorrowedDocument<'de>;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<BorrowedDocument<'de>, D::Error> {
+        const FIELDS: &[&str] = &["details", "students"];
+
+        deserializer.deserialize_struct("ExamDocument", FIELDS, self)
+    }
+}
+
+// Reads the `details` and the `students` of the document, rejecting unknown and
+// repeated fields like a derived `Deserialize` would
+impl<'de> Visitor<'de> for DocumentSeed<'_> {
+    type Value = BorrowedDocument<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a table with the `students` of the exam and its optional `details`"
+        )
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<BorrowedDocument<'de>, A::Error> {
+        let mut details = None;
+        let mut students = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "details" if details.is_some() => {
+                    return Err(de::Error::duplicate_field("details"));
+                }
+                "details" => details = Some(map.next_value()?),
+                "students" if students.is_some() => {
+                    return Err(de::Error::duplicate_field("students"));
+                }
+                "students" => {
+                    students = Some(map.next_value_seed(StudentMapSeed {
+                        limits: self.limits,
+                        exceeded: self.exceeded,
+                    })?);
+                }
+                _ => return Err(de::Error::unknown_field(&key, &["details", "students"])),
+            }
+        }
+
+        let students: StudentMap = students.ok_or_else(|| de::Error::missing_field("students"))?;
+
+        Ok(BorrowedDocument {
+            details: details.flatten(),
+            students: students.students,
+            duplicates: students.duplicates,
+        })
+    }
+}
+
+impl StudentEntry {
+    /// Returns the grade of the student, the sum of their question scores if
+    /// no grade is given.
+    pub fn grade(&self) -> f32 {
+        match self {
+            StudentEntry::Grade(grade) => *grade,
+            StudentEntry::Detailed(record) => record
+                .grade
+                .unwrap_or_else(|| record.questions.values().sum()),
+        }
+    }
+}
+
+impl From<&Exam> for ExamDocument {
+    fn from(exam: &Exam) -> Self {
+        let mut students = StudentMap::default();
+        for (name, entry) in exam.students.iter().map(|student| {
+            let detailed = !student.questions.is_empty()
+                || !student.answers.is_empty()
+                || student.id.is_some()
+                || student.group.is_some()
+                || student.email.is_some();
+
+            let entry = if detailed {
+                StudentEntry::Detailed(Box::new(StudentRecord {
+                    grade: Some(student.grade),
+                    questions: student.questions.clone(),
+                    answers: student.answers.clone(),
+                    id: student.id.clone(),
+                    group: student.group.clone(),
+                    email: student.email.clone(),
+                }))
+            } else {
+                StudentEntry::Grade(student.grade)
+            };
+
+            (student.name.clone(), entry)
+        }) {
+            // The map has no limits, so no student is rejected
+            let _ = students.insert(name, entry);
+        }
+
+        let details = ExamDetails {
+            name: exam.title.clone(),
+            max_grade: Some(exam.max_grade),
+            pass_grade: exam.pass_threshold,
+            unit: (exam.unit != GradeUnit::Points).then_some(exam.unit),
+            date: exam.date.clone(),
+            course: exam.course.clone(),
+            term: exam.term.clone(),
+            instructor: exam.instructor.clone(),
+            questions: (!exam.question_max_scores.is_empty())
+                .then(|| exam.question_max_scores.clone()),
+            scale: exam.grade_scale.as_ref().map(|scale| {
+                scale
+                    .bands()
+                    .iter()
+                    .map(|band| (band.label.clone(), band.min_grade))
+                    .collect()
+            }),
+            answer_key: (!exam.answer_key.is_empty()).then(|| exam.answer_key.clone()),
+        };
+
+        ExamDocument {
+            details: Some(details),
+            students: owned_names(students.students),
+            duplicates: students.duplicates,
+        }
+    }
+}
+
+// Some serializers, like the TOML one, write `f32` numbers as `f64`, e.g. 4.6
+// as 4.599999904632568. Numbers are written as the `f64` with the same decimal
+// representation instead, which is read back as the same `f32`.
+struct Number(f32);
+
+impl Serialize for Number {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let number = self.0.to_string().parse().unwrap_or(self.0 as f64);
+        serializer.serialize_f64(number)
+    }
+}
+
+fn serialize_number<S: Serializer>(number: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+    Number(*number).serialize(serializer)
+}
+
+fn serialize_optional_number<S: Serializer>(
+    number: &Option<f32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    number.map(Number).serialize(serializer)
+}
+
+fn serialize_numbers<S: Serializer>(
+    numbers: &IndexMap<String, f32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(numbers.iter().map(|(key, &number)| (key, Number(number))))
+}
+
+fn serialize_optional_numbers<S: Serializer>(
+    numbers: &Option<IndexMap<String, f32>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match numbers {
+        Some(numbers) => serialize_numbers(numbers, serializer),
+        None => serializer.serialize_none(),
+    }
+}
@@ -0,0 +1,112 @@
+use indexmap::set::IndexSet;
+use serde::{Deserialize, Serialize};
+
+use crate::exam::Student;
+
+/// Procedure used to put the grades of an exam form on the scale of another
+/// form, see [Exam::equate_to](crate::exam::Exam::equate_to).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EquatingMethod {
+    /// Shifts the grades by the difference in difficulty of the forms, so
+    /// only their means are matched.
+    Mean,
+
+    /// Shifts and stretches the grades so both their means and their standard
+    /// deviations are matched.
+    #[default]
+    Linear,
+}
+
+// Mean and standard deviation of the total grades and the anchor scores of the
+// students of a form with a breakdown of their grade
+struct FormMoments {
+    total: (f64, f64),
+    anchor: (f64, f64),
+}
+
+// Equated grade of each student of the form, or `None` if the forms share no
+// question or any of them has no student with a breakdown of their grade
+pub fn equate(
+    form: &[Student],
+    reference: &[Student],
+    method: EquatingMethod,
+) -> Option<Vec<(String, f32)>> {
+    let anchors: IndexSet<&str> = form
+        .iter()
+        .flat_map(|s| s.questions.keys())
+        .filter(|q| reference.iter().any(|s| s.questions.contains_key(*q)))
+        .map(String::as_str)
+        .collect();
+    if anchors.is_empty() {
+        return None;
+    }
+
+    let form_moments = moments(form, &anchors)?;
+    let reference_moments = moments(reference, &anchors)?;
+
+    // Chained equating: the grades of the form are put on the scale of its
+    // anchor scores, which are then put on the scale of the reference grades
+    let (to_anchor, from_anchor) = match method {
+        EquatingMethod::Mean => (1.0, 1.0),
+        EquatingMethod::Linear => (
+            ratio(form_moments.anchor.1, form_moments.total.1),
+            ratio(reference_moments.total.1, reference_moments.anchor.1),
+        ),
+    };
+
+    let equate = |grade: f64| {
+        let anchor = form_moments.anchor.0 + to_anchor * (grade - form_moments.total.0);
+        reference_moments.total.0 + from_anchor * (anchor - reference_moments.anchor.0)
+    };
+
+    let grades = form
+        .iter()
+        .map(|s| (s.name.clone(), equate(s.grade as f64) as f32))
+        .collect();
+
+    Some(grades)
+}
+
+fn moments(students: &[Student], anchors: &IndexSet<&str>) -> Option<FormMoments> {
+    let scores: Vec<(f64, f64)> = students
+        .iter()
+        .filter(|s| !s.questions.is_empty())
+        .map(|s| {
+            let anchor = anchors
+                .iter()
+                .filter_map(|q| s.questions.get(*q))
+                .sum::<f32>();
+            (s.grade as f64, anchor as f64)
+        })
+        .collect();
+    if scores.is_empty() {
+        return None;
+    }
+
+    let total: Vec<f64> = scores.iter().map(|&(total, _)| total).collect();
+    let anchor: Vec<f64> = scores.iter().map(|&(_, anchor)| anchor).collect();
+
+    Some(FormMoments {
+        total: mean_and_deviation(&total),
+        anchor: mean_and_deviation(&anchor),
+    })
+}
+
+fn mean_and_deviation(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+    (mean, variance.sqrt())
+}
+
+// Ratio of the standard deviations, falling back to mean equating if the
+// grades don't vary
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        1.0
+    } else {
+        numerator / denominator
+    }
+}
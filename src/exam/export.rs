@@ -0,0 +1,141 @@
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::exam::{Exam, QuestionStatistics};
+
+/// File formats supported when exporting exam data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    exam: &'a str,
+    date: &'a str,
+    metric: &'static str,
+    value: f32,
+}
+
+/// Writes the headline statistics of each exam in long format, that is, one
+/// `(exam, date, metric, value)` record per statistic, which is the layout
+/// expected by most dashboard tools.
+///
+/// Exams without a title or date are exported with an empty value in those
+/// columns.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{export_time_series, Exam, ExportFormat, Student};
+///
+/// let students = &[
+///     Student::new("Joan Beltrán Peris", 4.6),
+///     Student::new("Jose Abad Martínez", 3.6),
+///     Student::new("David Jiménez Hidalgo", 7.94),
+/// ];
+///
+/// let mut exam = Exam::new(students);
+/// exam.set_title("Econometrics").unwrap();
+/// exam.set_date("2024-06-14").unwrap();
+///
+/// let mut csv = Vec::new();
+/// export_time_series(&[exam], &mut csv, ExportFormat::Csv).unwrap();
+///
+/// let csv = String::from_utf8(csv).unwrap();
+/// let mut lines = csv.lines();
+///
+/// assert_eq!(lines.next(), Some("exam,date,metric,value"));
+/// assert_eq!(
+///     lines.next(),
+///     Some("Econometrics,2024-06-14,total_students,3")
+/// );
+/// ```
+pub fn export_time_series<W: Write>(
+    exams: &[Exam],
+    mut writer: W,
+    format: ExportFormat,
+) -> io::Result<()> {
+    let records: Vec<Record<'_>> = exams.iter().flat_map(records).collect();
+
+    match format {
+        ExportFormat::Json => serde_json::to_writer_pretty(&mut writer, &records)?,
+        ExportFormat::Csv => {
+            writeln!(writer, "exam,date,metric,value")?;
+            for record in &records {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    csv_field(record.exam),
+                    csv_field(record.date),
+                    record.metric,
+                    record.value
+                )?;
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+fn records(exam: &Exam) -> Vec<Record<'_>> {
+    let stats = &exam.statistics;
+    let metrics = [
+        ("total_students", stats.total_students as f32),
+        ("passed_students", stats.passed_students as f32),
+        ("failed_students", stats.failed_students as f32),
+        ("pass_rate", stats.pass_rate),
+        ("mean", stats.mean),
+        ("median", stats.median),
+        ("std_dev", stats.std_dev),
+        ("highest_grade", stats.highest_grade),
+        ("lowest_grade", stats.lowest_grade),
+        ("max_grade", stats.max_grade),
+    ];
+
+    metrics
+        .into_iter()
+        .map(|(metric, value)| Record {
+            exam: exam.title.as_deref().unwrap_or_default(),
+            date: exam.date.as_deref().unwrap_or_default(),
+            metric,
+            value,
+        })
+        .collect()
+}
+
+// Writes one row per question with its difficulty and discrimination indices,
+// mean and blank rate. Unknown indices are left empty.
+pub fn write_item_stats_csv<W: Write>(
+    questions: &[QuestionStatistics],
+    mut writer: W,
+) -> io::Result<()> {
+    let optional = |value: Option<f32>| value.map_or(String::new(), |value| value.to_string());
+
+    writeln!(writer, "question,difficulty,discrimination,mean,blank_rate")?;
+    for question in questions {
+        writeln!(
+            writer,
+            "{},{},{},{},{}",
+            csv_field(&question.name),
+            optional(question.difficulty),
+            optional(question.discrimination),
+            question.mean,
+            question.blank_rate
+        )?;
+    }
+
+    writer.flush()
+}
+
+// Quotes a CSV field if it contains any character with special meaning.
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
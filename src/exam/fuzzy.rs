@@ -0,0 +1,25 @@
+// Smallest Levenshtein distance between the pattern and any substring of the
+// text, i.e. the number of typos needed for the text to contain the pattern.
+// Both are compared character by character, as given.
+pub fn substring_distance(pattern: &str, text: &str) -> usize {
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    // Distance between the first `i` characters of the pattern and the best
+    // substring of the text ending at the current character. Substrings can
+    // start anywhere, so the first row is all zeros.
+    let mut previous: Vec<usize> = (0..=pattern.len()).collect();
+    let mut best = pattern.len();
+
+    for c in text.chars() {
+        let mut current = vec![0; pattern.len() + 1];
+        for (i, &p) in pattern.iter().enumerate() {
+            let substitution = previous[i] + usize::from(p != c);
+            current[i + 1] = substitution.min(previous[i + 1] + 1).min(current[i] + 1);
+        }
+
+        best = best.min(current[pattern.len()]);
+        previous = current;
+    }
+
+    best
+}
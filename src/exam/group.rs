@@ -0,0 +1,128 @@
+use colored::Colorize;
+use prettytable::{row, Cell, Row, Table};
+use serde::{Deserialize, Serialize};
+
+use crate::exam::statistics::{missing_statistics, StudentColumns};
+use crate::exam::{table_format, RankMethod, RenderWarning, Statistics, Student};
+
+/// Statistics of the grades of the students of a single group of an exam.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupStatistics {
+    /// Name of the group, or `None` for the students without a group.
+    pub group: Option<String>,
+
+    /// Statistics of the grades of the students of the group. Their ranks and
+    /// percentiles are relative to the group.
+    pub statistics: Statistics,
+}
+
+// Statistics of every group found in the students, sorted by group name. The
+// students without a group come last.
+pub fn group_statistics(
+    students: &[Student],
+    max_grade: f32,
+    pass_grade: f32,
+    rank_method: RankMethod,
+) -> Vec<GroupStatistics> {
+    group_names(students)
+        .into_iter()
+        .map(|group| {
+            let mut members: Vec<Student> = students
+                .iter()
+                .filter(|s| s.group.as_deref() == group)
+                .cloned()
+                .collect();
+
+            GroupStatistics {
+                group: group.map(str::to_string),
+                statistics: Statistics::new(&mut members, max_grade, pass_grade, rank_method),
+            }
+        })
+        .collect()
+}
+
+// Every group found in the students, sorted by group name, with `None` last
+// for the students without a group
+pub fn group_names(students: &[Student]) -> Vec<Option<&str>> {
+    let mut groups: Vec<Option<&str>> = students.iter().map(|s| s.group.as_deref()).collect();
+    groups.sort_by_key(|group| (group.is_none(), *group));
+    groups.dedup();
+    groups
+}
+
+// Table with the main statistics of each group, one group per row, along with
+// the difference between the mean of each group and the mean of the exam
+pub fn groups_table(groups: &[GroupStatistics], exam_mean: f32) -> Table {
+    let mut table = Table::new();
+    table.set_titles(row![
+        c->"Group",
+        c->"Students",
+        c->"Pass Rate",
+        c->"Mean",
+        c->"Median",
+        c->"Standard Deviation",
+        c->"Mean vs Exam"
+    ]);
+
+    for group in groups {
+        let statistics = &group.statistics;
+        let difference = statistics.mean - exam_mean;
+        let colored_difference = if difference < 0.0 {
+            difference.to_string().red()
+        } else {
+            format!("+{difference}").green()
+        };
+
+        table.add_row(row![
+            group.group.as_deref().unwrap_or("-"),
+            c->statistics.total_students,
+            c->format!("{}%", statistics.pass_rate),
+            c->statistics.mean,
+            c->statistics.median,
+            c->statistics.std_dev,
+            c->colored_difference
+        ]);
+    }
+
+    table.set_format(table_format());
+    table
+}
+
+// Students table with the students of each group together, in the order of
+// `groups`, each group followed by a row with its subtotals. The students keep
+// their order within each group.
+pub fn grouped_students_table(
+    groups: &[GroupStatistics],
+    students: &[&Student],
+    columns: &StudentColumns,
+) -> (Table, Vec<RenderWarning>) {
+    let mut table = Table::new();
+    table.set_titles(columns.title_row());
+    let width = columns.titles().len();
+
+    for group in groups {
+        for student in students.iter().filter(|s| s.group == group.group) {
+            table.add_row(columns.row(student));
+        }
+
+        let statistics = &group.statistics;
+        let label = match &group.group {
+            Some(name) => format!("Subtotal {name}"),
+            None => "Subtotal without group".to_string(),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&label.bold().to_string()),
+            Cell::new(&statistics.mean.to_string().bold().to_string()).style_spec("c"),
+            Cell::new(&format!(
+                "{} students, {}% passed",
+                statistics.total_students, statistics.pass_rate
+            ))
+            .style_spec("c")
+            .with_hspan(width - 2),
+        ]));
+    }
+
+    table.set_format(table_format());
+
+    (table, missing_statistics(students))
+}
@@ -0,0 +1,186 @@
+use std::error::Error;
+use std::io;
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::exam::{BoxPlot, Histogram};
+
+const IMAGE_SIZE: (u32, u32) = (640, 400);
+const CAPTION_FONT: (&str, u32) = ("sans-serif", 20);
+
+type DrawResult<E> = Result<(), DrawingAreaErrorKind<E>>;
+
+/// Format of the images plots can be exported to, see
+/// [Exam::histogram_to_file](crate::exam::Exam::histogram_to_file).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Scalable vector graphics, best suited for slides and documents.
+    Svg,
+
+    /// Portable network graphics.
+    Png,
+}
+
+impl Histogram {
+    /// Renders the histogram as an image in the given format and writes it to
+    /// the given path.
+    pub fn to_file(&self, path: impl AsRef<Path>, format: ImageFormat) -> io::Result<()> {
+        let path = path.as_ref();
+
+        match format {
+            ImageFormat::Svg => {
+                draw_histogram(self, SVGBackend::new(path, IMAGE_SIZE).into_drawing_area())
+                    .map_err(io_error)
+            }
+            ImageFormat::Png => draw_histogram(
+                self,
+                BitMapBackend::new(path, IMAGE_SIZE).into_drawing_area(),
+            )
+            .map_err(io_error),
+        }
+    }
+}
+
+impl BoxPlot {
+    /// Renders the box plot as an image in the given format and writes it to
+    /// the given path.
+    pub fn to_file(&self, path: impl AsRef<Path>, format: ImageFormat) -> io::Result<()> {
+        let path = path.as_ref();
+
+        match format {
+            ImageFormat::Svg => {
+                draw_boxplot(self, SVGBackend::new(path, IMAGE_SIZE).into_drawing_area())
+                    .map_err(io_error)
+            }
+            ImageFormat::Png => draw_boxplot(
+                self,
+                BitMapBackend::new(path, IMAGE_SIZE).into_drawing_area(),
+            )
+            .map_err(io_error),
+        }
+    }
+}
+
+fn draw_histogram<DB: DrawingBackend>(
+    histogram: &Histogram,
+    area: DrawingArea<DB, Shift>,
+) -> DrawResult<DB::ErrorType> {
+    let total: usize = histogram.buckets().iter().map(|(_, count)| *count).sum();
+    let max_count = histogram
+        .buckets()
+        .iter()
+        .map(|(_, count)| *count as f64)
+        .chain(
+            histogram
+                .intervals()
+                .into_iter()
+                .flatten()
+                .map(|interval| interval.end * total as f64),
+        )
+        .fold(1.0, f64::max);
+
+    area.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&area)
+        .caption("Grades Histogram", CAPTION_FONT)
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(0.0..histogram.max_grade(), 0.0..max_count)?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_desc("Grade")
+        .y_desc("Number of Students")
+        .draw()?;
+
+    let bars = histogram.buckets().iter().zip(histogram.tones());
+    chart.draw_series(bars.map(|((range, count), tone)| {
+        let (red, green, blue) = tone.rgb();
+        let mut bar = Rectangle::new(
+            [(range.start, 0.0), (range.end, *count as f64)],
+            RGBColor(red, green, blue).filled(),
+        );
+
+        // Leave a gap between adjacent bars
+        bar.set_margin(0, 0, 1, 1);
+        bar
+    }))?;
+
+    // Error bars with the confidence interval of each bucket, in students
+    if let Some(intervals) = histogram.intervals() {
+        let error_bars = histogram.buckets().iter().zip(intervals);
+        chart.draw_series(error_bars.map(|((range, count), interval)| {
+            ErrorBar::new_vertical(
+                (range.start + range.end) / 2.0,
+                interval.start * total as f64,
+                *count as f64,
+                interval.end * total as f64,
+                BLACK.stroke_width(1),
+                8,
+            )
+        }))?;
+    }
+
+    area.present()
+}
+
+fn draw_boxplot<DB: DrawingBackend>(
+    boxplot: &BoxPlot,
+    area: DrawingArea<DB, Shift>,
+) -> DrawResult<DB::ErrorType> {
+    let [lower, first, median, third, upper] = boxplot.five_numbers().map(f64::from);
+
+    area.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&area)
+        .caption("Grades Box Plot", CAPTION_FONT)
+        .margin(10)
+        .x_label_area_size(40)
+        .build_cartesian_2d(0.0..f64::from(boxplot.max_grade()), 0.0..2.0)?;
+
+    chart
+        .configure_mesh()
+        .disable_y_mesh()
+        .disable_y_axis()
+        .x_desc("Grade")
+        .draw()?;
+
+    // There's nothing to draw if there are no grades, or all of them are
+    // outliers
+    if lower <= upper {
+        let style = ShapeStyle::from(&BLUE).stroke_width(2);
+
+        chart.draw_series([Rectangle::new(
+            [(first, 0.6), (third, 1.4)],
+            ShapeStyle::from(&BLUE.mix(0.3)).filled(),
+        )])?;
+        chart.draw_series([Rectangle::new([(first, 0.6), (third, 1.4)], style)])?;
+        chart.draw_series(
+            [
+                [(median, 0.6), (median, 1.4)],
+                [(lower, 1.0), (first, 1.0)],
+                [(third, 1.0), (upper, 1.0)],
+                [(lower, 0.8), (lower, 1.2)],
+                [(upper, 0.8), (upper, 1.2)],
+            ]
+            .map(|points| PathElement::new(points, style)),
+        )?;
+    }
+
+    chart.draw_series(
+        boxplot
+            .outliers()
+            .iter()
+            .map(|&grade| Circle::new((f64::from(grade), 1.0), 4, RED.filled())),
+    )?;
+
+    area.present()
+}
+
+fn io_error<E: Error + Send + Sync>(err: DrawingAreaErrorKind<E>) -> io::Error {
+    io::Error::other(err.to_string())
+}
@@ -0,0 +1,115 @@
+/// Language of the generated reports, see
+/// [Exam::to_markdown_in](crate::exam::Exam::to_markdown_in), and of the
+/// descriptions of the warnings, see
+/// [ExamWarning::message](crate::exam::ExamWarning::message).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+// Fixed text of the reports in a language. The labels of the grade bands are
+// not translated, as they are given by the user, but the pass and fail labels
+// used when there are none are.
+pub struct Labels {
+    pub course: &'static str,
+    pub term: &'static str,
+    pub instructor: &'static str,
+    pub date: &'static str,
+    pub summary: &'static str,
+    pub statistic: &'static str,
+    pub value: &'static str,
+    pub total_students: &'static str,
+    pub passed_students: &'static str,
+    pub failed_students: &'static str,
+    pub pass_rate: &'static str,
+    pub mean: &'static str,
+    pub median: &'static str,
+    pub std_dev: &'static str,
+    pub max_grade: &'static str,
+    pub min_grade: &'static str,
+    pub questions: &'static str,
+    pub question: &'static str,
+    pub students: &'static str,
+    pub name: &'static str,
+    pub grade: &'static str,
+    pub band: &'static str,
+    pub percentile: &'static str,
+    pub rank: &'static str,
+    pub histogram: &'static str,
+    pub warnings: &'static str,
+    pub pass: &'static str,
+    pub fail: &'static str,
+}
+
+const ENGLISH: Labels = Labels {
+    course: "Course",
+    term: "Term",
+    instructor: "Instructor",
+    date: "Date",
+    summary: "Summary",
+    statistic: "Statistic",
+    value: "Value",
+    total_students: "Total Students",
+    passed_students: "Passed Students",
+    failed_students: "Failed Students",
+    pass_rate: "Pass Rate",
+    mean: "Mean",
+    median: "Median",
+    std_dev: "Standard Deviation",
+    max_grade: "Max Grade",
+    min_grade: "Min Grade",
+    questions: "Questions",
+    question: "Question",
+    students: "Students",
+    name: "Name",
+    grade: "Grade",
+    band: "Band",
+    percentile: "Percentile",
+    rank: "Rank",
+    histogram: "Histogram",
+    warnings: "Warnings",
+    pass: "Pass",
+    fail: "Fail",
+};
+
+const SPANISH: Labels = Labels {
+    course: "Asignatura",
+    term: "Periodo",
+    instructor: "Profesor",
+    date: "Fecha",
+    summary: "Resumen",
+    statistic: "Estadística",
+    value: "Valor",
+    total_students: "Total de estudiantes",
+    passed_students: "Aprobados",
+    failed_students: "Suspensos",
+    pass_rate: "Porcentaje de aprobados",
+    mean: "Media",
+    median: "Mediana",
+    std_dev: "Desviación típica",
+    max_grade: "Nota máxima",
+    min_grade: "Nota mínima",
+    questions: "Preguntas",
+    question: "Pregunta",
+    students: "Estudiantes",
+    name: "Nombre",
+    grade: "Nota",
+    band: "Calificación",
+    percentile: "Percentil",
+    rank: "Posición",
+    histogram: "Histograma",
+    warnings: "Avisos",
+    pass: "Aprobado",
+    fail: "Suspenso",
+};
+
+impl Language {
+    pub(crate) fn labels(self) -> &'static Labels {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+        }
+    }
+}
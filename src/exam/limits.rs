@@ -0,0 +1,81 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::exam::document::BorrowedDocument;
+
+/// Limits enforced while parsing an exam file, to protect services that parse
+/// files uploaded by untrusted users, see
+/// [Exam::from_file_with_limits](crate::exam::Exam::from_file_with_limits).
+///
+/// Every limit is disabled by default. Files exceeding any of them fail to
+/// parse with an error for which
+/// [ParseError::is_limit_exceeded](crate::error::ParseError::is_limit_exceeded)
+/// returns `true`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct Limits {
+    /// Maximum size of the file, in bytes. It's checked before the file is
+    /// read.
+    pub max_file_size: Option<u64>,
+
+    /// Maximum number of students of the exam.
+    pub max_students: Option<usize>,
+
+    /// Maximum length of the name of a student, in characters.
+    pub max_name_length: Option<usize>,
+}
+
+// Limit exceeded by a file
+#[derive(Debug)]
+pub(crate) enum LimitExceeded {
+    FileSize { size: u64, max: u64 },
+    Students { max: usize },
+    NameLength { max: usize },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::FileSize { size, max } => {
+                write!(f, "the file is {size} bytes long, the maximum is {max}")
+            }
+            LimitExceeded::Students { max } => {
+                write!(f, "the file has more than {max} students")
+            }
+            LimitExceeded::NameLength { max } => {
+                write!(f, "a student name is longer than {max} characters")
+            }
+        }
+    }
+}
+
+impl Limits {
+    pub(crate) fn check_file_size(&self, size: u64) -> Result<(), LimitExceeded> {
+        match self.max_file_size {
+            Some(max) if size > max => Err(LimitExceeded::FileSize { size, max }),
+            _ => Ok(()),
+        }
+    }
+
+    // Documents parsed from a file are already checked while their students
+    // are read, but not those built by hand
+    pub(crate) fn check_students(&self, document: &BorrowedDocument) -> Result<(), LimitExceeded> {
+        let count = document.students.len();
+        if let Some(max) = self.max_students.filter(|&max| count > max) {
+            return Err(LimitExceeded::Students { max });
+        }
+
+        if let Some(max) = self.max_name_length {
+            let too_long = document
+                .students
+                .keys()
+                .any(|name| name.chars().count() > max);
+
+            if too_long {
+                return Err(LimitExceeded::NameLength { max });
+            }
+        }
+
+        Ok(())
+    }
+}
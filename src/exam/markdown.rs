@@ -0,0 +1,243 @@
+use std::fmt::Write;
+
+use crate::exam::language::Labels;
+use crate::exam::{Exam, Histogram, Language};
+
+// Width, in characters, of the longest bar of the histogram
+const HISTOGRAM_WIDTH: usize = 40;
+
+// GitHub-flavored Markdown report with the summary, the students table and the
+// histogram of the exam.
+pub fn to_markdown(exam: &Exam, language: Language) -> String {
+    let labels = language.labels();
+    let histogram = exam.build_histogram(None);
+    let mut markdown = String::new();
+
+    // Writing to a String can't fail
+    let _ = write_header(exam, labels, &mut markdown);
+    let _ = write_summary(exam, labels, &mut markdown);
+    let _ = write_questions(exam, labels, &mut markdown);
+    let _ = write_students(exam, labels, &mut markdown);
+    let _ = write_histogram(&histogram, labels, &mut markdown);
+    let _ = write_warnings(exam, &histogram, language, &mut markdown);
+
+    markdown
+}
+
+fn write_header(exam: &Exam, labels: &Labels, markdown: &mut String) -> std::fmt::Result {
+    if let Some(title) = &exam.title {
+        writeln!(markdown, "# {}\n", escape(title))?;
+    }
+
+    let metadata = [
+        (labels.course, &exam.course),
+        (labels.term, &exam.term),
+        (labels.instructor, &exam.instructor),
+        (labels.date, &exam.date),
+    ];
+    let mut any = false;
+    for (label, value) in metadata {
+        if let Some(value) = value {
+            writeln!(markdown, "- **{label}:** {}", escape(value))?;
+            any = true;
+        }
+    }
+    if any {
+        writeln!(markdown)?;
+    }
+
+    Ok(())
+}
+
+fn write_summary(exam: &Exam, labels: &Labels, markdown: &mut String) -> std::fmt::Result {
+    let stats = &exam.statistics;
+
+    writeln!(markdown, "## {}\n", labels.summary)?;
+    writeln!(markdown, "| {} | {} |", labels.statistic, labels.value)?;
+    writeln!(markdown, "| --- | ---: |")?;
+
+    let rows = [
+        (labels.total_students, stats.total_students.to_string()),
+        (labels.passed_students, stats.passed_students.to_string()),
+        (labels.failed_students, stats.failed_students.to_string()),
+        (labels.pass_rate, format!("{}%", stats.pass_rate)),
+        (labels.mean, stats.mean.to_string()),
+        (labels.median, stats.median.to_string()),
+        (labels.std_dev, stats.std_dev.to_string()),
+        (labels.max_grade, stats.highest_grade.to_string()),
+        (labels.min_grade, stats.lowest_grade.to_string()),
+    ];
+    for (label, value) in rows {
+        writeln!(markdown, "| {label} | {value} |")?;
+    }
+
+    if let Some(scale) = &exam.grade_scale {
+        for (label, count) in scale.counts(&exam.students) {
+            writeln!(markdown, "| {} | {count} |", escape(label))?;
+        }
+    }
+
+    writeln!(markdown)
+}
+
+fn write_questions(exam: &Exam, labels: &Labels, markdown: &mut String) -> std::fmt::Result {
+    let questions = exam.question_statistics();
+    if questions.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(markdown, "## {}\n", labels.questions)?;
+    writeln!(
+        markdown,
+        "| {} | {} | {} |",
+        labels.question, labels.mean, labels.pass_rate
+    )?;
+    writeln!(markdown, "| --- | ---: | ---: |")?;
+    for question in &questions {
+        let pass_rate = question
+            .pass_rate
+            .map_or("-".to_string(), |pass_rate| format!("{pass_rate}%"));
+        writeln!(
+            markdown,
+            "| {} | {} | {pass_rate} |",
+            escape(&question.name),
+            question.mean
+        )?;
+    }
+
+    writeln!(markdown)
+}
+
+fn write_students(exam: &Exam, labels: &Labels, markdown: &mut String) -> std::fmt::Result {
+    let scale = exam.grade_scale.as_ref();
+
+    writeln!(markdown, "## {}\n", labels.students)?;
+    if scale.is_some() {
+        writeln!(
+            markdown,
+            "| {} | {} | {} | {} | {} |",
+            labels.name, labels.grade, labels.band, labels.percentile, labels.rank
+        )?;
+        writeln!(markdown, "| --- | ---: | :---: | ---: | ---: |")?;
+    } else {
+        writeln!(
+            markdown,
+            "| {} | {} | {} | {} |",
+            labels.name, labels.grade, labels.percentile, labels.rank
+        )?;
+        writeln!(markdown, "| --- | ---: | ---: | ---: |")?;
+    }
+
+    for student in &exam.students {
+        let mut cells = vec![escape(&student.name), student.grade.to_string()];
+        if let Some(scale) = scale {
+            let label = scale.band(student.grade).map_or("-", |band| &band.label);
+            cells.push(escape(label));
+        }
+        cells.push(
+            student
+                .percentile
+                .map_or("-".to_string(), |percentile| percentile.to_string()),
+        );
+        cells.push(format!(
+            "{}/{}",
+            student
+                .rank
+                .map_or("-".to_string(), |rank| rank.to_string()),
+            exam.statistics.highest_rank
+        ));
+
+        // Rows are styled by the first rule the student meets
+        let rule = exam
+            .format_rules
+            .iter()
+            .find(|rule| rule.matches(student, &exam.statistics));
+        if let Some(rule) = rule {
+            if rule.bold {
+                for cell in &mut cells {
+                    *cell = format!("**{cell}**");
+                }
+            }
+            if let Some(color) = rule.color {
+                cells[0] = format!("{} {}", color.marker(), cells[0]);
+            }
+        }
+
+        writeln!(markdown, "| {} |", cells.join(" | "))?;
+    }
+
+    writeln!(markdown)
+}
+
+// The histogram is written as a fenced code block, as Markdown has no charts
+fn write_histogram(
+    histogram: &Histogram,
+    labels: &Labels,
+    markdown: &mut String,
+) -> std::fmt::Result {
+    let buckets = histogram.buckets();
+    let max_count = buckets.iter().map(|(_, count)| *count).max().unwrap_or(0);
+
+    let ranges: Vec<String> = buckets
+        .iter()
+        .map(|(range, _)| format!("{}-{}", range.start, range.end))
+        .collect();
+    let range_width = ranges.iter().map(String::len).max().unwrap_or(0);
+
+    writeln!(markdown, "## {}\n", labels.histogram)?;
+    writeln!(markdown, "```text")?;
+    for (range, (_, count)) in ranges.iter().zip(buckets) {
+        let bar_width = (count * HISTOGRAM_WIDTH).div_ceil(max_count.max(1));
+        writeln!(
+            markdown,
+            "{range:>range_width$} | {} {count}",
+            "█".repeat(bar_width)
+        )?;
+    }
+    writeln!(markdown, "```")
+}
+
+// Warnings of the exam and of the histogram, as a list at the end of the
+// report
+fn write_warnings(
+    exam: &Exam,
+    histogram: &Histogram,
+    language: Language,
+    markdown: &mut String,
+) -> std::fmt::Result {
+    let mut warnings: Vec<String> = exam
+        .warnings()
+        .iter()
+        .map(|warning| warning.message(language))
+        .collect();
+    warnings.extend(
+        histogram
+            .warnings()
+            .iter()
+            .map(|warning| warning.message(language)),
+    );
+    if warnings.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(markdown, "\n## {}\n", language.labels().warnings)?;
+    for warning in warnings {
+        writeln!(markdown, "- {}", escape(&warning))?;
+    }
+
+    Ok(())
+}
+
+// Escapes the characters that would break a table cell or be taken as
+// formatting
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '|' | '*' | '_' | '`' | '\\' | '[' | ']' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
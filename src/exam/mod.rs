@@ -1,29 +1,3666 @@
+mod archive;
+mod band;
+mod builder;
+mod cache;
+mod course;
+mod curve;
+mod cutoff;
+mod digest;
+mod distractor;
+mod document;
+mod equating;
+mod export;
+mod fuzzy;
+mod group;
+#[cfg(feature = "image-export")]
+mod image;
+mod language;
+mod limits;
+mod markdown;
+mod names;
+mod options;
+mod outlier;
+mod output;
 mod parse;
+mod pipeline;
 mod plot;
+mod publish;
+mod quality;
+mod question;
+mod retotal;
+mod roster;
+mod rules;
+mod sample;
+mod seed;
+mod speededness;
 mod statistics;
+mod stream;
 mod student;
+mod view;
+mod warning;
+#[cfg(feature = "watch")]
+mod watch;
+#[cfg(feature = "xlsx")]
+mod xlsx;
 
 use std::cmp::Ordering;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::ops::{Range, RangeBounds};
+use std::path::{Path, PathBuf};
 
+use indexmap::map::IndexMap;
+use prettytable::Table;
+use serde::{Deserialize, Serialize};
 use unidecode::unidecode;
 
-use crate::error::ParseError;
-use parse::parse_exam_file;
-use statistics::ExamStatistics;
+use crate::error::{AnonymousError, FilterError, ParseError, PublishedError};
+pub use band::{BandTransitions, GradeBand, GradeScale};
+pub use builder::ExamBuilder;
+pub use course::{
+    Aggregation, BandChart, Course, CourseComparison, ExamSnapshot, FinalStanding, HistoryEntry,
+    MissingExams, StudentHistory, Trend,
+};
+pub use curve::CurveMethod;
+pub use cutoff::{Cutoff, Top};
+pub use distractor::{Distractor, QuestionDistractors};
+pub use document::{ExamDetails, ExamDocument, StudentEntry, StudentRecord};
+pub use equating::EquatingMethod;
+pub use export::{export_time_series, ExportFormat};
+pub use group::GroupStatistics;
+#[cfg(feature = "image-export")]
+pub use image::ImageFormat;
+pub use language::Language;
+pub(crate) use limits::LimitExceeded;
+pub use limits::Limits;
+pub use names::{NameAnomaly, NameIssue};
+pub use options::ParseOptions;
+pub use outlier::OutlierMethod;
+pub use output::{
+    output_style, set_color_mode, set_output_style, ColorMode, OutputStyle, TableStyle,
+};
+pub(crate) use output::{table_format, with_table_style};
+#[cfg(feature = "mmap")]
+use parse::parse_mapped_exam_file;
+#[cfg(feature = "xlsx")]
+use parse::parse_xlsx_file;
+pub use parse::{parse_bytes, parse_bytes_with_limits, parse_bytes_with_options, FileFormat};
+use parse::{parse_exam_dir, parse_exam_file, parse_reader};
+pub use pipeline::{Pipeline, PipelineWarnings};
+pub use plot::{
+    BoxPlot, BucketTone, DifficultyHistogram, GroupSeries, GroupedHistogram, Histogram,
+    HistogramBuilder, PassRateChart,
+};
+use publish::PinnedStandings;
+pub use publish::{AnonymousEntry, AnonymousOptions};
+pub use quality::{QualityIssue, QualityScore, Severity};
+pub use question::{QuestionFlag, QuestionStatistics};
+pub use retotal::{GradeDiscrepancy, RetotalOptions, ScoreRounding};
+pub use roster::{Roster, RosterReport};
+pub use rules::{FormatRule, RuleColor, RuleCondition};
+pub use sample::AuditWeights;
+pub use seed::Seed;
+pub use speededness::{QuestionCompletion, Speededness};
+pub use statistics::Statistics;
+use statistics::{header_table, questions_table, StudentColumns};
 pub use student::Student;
+pub use view::ExamView;
+pub use warning::{ExamWarning, RenderWarning};
+#[cfg(feature = "watch")]
+pub use watch::ExamWatcher;
+#[cfg(feature = "xlsx")]
+pub use xlsx::XlsxOptions;
+
+/// Order in which the students table can be printed, see
+/// [print_students](Exam::print_students).
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{Exam, SortBy, Student};
+///
+/// let mut students = vec![
+///     Student::new("Joan Beltrán Peris", 4.6),
+///     Student::new("Jose Abad Martínez", 3.6),
+///     Student::new("David Jiménez Hidalgo", 7.94),
+/// ];
+/// students[0].group = Some("B".to_string());
+/// students[2].group = Some("A".to_string());
+///
+/// let exam = Exam::new(students);
+///
+/// let mut output = Vec::new();
+/// exam.students_to(&mut output, Some(SortBy::Group)).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+///
+/// let david = output.find("David").unwrap();
+/// let joan = output.find("Joan").unwrap();
+/// let jose = output.find("Jose").unwrap();
+/// assert!(david < joan && joan < jose);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    /// Highest grade first, students with the same grade sorted by name.
+    Grade,
+
+    /// Alphabetical order, ignoring case and accents.
+    Name,
+
+    /// Best rank first.
+    Rank,
+
+    /// Highest percentile first.
+    Percentile,
+
+    /// Grouped by [group](Student::group), students without a group last,
+    /// sorted by name within each group.
+    Group,
+
+    /// Furthest from the mean first, above or below it, students at the same
+    /// distance sorted by name.
+    Delta,
+}
+
+/// How the students of different exams are matched, see
+/// [intersect_by](Exam::intersect_by), [union_by](Exam::union_by) and
+/// [Course::set_match_by].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchBy {
+    /// Names, ignoring case, accents and surrounding whitespace.
+    #[default]
+    Name,
+
+    /// [IDs](Student::id). Students without an ID never match any other
+    /// student.
+    Id,
+}
+
+/// How students with the same grade are ranked, e.g. for the grades 9, 8, 8
+/// and 7.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RankMethod {
+    /// Students with the same grade share the same rank, and the next grade
+    /// gets the following rank: 1, 2, 2, 3.
+    #[default]
+    Dense,
+
+    /// Students with the same grade share the same rank, and the next grade
+    /// skips as many ranks as students tied: 1, 2, 2, 4.
+    Competition,
+
+    /// Students with the same grade share the mean of the ranks they would
+    /// take: 1, 2.5, 2.5, 4.
+    Fractional,
+}
+
+/// Unit in which the grades of an exam are expressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GradeUnit {
+    /// Raw points, ranging from 0 to the maximum grade of the exam.
+    #[default]
+    Points,
+
+    /// Percentage of the maximum grade, ranging from 0 to 100.
+    Percentage,
+}
 
 /// This type represents and exam.
-#[derive(Debug, Clone)]
+///
+/// Exams are `Send + Sync`. To share a single exam between threads without
+/// cloning it, see [ExamView].
+///
+/// Exams can be serialized and deserialized with [serde], e.g. to send them
+/// over the network or to cache them, along with their computed statistics and
+/// the rank and percentile of each student, which are not computed again when
+/// deserializing.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{Exam, Student};
+///
+/// let students = &[
+///     Student::new("Joan Beltrán Peris", 4.6),
+///     Student::new("Jose Abad Martínez", 3.6),
+///     Student::new("David Jiménez Hidalgo", 7.94),
+/// ];
+///
+/// let exam = Exam::new(students);
+///
+/// let json = serde_json::to_string(&exam).unwrap();
+/// let deserialized: Exam = serde_json::from_str(&json).unwrap();
+///
+/// assert!(json.contains(r#""rank":1,"percentile":100.0"#));
+/// assert_eq!(deserialized.statistics(), exam.statistics());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Exam {
     title: Option<String>,
+    date: Option<String>,
+    course: Option<String>,
+    term: Option<String>,
+    instructor: Option<String>,
     max_grade: f32,
+    pass_threshold: Option<f32>,
+    unit: GradeUnit,
+    normalized_scale: Option<f32>,
+    outlier_marker: Option<OutlierMethod>,
+    #[serde(default)]
+    format_rules: Vec<FormatRule>,
+    grade_scale: Option<GradeScale>,
+    question_max_scores: IndexMap<String, f32>,
+    #[serde(default)]
+    answer_key: IndexMap<String, String>,
+    #[serde(default)]
+    rank_method: RankMethod,
     students: Vec<Student>,
-    statistics: ExamStatistics,
+    statistics: Statistics,
+    source: Option<PathBuf>,
+    transformations: Vec<String>,
+    // Warnings that can't be computed from the students, e.g. the students
+    // that were overwritten by a later entry of the file
+    #[serde(default)]
+    warnings: Vec<ExamWarning>,
+    #[serde(default)]
+    validate_grades: bool,
+    #[serde(default)]
+    check_names: bool,
+    published: bool,
+    pinned_standings: Option<PinnedStandings>,
 }
 
-impl Exam {
-    /// Creates a new `Exam` from a given set of students.
+impl Exam {
+    /// Creates a new `Exam` from a given set of students.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// ```
+    ///
+    /// A NaN grade is equal to no other, so students with a NaN grade never
+    /// share their rank:
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let exam = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", f32::NAN),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", f32::NAN),
+    /// ]);
+    ///
+    /// let mut ranks: Vec<f32> = exam.students().iter().filter_map(|s| s.rank()).collect();
+    /// ranks.sort_by(f32::total_cmp);
+    /// assert_eq!(ranks, [1.0, 2.0, 3.0]);
+    /// ```
+    pub fn new(students: impl Into<Vec<Student>>) -> Self {
+        Self::builder(students).build()
+    }
+
+    /// Returns an [ExamBuilder] to create an exam with all its settings at
+    /// once, computing its statistics a single time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::builder(students)
+    ///     .title("Econometrics")
+    ///     .max_grade(12.0)
+    ///     .pass_threshold(4.0)
+    ///     .build();
+    ///
+    /// assert_eq!(exam.statistics().passed_students(), 2);
+    /// ```
+    pub fn builder(students: impl Into<Vec<Student>>) -> ExamBuilder {
+        ExamBuilder::new(students.into())
+    }
+
+    /// Creates a new `Exam` from a given file.
+    /// The file formats suppported for the moment are JSON, TOML, YAML and CSV
+    /// files, and Excel workbooks with the `xlsx` feature enabled.
+    /// The file should have a student object with all the students and their
+    /// grades as key/value pairs. For more information about wich format
+    /// a file should follow, please see [exms](crate).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// use exms::error::ParseError;
+    /// use exms::exam::Exam;
+    ///
+    /// fn main() -> Result<(), ParseError> {
+    ///     let file_path = Path::new("students.json");
+    ///     let exam = Exam::from_file(&file_path)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ParseError> {
+        parse_exam_file(path.as_ref(), &ParseOptions::default())
+    }
+
+    /// Creates an `Exam` from every exam file in the given directory, like
+    /// [from_file](Exam::from_file) does with each of them. The exams are
+    /// sorted by file name, so naming the files by date keeps them in
+    /// chronological order.
+    ///
+    /// Files whose extension is not supported, hidden files and
+    /// subdirectories are skipped. Fails if any of the supported files can't
+    /// be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::error::ParseError;
+    /// use exms::exam::Exam;
+    ///
+    /// fn main() -> Result<(), ParseError> {
+    ///     let exams = Exam::from_dir("econometrics")?;
+    ///
+    ///     for exam in &exams {
+    ///         exam.summary();
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Vec<Self>, ParseError> {
+        parse_exam_dir(path.as_ref())
+    }
+
+    /// Creates a new `Exam` from the content of an exam file in the given
+    /// format, e.g. the body of an HTTP response or a database field, where
+    /// there's no file extension to tell the format. The content follows the
+    /// same format as in [from_file](Exam::from_file), see also [parse_bytes].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, FileFormat};
+    ///
+    /// let content = r#"{ "students": { "Joan Beltrán Peris": 4.6, "David Jiménez Hidalgo": 7.94 } }"#;
+    /// let exam = Exam::from_str(content, FileFormat::Json).unwrap();
+    ///
+    /// assert_eq!(exam.statistics().total_students(), 2);
+    /// ```
+    pub fn from_str(content: &str, format: FileFormat) -> Result<Self, ParseError> {
+        parse_bytes(format, content.as_bytes())
+    }
+
+    /// Creates a new `Exam` from the content of an exam file in the given
+    /// format read from a reader, e.g. the standard input, like
+    /// [from_str](Exam::from_str). The whole content is read before parsing
+    /// it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, FileFormat};
+    ///
+    /// let content = "name,grade\nJoan Beltrán Peris,4.6\nDavid Jiménez Hidalgo,7.94";
+    /// let exam = Exam::from_reader(content.as_bytes(), FileFormat::Csv).unwrap();
+    ///
+    /// assert_eq!(exam.statistics().highest_grade(), 7.94);
+    /// ```
+    ///
+    /// ```no_run
+    /// use std::io;
+    ///
+    /// use exms::exam::{Exam, FileFormat};
+    ///
+    /// let exam = Exam::from_reader(io::stdin(), FileFormat::Toml).unwrap();
+    /// ```
+    pub fn from_reader<R: Read>(reader: R, format: FileFormat) -> Result<Self, ParseError> {
+        parse_reader(format, reader)
+    }
+
+    /// Creates a new `Exam` from a given file like
+    /// [from_file](Exam::from_file), failing if the file exceeds any of the
+    /// given [Limits]. Use it to parse files uploaded by untrusted users.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::error::ParseError;
+    /// use exms::exam::{Exam, Limits};
+    ///
+    /// fn main() -> Result<(), ParseError> {
+    ///     let limits = Limits {
+    ///         max_file_size: Some(1024 * 1024),
+    ///         max_students: Some(1000),
+    ///         max_name_length: Some(100),
+    ///     };
+    ///     let exam = Exam::from_file_with_limits("upload.json", &limits)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_file_with_limits(
+        path: impl AsRef<Path>,
+        limits: &Limits,
+    ) -> Result<Self, ParseError> {
+        let options = ParseOptions {
+            limits: limits.clone(),
+            ..Default::default()
+        };
+
+        parse_exam_file(path.as_ref(), &options)
+    }
+
+    /// Creates a new `Exam` from a given file like
+    /// [from_file](Exam::from_file), parsing it with the given
+    /// [ParseOptions], e.g. to reject files with repeated students or to
+    /// validate their grades.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::error::ParseError;
+    /// use exms::exam::{Exam, Limits, ParseOptions};
+    ///
+    /// fn main() -> Result<(), ParseError> {
+    ///     let options = ParseOptions {
+    ///         limits: Limits {
+    ///             max_file_size: Some(1024 * 1024),
+    ///             ..Default::default()
+    ///         },
+    ///         reject_duplicates: true,
+    ///         validate_grades: true,
+    ///         ..Default::default()
+    ///     };
+    ///     let exam = Exam::from_file_with_options("upload.json", &options)?;
+    ///
+    ///     for warning in exam.warnings() {
+    ///         eprintln!("{warning}");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_file_with_options(
+        path: impl AsRef<Path>,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        parse_exam_file(path.as_ref(), options)
+    }
+
+    /// Creates a new `Exam` from a given file like
+    /// [from_file](Exam::from_file), but memory-mapping the file instead of
+    /// reading it into memory, and parsing it with the given [ParseOptions].
+    ///
+    /// This keeps the peak memory usage low for very large files, as the file
+    /// content doesn't need to be copied and already parsed parts of it can be
+    /// paged out. CSV files are parsed line by line straight from the mapped
+    /// file. The [Limits] on the students are checked as they are read, so
+    /// files exceeding them are rejected without reading the rest of them.
+    ///
+    /// The file must not be modified by other processes while it is being
+    /// parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::error::ParseError;
+    /// use exms::exam::{Exam, Limits, ParseOptions};
+    ///
+    /// fn main() -> Result<(), ParseError> {
+    ///     let options = ParseOptions {
+    ///         limits: Limits {
+    ///             max_students: Some(1_000_000),
+    ///             ..Default::default()
+    ///         },
+    ///         ..Default::default()
+    ///     };
+    ///     let exam = Exam::from_file_mapped("students.csv", &options)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub fn from_file_mapped(
+        path: impl AsRef<Path>,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        parse_mapped_exam_file(path.as_ref(), options)
+    }
+
+    /// Creates a new `Exam` from a given file like
+    /// [from_file_with_options](Exam::from_file_with_options), reusing the
+    /// result of a previous call if neither the file nor the options changed
+    /// since.
+    ///
+    /// Parsed exams are stored as snapshots in `cache_dir`, keyed by a SHA-256
+    /// hash of the options and of the file name and content, so any change to
+    /// any of them invalidates the snapshot. The length and the hash of the
+    /// content are checked again when reading a snapshot. Snapshots that are
+    /// no longer used can be safely deleted. Failing to write a snapshot
+    /// doesn't make the exam fail to load.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs;
+    ///
+    /// use exms::exam::{Exam, ParseOptions};
+    ///
+    /// let dir = std::env::temp_dir().join("exms_cache_example");
+    /// let _ = fs::remove_dir_all(&dir);
+    /// fs::create_dir_all(&dir).unwrap();
+    ///
+    /// let path = dir.join("exam.csv");
+    /// fs::write(&path, "name,grade\nJoan Beltrán Peris,4.6\nJose  Abad,12").unwrap();
+    ///
+    /// let options = ParseOptions {
+    ///     validate_grades: true,
+    ///     check_names: true,
+    ///     ..Default::default()
+    /// };
+    /// let cache_dir = dir.join("cache");
+    ///
+    /// let fresh = Exam::from_file_with_options(&path, &options).unwrap();
+    /// let missed = Exam::from_file_cached(&path, &cache_dir, &options).unwrap();
+    /// let hit = Exam::from_file_cached(&path, &cache_dir, &options).unwrap();
+    ///
+    /// assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+    /// assert_eq!(hit.warnings().len(), 2);
+    /// assert_eq!(
+    ///     format!("{:?}", hit.warnings()),
+    ///     format!("{:?}", fresh.warnings())
+    /// );
+    /// assert_eq!(
+    ///     serde_json::to_string(&hit).unwrap(),
+    ///     serde_json::to_string(&fresh).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     serde_json::to_string(&missed).unwrap(),
+    ///     serde_json::to_string(&fresh).unwrap()
+    /// );
+    /// ```
+    pub fn from_file_cached(
+        path: impl AsRef<Path>,
+        cache_dir: impl AsRef<Path>,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        cache::from_file_cached(path.as_ref(), cache_dir.as_ref(), options)
+    }
+
+    /// Creates a new exam from an Excel (`.xlsx`) workbook, choosing the sheet
+    /// and the columns to read with the given [XlsxOptions].
+    ///
+    /// [Exam::from_file()] also reads `.xlsx` files, using the default options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::error::ParseError;
+    /// use exms::exam::{Exam, XlsxOptions};
+    ///
+    /// fn main() -> Result<(), ParseError> {
+    ///     let options = XlsxOptions {
+    ///         sheet: Some("Final".to_string()),
+    ///         grade_column: 3,
+    ///         id_column: Some(0),
+    ///         name_column: 1,
+    ///         ..Default::default()
+    ///     };
+    ///     let exam = Exam::from_xlsx("grades.xlsx", &options)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "xlsx")]
+    pub fn from_xlsx(path: impl AsRef<Path>, options: &XlsxOptions) -> Result<Self, ParseError> {
+        parse_xlsx_file(path.as_ref(), options, &ParseOptions::default())
+    }
+
+    /// Watches the given exam file, calling `on_change` with the exam parsed
+    /// again every time the file is saved, so statistics and tables can be
+    /// rendered again while grades are being entered. It's also called once
+    /// with the current content of the file. Requires the `watch` feature.
+    ///
+    /// The file is parsed like in [from_file](Exam::from_file). `on_change`
+    /// receives the error if the file can't be parsed, e.g. because it's only
+    /// half written, and will be called again on the next change. It's called
+    /// from a background thread until the returned [ExamWatcher] is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// use exms::exam::Exam;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let _watcher = Exam::watch("students.toml", |exam| match exam {
+    ///         Ok(exam) => exam.summary(),
+    ///         Err(err) => eprintln!("{err}"),
+    ///     })?;
+    ///
+    ///     thread::sleep(Duration::from_secs(60));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "watch")]
+    pub fn watch<F>(path: impl AsRef<Path>, on_change: F) -> io::Result<ExamWatcher>
+    where
+        F: FnMut(Result<Exam, ParseError>) + Send + 'static,
+    {
+        watch::watch(path.as_ref(), &ParseOptions::default(), on_change)
+    }
+
+    /// Same as [watch](Exam::watch), but parsing the file with the given
+    /// [ParseOptions] every time, like in
+    /// [from_file_with_options](Exam::from_file_with_options).
+    #[cfg(feature = "watch")]
+    pub fn watch_with_options<F>(
+        path: impl AsRef<Path>,
+        options: &ParseOptions,
+        on_change: F,
+    ) -> io::Result<ExamWatcher>
+    where
+        F: FnMut(Result<Exam, ParseError>) + Send + 'static,
+    {
+        watch::watch(path.as_ref(), options, on_change)
+    }
+
+    /// Returns the [ExamDocument] of the exam, with its details and the grade
+    /// and question scores of each student, which can be written to any
+    /// supported format and parsed back into the same exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, FileFormat, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_max_grade(8.0).unwrap();
+    ///
+    /// let toml = exam.to_document().to_string(FileFormat::Toml).unwrap();
+    /// let parsed = Exam::from_str(&toml, FileFormat::Toml).unwrap();
+    ///
+    /// assert_eq!(parsed.statistics(), exam.statistics());
+    /// ```
+    pub fn to_document(&self) -> ExamDocument {
+        ExamDocument::from(self)
+    }
+
+    /// Writes the exam to the given writer in the given format, see
+    /// [ExamDocument::write].
+    pub fn write_to<W: Write>(&self, writer: W, format: FileFormat) -> io::Result<()> {
+        self.to_document().write(writer, format)
+    }
+
+    /// Sets the maximum achievable grade in the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_max_grade(6.0).unwrap();
+    /// ```
+    pub fn set_max_grade(&mut self, max_grade: f32) -> Result<(), PublishedError> {
+        self.check_not_published("setting the maximum grade")?;
+
+        self.max_grade = max_grade;
+        self.compute_statistics();
+
+        Ok(())
+    }
+
+    // Computes the statistics and the standings of the students again, after
+    // any change to their grades or to the settings they depend on
+    pub(crate) fn compute_statistics(&mut self) {
+        let pass_grade = self.pass_grade();
+        self.statistics = Statistics::new(
+            &mut self.students,
+            self.max_grade,
+            pass_grade,
+            self.rank_method,
+        );
+
+        if let Some(pinned) = &self.pinned_standings {
+            pinned.apply(&mut self.students, &mut self.statistics);
+        }
+    }
+
+    /// Returns the minimum grade needed to pass the exam. Unless a threshold
+    /// has been set with [set_pass_threshold](Exam::set_pass_threshold), it is
+    /// half the maximum grade.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_max_grade(12.0).unwrap();
+    ///
+    /// assert_eq!(exam.pass_grade(), 6.0);
+    /// ```
+    pub fn pass_grade(&self) -> f32 {
+        self.pass_threshold.unwrap_or(self.max_grade / 2.0)
+    }
+
+    /// Sets the minimum grade needed to pass the exam, used to count the
+    /// passed students and to color the grades of the students table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_max_grade(12.0).unwrap();
+    /// exam.set_pass_threshold(4.0).unwrap();
+    ///
+    /// assert_eq!(exam.statistics().passed_students(), 2);
+    /// ```
+    pub fn set_pass_threshold(&mut self, pass_grade: f32) -> Result<(), PublishedError> {
+        self.check_not_published("setting the pass threshold")?;
+
+        self.pass_threshold = Some(pass_grade);
+        self.compute_statistics();
+
+        Ok(())
+    }
+
+    /// Returns the unit in which the exam grades are expressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, GradeUnit, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// assert_eq!(exam.unit(), GradeUnit::Points);
+    /// ```
+    pub fn unit(&self) -> GradeUnit {
+        self.unit
+    }
+
+    /// Sets the unit in which the exam grades are expressed, without modifying
+    /// the grades. As percentages always range from 0 to 100, setting the unit
+    /// to [GradeUnit::Percentage] also sets the maximum grade to 100.
+    ///
+    /// To rescale the grades from one unit to the other use
+    /// [convert_to_percentage](Exam::convert_to_percentage) and
+    /// [convert_to_points](Exam::convert_to_points) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, GradeUnit, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 46.0),
+    ///     Student::new("Jose Abad Martínez", 36.0),
+    ///     Student::new("David Jiménez Hidalgo", 79.4),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_unit(GradeUnit::Percentage).unwrap();
+    ///
+    /// assert_eq!(exam.unit(), GradeUnit::Percentage);
+    /// ```
+    pub fn set_unit(&mut self, unit: GradeUnit) -> Result<(), PublishedError> {
+        self.check_not_published("setting the grade unit")?;
+
+        self.unit = unit;
+
+        if unit == GradeUnit::Percentage {
+            self.max_grade = 100.0;
+            self.compute_statistics();
+        }
+
+        Ok(())
+    }
+
+    /// Rescales the exam grades so they are expressed as a percentage of the
+    /// maximum grade.
+    ///
+    /// The grades are not converted if the maximum grade of the exam is not a
+    /// number greater than 0, and the conversion is recorded as rejected in
+    /// the [transformations](Exam::transformations) of the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, GradeUnit, Student};
+    ///
+    /// let students = &[Student::new("Joan Beltrán Peris", 4.5)];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.convert_to_percentage().unwrap();
+    ///
+    /// assert_eq!(exam.unit(), GradeUnit::Percentage);
+    /// assert_eq!(exam.find("joan")[0].grade, 45.0);
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_max_grade(0.0).unwrap();
+    /// exam.convert_to_percentage().unwrap();
+    ///
+    /// assert_eq!(exam.unit(), GradeUnit::Points);
+    /// assert_eq!(exam.find("joan")[0].grade, 4.5);
+    /// assert!(exam.transformations()[0].starts_with("Rejected"));
+    /// ```
+    pub fn convert_to_percentage(&mut self) -> Result<(), PublishedError> {
+        self.check_not_published("converting grades to percentages")?;
+
+        if self.unit == GradeUnit::Percentage {
+            return Ok(());
+        }
+
+        if self.rescale(100.0, "grades to percentages") {
+            self.unit = GradeUnit::Percentage;
+        }
+
+        Ok(())
+    }
+
+    /// Rescales the exam grades so they are expressed as points out of the
+    /// given maximum grade.
+    ///
+    /// Like in [convert_to_percentage](Exam::convert_to_percentage), the
+    /// grades are not converted if the given maximum grade or the one of the
+    /// exam is not a number greater than 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, GradeUnit, Student};
+    ///
+    /// let students = &[Student::new("Joan Beltrán Peris", 45.0)];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_unit(GradeUnit::Percentage).unwrap();
+    /// exam.convert_to_points(10.0).unwrap();
+    ///
+    /// assert_eq!(exam.unit(), GradeUnit::Points);
+    /// assert_eq!(exam.find("joan")[0].grade, 4.5);
+    ///
+    /// exam.convert_to_points(f32::NAN).unwrap();
+    ///
+    /// assert_eq!(exam.find("joan")[0].grade, 4.5);
+    /// assert!(exam
+    ///     .transformations()
+    ///     .last()
+    ///     .unwrap()
+    ///     .starts_with("Rejected"));
+    /// ```
+    pub fn convert_to_points(&mut self, max_grade: f32) -> Result<(), PublishedError> {
+        self.check_not_published("converting grades to points")?;
+
+        if self.rescale(max_grade, &format!("grades to points out of {max_grade}")) {
+            self.unit = GradeUnit::Points;
+        }
+
+        Ok(())
+    }
+
+    /// Curves the exam grades with the given [CurveMethod] and recomputes the
+    /// exam statistics.
+    ///
+    /// Curves whose parameters are not valid, see [CurveMethod::is_valid], are
+    /// not applied and are recorded as rejected in the
+    /// [transformations](Exam::transformations) of the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{CurveMethod, Exam, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 8.0),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.apply_curve(CurveMethod::ScaleHighestTo(10.0)).unwrap();
+    ///
+    /// assert_eq!(exam.find("david")[0].grade, 10.0);
+    /// assert_eq!(exam.find("jose")[0].grade, 4.5);
+    ///
+    /// exam.apply_curve(CurveMethod::Bonus(1.0)).unwrap();
+    ///
+    /// assert_eq!(exam.find("david")[0].grade, 10.0);
+    /// assert_eq!(exam.find("jose")[0].grade, 5.5);
+    ///
+    /// exam.apply_curve(CurveMethod::Normalize {
+    ///     mean: f32::NAN,
+    ///     std_dev: 1.0,
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(exam.find("jose")[0].grade, 5.5);
+    /// assert!(exam
+    ///     .transformations()
+    ///     .last()
+    ///     .unwrap()
+    ///     .starts_with("Rejected"));
+    /// ```
+    pub fn apply_curve(&mut self, method: CurveMethod) -> Result<(), PublishedError> {
+        self.check_not_published("curving grades")?;
+        if !method.is_valid() {
+            self.transformations.push(format!(
+                "Rejected curve: {method}: its parameters are not valid"
+            ));
+            return Ok(());
+        }
+
+        curve::apply_curve(
+            &mut self.students,
+            method,
+            self.max_grade,
+            self.statistics.mean,
+            self.statistics.std_dev,
+        );
+
+        self.compute_statistics();
+        self.transformations
+            .push(format!("Curved grades: {method}"));
+
+        Ok(())
+    }
+
+    /// Returns the students whose grade doesn't match the total of their
+    /// question scores, weighted and rounded as given by the options, without
+    /// changing their grades. Students without question scores are not
+    /// checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, RetotalOptions, Student};
+    ///
+    /// let mut students = vec![
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.5), ("Q2", 2.1)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 1.0), ("Q2", 2.6)]),
+    /// ];
+    /// students[1].grade = 4.0;
+    ///
+    /// let exam = Exam::new(students);
+    /// let discrepancies = exam.grade_discrepancies(&RetotalOptions::default());
+    ///
+    /// assert_eq!(discrepancies.len(), 1);
+    /// assert_eq!(discrepancies[0].name, "Jose Abad Martínez");
+    /// assert_eq!(discrepancies[0].grade, 4.0);
+    /// assert_eq!(discrepancies[0].total, 3.6);
+    /// ```
+    pub fn grade_discrepancies(&self, options: &RetotalOptions) -> Vec<GradeDiscrepancy> {
+        retotal::discrepancies(&self.students, options)
+    }
+
+    /// Sets the grade of every student with question scores to the total of
+    /// their scores, weighted and rounded as given by the options, instead of
+    /// trusting the grades of the file, and recomputes the statistics.
+    /// Returns the students whose grade changed, as reported by
+    /// [grade_discrepancies](Exam::grade_discrepancies). Fails if the exam is
+    /// [published](Exam::publish).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, RetotalOptions, ScoreRounding, Student};
+    ///
+    /// let students = &[
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.4), ("Q2", 2.1)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 1.0), ("Q2", 2.5)]),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// let options = RetotalOptions {
+    ///     weights: [("Q2".to_string(), 2.0)].into_iter().collect(),
+    ///     rounding: ScoreRounding::Nearest(0.5),
+    ///     ..Default::default()
+    /// };
+    /// let discrepancies = exam.retotal(&options).unwrap();
+    ///
+    /// assert_eq!(discrepancies.len(), 2);
+    /// assert_eq!(exam.find("joan")[0].grade, 6.5);
+    /// assert_eq!(exam.find("jose")[0].grade, 6.0);
+    /// assert_eq!(exam.find("david")[0].grade, 7.9);
+    /// assert_eq!(exam.statistics().lowest_grade(), 6.0);
+    /// ```
+    pub fn retotal(
+        &mut self,
+        options: &RetotalOptions,
+    ) -> Result<Vec<GradeDiscrepancy>, PublishedError> {
+        self.check_not_published("recomputing grades from question scores")?;
+
+        let discrepancies = self.grade_discrepancies(options);
+
+        for student in &mut self.students {
+            if let Some(total) = retotal::total(student, options) {
+                student.grade = total;
+            }
+        }
+
+        self.compute_statistics();
+        self.transformations.push(format!(
+            "Recomputed grades from question scores: {} changed",
+            discrepancies.len()
+        ));
+
+        Ok(discrepancies)
+    }
+
+    // Rescales the grades to the given maximum grade and logs the conversion,
+    // unless the current or the new maximum grade is not a number greater
+    // than 0, in which case the conversion is logged as rejected
+    fn rescale(&mut self, max_grade: f32, conversion: &str) -> bool {
+        let valid = |max_grade: f32| max_grade.is_finite() && max_grade > 0.0;
+        if !valid(self.max_grade) || !valid(max_grade) {
+            self.transformations.push(format!(
+                "Rejected converting {conversion}: the maximum grade is not greater than 0"
+            ));
+            return false;
+        }
+
+        let factor = max_grade / self.max_grade;
+        for student in &mut self.students {
+            student.grade *= factor;
+        }
+        self.pass_threshold = self.pass_threshold.map(|pass_grade| pass_grade * factor);
+
+        self.max_grade = max_grade;
+        self.compute_statistics();
+        self.transformations.push(format!("Converted {conversion}"));
+
+        true
+    }
+
+    /// Sets the title of the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_title("Econometrics").unwrap();
+    /// ```
+    pub fn set_title(&mut self, title: impl Into<String>) -> Result<(), PublishedError> {
+        self.check_not_published("setting the title")?;
+
+        self.title = Some(title.into());
+
+        Ok(())
+    }
+
+    /// Sets the date the exam took place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_date("2024-06-14").unwrap();
+    /// ```
+    pub fn set_date(&mut self, date: impl Into<String>) -> Result<(), PublishedError> {
+        self.check_not_published("setting the date")?;
+
+        self.date = Some(date.into());
+
+        Ok(())
+    }
+
+    /// Sets the code of the course the exam belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_course("ECON-201").unwrap();
+    /// ```
+    pub fn set_course(&mut self, course: impl Into<String>) -> Result<(), PublishedError> {
+        self.check_not_published("setting the course")?;
+
+        self.course = Some(course.into());
+
+        Ok(())
+    }
+
+    /// Sets the academic term in which the exam took place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_term("2023-24 Spring").unwrap();
+    /// ```
+    pub fn set_term(&mut self, term: impl Into<String>) -> Result<(), PublishedError> {
+        self.check_not_published("setting the term")?;
+
+        self.term = Some(term.into());
+
+        Ok(())
+    }
+
+    /// Sets the name of the instructor of the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_instructor("María López").unwrap();
+    /// ```
+    pub fn set_instructor(&mut self, instructor: impl Into<String>) -> Result<(), PublishedError> {
+        self.check_not_published("setting the instructor")?;
+
+        self.instructor = Some(instructor.into());
+
+        Ok(())
+    }
+
+    /// Sets the maximum achievable score in the given question, used to
+    /// compute the pass rate of the question. See
+    /// [question_statistics](Exam::question_statistics).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.5), ("Q2", 2.1)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 1.0), ("Q2", 2.6)]),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_question_max_score("Q1", 5.0).unwrap();
+    /// ```
+    pub fn set_question_max_score(
+        &mut self,
+        question: impl Into<String>,
+        max_score: f32,
+    ) -> Result<(), PublishedError> {
+        self.check_not_published("setting a question maximum score")?;
+
+        self.question_max_scores.insert(question.into(), max_score);
+
+        Ok(())
+    }
+
+    /// Sets the correct option of the given multiple-choice question, used to
+    /// tell the wrong options apart in the
+    /// [distractor analysis](Exam::distractor_analysis).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let mut joan = Student::new("Joan Beltrán Peris", 4.6);
+    /// joan.answers.insert("Q1".to_string(), "B".to_string());
+    ///
+    /// let mut exam = Exam::new(vec![joan]);
+    /// exam.set_answer_key("Q1", "A").unwrap();
+    ///
+    /// assert_eq!(exam.distractor_analysis()[0].distractors[0].option, "B");
+    /// ```
+    pub fn set_answer_key(
+        &mut self,
+        question: impl Into<String>,
+        option: impl Into<String>,
+    ) -> Result<(), PublishedError> {
+        self.check_not_published("setting the answer key")?;
+
+        self.answer_key.insert(question.into(), option.into());
+
+        Ok(())
+    }
+
+    /// Sets the scale used to show an additional, normalized grade column in
+    /// the students table, e.g. `Some(10.0)` to show each grade out of 10 or
+    /// `Some(100.0)` to show it as a percentage. The column is not shown when
+    /// the scale is the same as the maximum grade of the exam. Use `None` to
+    /// stop showing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 14.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 17.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_max_grade(20.0).unwrap();
+    /// exam.set_normalized_scale(Some(10.0));
+    /// exam.print_students(None);
+    /// ```
+    pub fn set_normalized_scale(&mut self, scale: Option<f32>) {
+        self.normalized_scale = scale;
+    }
+
+    /// Sets the [OutlierMethod] used to flag outliers in the students table
+    /// with an additional column. Use `None` to stop flagging them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, OutlierMethod, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 6.5),
+    ///     Student::new("Jose Abad Martínez", 7.0),
+    ///     Student::new("David Jiménez Hidalgo", 6.8),
+    ///     Student::new("Irene Alcántara Campillo", 7.2),
+    ///     Student::new("Jorge Rodríguez Martín", 0.5),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_outlier_marker(Some(OutlierMethod::Iqr(1.5)));
+    ///
+    /// let mut output = Vec::new();
+    /// exam.students_to(&mut output, None).unwrap();
+    ///
+    /// assert!(String::from_utf8(output).unwrap().contains("Outlier"));
+    /// ```
+    pub fn set_outlier_marker(&mut self, method: Option<OutlierMethod>) {
+        self.outlier_marker = method;
+    }
+
+    /// Sets the [FormatRule]s used to style the rows of the students table and
+    /// of the students table of the [Markdown report](Exam::to_markdown).
+    /// Each row is styled by the first rule its student meets, if any. Without
+    /// rules, the default, only the grades are painted in the terminal, in
+    /// green if they pass and in red if they don't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, FormatRule, RuleColor, RuleCondition, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_format_rules([
+    ///     FormatRule::new(RuleCondition::GradeBelow(4.0), RuleColor::Red).bold(),
+    ///     FormatRule::new(RuleCondition::RankAtMost(1.0), RuleColor::Gold),
+    /// ]);
+    /// exam.print_students(None);
+    ///
+    /// let markdown = exam.to_markdown();
+    ///
+    /// assert!(markdown.contains("| 🔴 **Jose Abad Martínez** | **3.6** |"));
+    /// assert!(markdown.contains("| 🥇 David Jiménez Hidalgo | 7.94 |"));
+    /// assert_eq!(exam.format_rules().len(), 2);
+    /// ```
+    pub fn set_format_rules(&mut self, rules: impl Into<Vec<FormatRule>>) {
+        self.format_rules = rules.into();
+    }
+
+    /// Returns the [FormatRule]s used to style the rows of the students table.
+    pub fn format_rules(&self) -> &[FormatRule] {
+        &self.format_rules
+    }
+
+    /// Sets the [GradeScale] used to classify the exam students into labeled
+    /// bands. Once set, the students table shows the band of each student, the
+    /// summary shows the number of students in each band and, unless a step is
+    /// given, the histogram buckets match the bands.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, GradeScale, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_grade_scale(GradeScale::spanish()).unwrap();
+    ///
+    /// let buckets = exam.histogram_buckets(None);
+    ///
+    /// assert_eq!(buckets[0], (0.0..5.0, 2));
+    /// assert_eq!(buckets[2], (7.0..9.0, 1));
+    /// ```
+    pub fn set_grade_scale(&mut self, scale: GradeScale) -> Result<(), PublishedError> {
+        self.check_not_published("setting the grade scale")?;
+
+        self.grade_scale = Some(scale);
+
+        Ok(())
+    }
+
+    /// Returns the [GradeScale] of the exam, if any.
+    pub fn grade_scale(&self) -> Option<&GradeScale> {
+        self.grade_scale.as_ref()
+    }
+
+    /// Sets how students with the same grade are ranked and computes the
+    /// ranks again. Defaults to [RankMethod::Dense].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, RankMethod, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 9.0),
+    ///     Student::new("Jose Abad Martínez", 8.0),
+    ///     Student::new("David Jiménez Hidalgo", 8.0),
+    ///     Student::new("Irene Alcántara Campillo", 7.0),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// let ranks =
+    ///     |exam: &Exam| -> Vec<f32> { exam.students().iter().filter_map(|s| s.rank()).collect() };
+    ///
+    /// assert_eq!(ranks(&exam), [1.0, 2.0, 2.0, 3.0]);
+    ///
+    /// exam.set_rank_method(RankMethod::Competition).unwrap();
+    /// assert_eq!(ranks(&exam), [1.0, 2.0, 2.0, 4.0]);
+    ///
+    /// exam.set_rank_method(RankMethod::Fractional).unwrap();
+    /// assert_eq!(ranks(&exam), [1.0, 2.5, 2.5, 4.0]);
+    /// ```
+    pub fn set_rank_method(&mut self, method: RankMethod) -> Result<(), PublishedError> {
+        self.check_not_published("setting the rank method")?;
+
+        self.rank_method = method;
+        self.compute_statistics();
+
+        Ok(())
+    }
+
+    /// Returns how students with the same grade are ranked.
+    pub fn rank_method(&self) -> RankMethod {
+        self.rank_method
+    }
+
+    /// Sorts the exam students based on their grade in descending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.sort_by_grade();
+    ///
+    /// assert_eq!(exam.students()[0].grade, 7.94);
+    /// assert_eq!(exam.students()[1].grade, 4.6);
+    /// assert_eq!(exam.students()[2].grade, 3.6);
+    /// ```
+    pub fn sort_by_grade(&mut self) {
+        // Sort students by name so that students with the same grade are sorted
+        // alphabetically
+        Self::sort_by_alphabetic_order(self);
+
+        self.students
+            .sort_by(|a, b| b.grade.partial_cmp(&a.grade).unwrap_or(Ordering::Equal))
+    }
+
+    /// Sorts the exam students based on their name alphabetically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.sort_by_alphabetic_order();
+    ///
+    /// assert_eq!(exam.students()[0].name, "David Jiménez Hidalgo");
+    /// assert_eq!(exam.students()[1].name, "Joan Beltrán Peris");
+    /// assert_eq!(exam.students()[2].name, "Jose Abad Martínez");
+    /// ```
+    pub fn sort_by_alphabetic_order(&mut self) {
+        self.students
+            .sort_by_key(|s| unidecode(&s.name.to_lowercase()))
+    }
+
+    /// Filters the exam students yielding only the students which name contains
+    /// the given query, ignoring case and accents, e.g. "jose" matches "José".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.filter_by_name(&["joan", "jorge", "jim"]).unwrap();
+    ///
+    /// assert_eq!(exam.students().len(), 2);
+    /// assert_eq!(exam.students()[0].name, "Joan Beltrán Peris");
+    /// assert_eq!(exam.students()[1].name, "David Jiménez Hidalgo");
+    /// ```
+    pub fn filter_by_name<S: AsRef<str>>(&mut self, query: &[S]) -> Result<(), PublishedError> {
+        self.check_not_published("filtering students by name")?;
+
+        self.students.retain(|student| {
+            query
+                .iter()
+                .any(|name| name_matches(student, name.as_ref()))
+        });
+
+        let query: Vec<&str> = query.iter().map(AsRef::as_ref).collect();
+        self.transformations
+            .push(format!("Filtered students by name: {}", query.join(", ")));
+
+        Ok(())
+    }
+
+    /// Same as [filter_by_name](Exam::filter_by_name), but also keeping the
+    /// students which name contains the query with up to `max_typos` typos,
+    /// that is, characters inserted, removed or replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.filter_by_name_fuzzy(&["beltan", "jimenes"], 1).unwrap();
+    ///
+    /// assert_eq!(exam.find("").len(), 2);
+    /// assert_eq!(exam.find("")[0].name, "Joan Beltrán Peris");
+    /// assert_eq!(exam.find("")[1].name, "David Jiménez Hidalgo");
+    /// ```
+    pub fn filter_by_name_fuzzy<S: AsRef<str>>(
+        &mut self,
+        query: &[S],
+        max_typos: usize,
+    ) -> Result<(), PublishedError> {
+        self.check_not_published("filtering students by name")?;
+
+        self.students.retain(|student| {
+            query
+                .iter()
+                .any(|name| name_matches_fuzzy(student, name.as_ref(), max_typos))
+        });
+
+        let query: Vec<&str> = query.iter().map(AsRef::as_ref).collect();
+        self.transformations.push(format!(
+            "Filtered students by name with up to {max_typos} typos: {}",
+            query.join(", ")
+        ));
+
+        Ok(())
+    }
+
+    /// Returns the exam students which name contains the given query, without
+    /// removing the rest of the students from the exam. Names are matched in
+    /// the same way as in [filter_by_name](Exam::filter_by_name).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let found = exam.find("jo");
+    ///
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[0].name, "Joan Beltrán Peris");
+    /// assert_eq!(found[1].name, "Jose Abad Martínez");
+    /// ```
+    pub fn find(&self, query: &str) -> Vec<&Student> {
+        self.students
+            .iter()
+            .filter(|student| name_matches(student, query))
+            .collect()
+    }
+
+    /// Filters the exam students yielding only the students with any of the
+    /// given [IDs](Student::id). Unlike [filter_by_name](Exam::filter_by_name),
+    /// IDs must match exactly, ignoring surrounding whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let mut joan = Student::new("Joan Beltrán Peris", 4.6);
+    /// joan.id = Some("12345".to_string());
+    /// let mut jose = Student::new("Jose Abad Martínez", 3.6);
+    /// jose.id = Some("123456".to_string());
+    /// let david = Student::new("David Jiménez Hidalgo", 7.94);
+    ///
+    /// let mut exam = Exam::new(vec![joan, jose, david]);
+    /// exam.filter_by_id(&["12345"]).unwrap();
+    ///
+    /// assert_eq!(exam.find("").len(), 1);
+    /// assert_eq!(exam.find("")[0].name, "Joan Beltrán Peris");
+    /// ```
+    pub fn filter_by_id<S: AsRef<str>>(&mut self, ids: &[S]) -> Result<(), PublishedError> {
+        self.check_not_published("filtering students by ID")?;
+
+        self.students.retain(|student| {
+            student
+                .id
+                .as_deref()
+                .is_some_and(|id| ids.iter().any(|query| query.as_ref().trim() == id.trim()))
+        });
+
+        let ids: Vec<&str> = ids.iter().map(AsRef::as_ref).collect();
+        self.transformations
+            .push(format!("Filtered students by ID: {}", ids.join(", ")));
+
+        Ok(())
+    }
+
+    /// Filters the exam students yielding only the students that are in the
+    /// given file. The file format should be the same as the one used in
+    /// [from_file](Exam::from_file). Names are compared ignoring case, accents
+    /// and repeated or surrounding whitespace.
+    ///
+    /// Fails if any of the files can't be parsed, or if the exam is
+    /// [published](Exam::publish).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// use exms::error::FilterError;
+    /// use exms::exam::Exam;
+    ///
+    /// fn main() -> Result<(), FilterError> {
+    ///     let file_path = Path::new("students.json");
+    ///     let mut exam = Exam::from_file(&file_path)?;
+    ///
+    ///     let file_path = Path::new("students2.json");
+    ///     exam.filter_by_file(&[file_path])?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn filter_by_file<P: AsRef<Path>>(&mut self, file_paths: &[P]) -> Result<(), FilterError> {
+        self.check_not_published("filtering students by file")?;
+        self.retain_students_in_files(file_paths)?;
+
+        Ok(())
+    }
+
+    // Same as `filter_by_file`, for exams that are known not to be published
+    pub(crate) fn retain_students_in_files<P: AsRef<Path>>(
+        &mut self,
+        file_paths: &[P],
+    ) -> Result<(), ParseError> {
+        for path in file_paths {
+            let exam = parse_exam_file(path.as_ref(), &ParseOptions::default())?;
+            let students = exam.students;
+
+            self.students.retain(|student| {
+                let name = normalized_name(&student.name);
+                students.iter().any(|s| normalized_name(&s.name) == name)
+            });
+
+            self.transformations.push(format!(
+                "Filtered students by file: {}",
+                path.as_ref().display()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns a copy of the exam with only the students which name contains
+    /// the given query, like [filter_by_name](Exam::filter_by_name), leaving
+    /// the exam intact. The statistics of the copy are computed again for the
+    /// remaining students.
+    ///
+    /// The copy is a new exam: it is not published and its standings are not
+    /// pinned, even if the ones of the original exam are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let joans = exam.filtered_by_name(&["joan", "jim"]);
+    ///
+    /// assert_eq!(joans.statistics().total_students(), 2);
+    /// assert_eq!(joans.statistics().lowest_grade(), 4.6);
+    /// assert_eq!(exam.statistics().total_students(), 3);
+    /// ```
+    pub fn filtered_by_name<S: AsRef<str>>(&self, query: &[S]) -> Exam {
+        self.derive(|exam| {
+            exam.filter_by_name(query)
+                .expect("copies of an exam are not published")
+        })
+    }
+
+    /// Returns a copy of the exam with only the students with any of the given
+    /// IDs, like [filter_by_id](Exam::filter_by_id), leaving the exam intact.
+    /// See [filtered_by_name](Exam::filtered_by_name) for the details of the
+    /// copy.
+    pub fn filtered_by_id<S: AsRef<str>>(&self, ids: &[S]) -> Exam {
+        self.derive(|exam| {
+            exam.filter_by_id(ids)
+                .expect("copies of an exam are not published")
+        })
+    }
+
+    /// Returns a copy of the exam with only the students that are in the given
+    /// files, like [filter_by_file](Exam::filter_by_file), leaving the exam
+    /// intact. See [filtered_by_name](Exam::filtered_by_name) for the details
+    /// of the copy.
+    pub fn filtered_by_file<P: AsRef<Path>>(&self, file_paths: &[P]) -> Result<Exam, ParseError> {
+        let mut result = Ok(());
+        let exam = self.derive(|exam| result = exam.retain_students_in_files(file_paths));
+
+        result.map(|()| exam)
+    }
+
+    /// Returns a copy of the exam with its students sorted by grade in
+    /// descending order, like [sort_by_grade](Exam::sort_by_grade), leaving
+    /// the exam intact.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let sorted = exam.sorted_by_grade();
+    ///
+    /// assert_eq!(sorted.find("")[0].name, "David Jiménez Hidalgo");
+    /// assert_eq!(exam.find("")[0].name, "Joan Beltrán Peris");
+    /// ```
+    pub fn sorted_by_grade(&self) -> Exam {
+        self.derive(Exam::sort_by_grade)
+    }
+
+    /// Returns a copy of the exam with its students sorted by name
+    /// alphabetically, like
+    /// [sort_by_alphabetic_order](Exam::sort_by_alphabetic_order), leaving the
+    /// exam intact.
+    pub fn sorted_by_alphabetic_order(&self) -> Exam {
+        self.derive(Exam::sort_by_alphabetic_order)
+    }
+
+    // Copy of the exam, neither published nor with pinned standings, changed
+    // by `change` and with its statistics computed again
+    fn derive(&self, change: impl FnOnce(&mut Exam)) -> Exam {
+        let mut exam = self.clone();
+        exam.published = false;
+        exam.pinned_standings = None;
+
+        change(&mut exam);
+        exam.compute_statistics();
+
+        exam
+    }
+
+    /// Filters the exam students yielding only the students that are also in
+    /// every one of the given exams, like
+    /// [filter_by_file](Exam::filter_by_file) does with files, and recomputes
+    /// the statistics. Names are compared ignoring case, accents and
+    /// surrounding whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let mut first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ]);
+    /// let second = Exam::new(vec![
+    ///     Student::new("joan beltran peris", 6.1),
+    ///     Student::new("David Jiménez Hidalgo", 8.2),
+    /// ]);
+    /// let third = Exam::new(vec![Student::new("David Jiménez Hidalgo", 9.0)]);
+    ///
+    /// first.intersect(&[&second]).unwrap();
+    /// assert_eq!(first.statistics().total_students(), 2);
+    /// assert_eq!(first.find("").len(), 2);
+    ///
+    /// first.intersect(&[&second, &third]).unwrap();
+    /// assert_eq!(first.find("")[0].name, "David Jiménez Hidalgo");
+    /// assert_eq!(first.find("")[0].grade, 7.94);
+    /// assert_eq!(first.statistics().mean(), 7.94);
+    /// ```
+    pub fn intersect(&mut self, exams: &[&Exam]) -> Result<(), PublishedError> {
+        self.intersect_by(exams, MatchBy::Name)
+    }
+
+    /// Same as [intersect](Exam::intersect), but matching the students in the
+    /// given way, e.g. by their ID instead of their name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, MatchBy, Student};
+    ///
+    /// let mut joan = Student::new("Joan Beltrán Peris", 4.6);
+    /// joan.id = Some("12345".to_string());
+    /// let mut first = Exam::new(vec![joan, Student::new("Jose Abad Martínez", 3.6)]);
+    ///
+    /// let mut joan = Student::new("Beltrán Peris, Joan", 6.1);
+    /// joan.id = Some("12345".to_string());
+    /// let second = Exam::new(vec![joan]);
+    ///
+    /// first.intersect_by(&[&second], MatchBy::Id).unwrap();
+    ///
+    /// assert_eq!(first.find("").len(), 1);
+    /// assert_eq!(first.find("")[0].name, "Joan Beltrán Peris");
+    /// ```
+    pub fn intersect_by(
+        &mut self,
+        exams: &[&Exam],
+        match_by: MatchBy,
+    ) -> Result<(), PublishedError> {
+        self.check_not_published("intersecting students with other exams")?;
+
+        let rosters: Vec<HashSet<String>> = exams
+            .iter()
+            .map(|exam| {
+                exam.students
+                    .iter()
+                    .filter_map(|s| student_key(s, match_by))
+                    .collect()
+            })
+            .collect();
+
+        self.students.retain(|student| {
+            student_key(student, match_by)
+                .is_some_and(|key| rosters.iter().all(|roster| roster.contains(&key)))
+        });
+
+        self.transformations.push(format!(
+            "Kept students present in {} other exams{}",
+            exams.len(),
+            match_by.log_suffix()
+        ));
+        self.compute_statistics();
+
+        Ok(())
+    }
+
+    /// Adds to the exam the students of the given exams that are not in it
+    /// yet, with the grade they got in the first exam they appear in, and
+    /// recomputes the statistics. Names are compared in the same way as in
+    /// [intersect](Exam::intersect).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let mut first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    /// ]);
+    /// let second = Exam::new(vec![
+    ///     Student::new("joan beltran peris", 6.1),
+    ///     Student::new("David Jiménez Hidalgo", 8.2),
+    /// ]);
+    ///
+    /// first.union(&[&second]).unwrap();
+    ///
+    /// assert_eq!(first.statistics().total_students(), 3);
+    /// assert_eq!(first.find("joan")[0].grade, 4.6);
+    /// assert_eq!(first.statistics().highest_grade(), 8.2);
+    /// ```
+    pub fn union(&mut self, exams: &[&Exam]) -> Result<(), PublishedError> {
+        self.union_by(exams, MatchBy::Name)
+    }
+
+    /// Same as [union](Exam::union), but matching the students in the given
+    /// way, e.g. by their ID instead of their name.
+    pub fn union_by(&mut self, exams: &[&Exam], match_by: MatchBy) -> Result<(), PublishedError> {
+        self.check_not_published("adding students from other exams")?;
+
+        let mut keys: HashSet<String> = self
+            .students
+            .iter()
+            .filter_map(|s| student_key(s, match_by))
+            .collect();
+
+        for exam in exams {
+            for student in &exam.students {
+                let new = match student_key(student, match_by) {
+                    Some(key) => keys.insert(key),
+                    None => true,
+                };
+                if new {
+                    self.students.push(student.clone());
+                }
+            }
+        }
+
+        self.transformations.push(format!(
+            "Added students from {} other exams{}",
+            exams.len(),
+            match_by.log_suffix()
+        ));
+        self.compute_statistics();
+
+        Ok(())
+    }
+
+    /// Returns how many students moved from each band of the given
+    /// [GradeScale] in this exam to each band in the `later` exam, e.g. from
+    /// "Aprobado" to "Notable". Students are matched like in
+    /// [intersect](Exam::intersect), and only the students in both exams
+    /// with a grade in a band in both of them are counted. Grades are
+    /// classified as they are, so both exams should have the same maximum
+    /// grade.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, GradeScale, Student};
+    ///
+    /// let first = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ]);
+    /// let second = Exam::new(vec![
+    ///     Student::new("joan beltran peris", 6.1),
+    ///     Student::new("Jose Abad Martínez", 4.2),
+    ///     Student::new("David Jiménez Hidalgo", 6.8),
+    /// ]);
+    ///
+    /// let transitions = first.band_transitions(&second, &GradeScale::spanish());
+    ///
+    /// assert_eq!(transitions.count("Suspenso", "Aprobado"), Some(1));
+    /// assert_eq!(transitions.count("Suspenso", "Suspenso"), Some(1));
+    /// assert_eq!(transitions.count("Notable", "Aprobado"), Some(1));
+    /// assert_eq!(transitions.counts()[1], vec![0, 0, 0, 0]);
+    ///
+    /// transitions.print();
+    /// ```
+    pub fn band_transitions(&self, later: &Exam, scale: &GradeScale) -> BandTransitions {
+        self.band_transitions_by(later, scale, MatchBy::Name)
+    }
+
+    /// Same as [band_transitions](Exam::band_transitions), but matching the
+    /// students as given by `match_by`.
+    pub fn band_transitions_by(
+        &self,
+        later: &Exam,
+        scale: &GradeScale,
+        match_by: MatchBy,
+    ) -> BandTransitions {
+        // Students that appear more than once count with their first grade
+        let mut later_grades = HashMap::new();
+        for student in &later.students {
+            if let Some(key) = student_key(student, match_by) {
+                later_grades.entry(key).or_insert(student.grade);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let grades = self.students.iter().filter_map(|student| {
+            let key = student_key(student, match_by)?;
+            let later_grade = *later_grades.get(&key)?;
+            seen.insert(key).then_some((student.grade, later_grade))
+        });
+
+        BandTransitions::new(scale, grades)
+    }
+
+    /// Checks the exam students against the given [Roster], reporting the
+    /// enrolled students missing from the exam and the students in the exam
+    /// that are not enrolled. Names are compared ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Roster, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let roster = Roster::new([
+    ///     "joan beltrán peris",
+    ///     "Jose Abad Martínez",
+    ///     "Irene Alcántara",
+    /// ]);
+    ///
+    /// let report = exam.check_against_roster(&roster);
+    ///
+    /// assert_eq!(report.missing, vec!["Irene Alcántara"]);
+    /// assert_eq!(report.not_enrolled, vec!["David Jiménez Hidalgo"]);
+    /// assert!(!report.is_consistent());
+    /// ```
+    pub fn check_against_roster(&self, roster: &Roster) -> RosterReport {
+        roster.check(&self.students)
+    }
+
+    /// Summarizes the problems found in the exam data into a [QualityScore],
+    /// a score from 0 to 100 along with the issues found, sorted by their
+    /// [Severity]. Students that appear more than once, as reported by
+    /// [warnings](Exam::warnings), and grades out of the range of the exam, as
+    /// reported by [validate](Exam::validate), are checked always, and
+    /// students not matching the given [Roster], if any, are checked like in
+    /// [check_against_roster](Exam::check_against_roster). Each affected
+    /// student lowers the score according to the severity of the issue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, QualityIssue, Roster, Severity, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("joan beltran peris", 7.94),
+    ///     Student::new("David Jiménez Hidalgo", 12.0),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let roster = Roster::new([
+    ///     "Joan Beltrán Peris",
+    ///     "Jose Abad Martínez",
+    ///     "David Jiménez Hidalgo",
+    ///     "Irene Alcántara",
+    /// ]);
+    ///
+    /// let quality = exam.data_quality(Some(&roster));
+    ///
+    /// assert_eq!(
+    ///     quality.issues()[1],
+    ///     QualityIssue::OutOfRange(vec!["David Jiménez Hidalgo".to_string()])
+    /// );
+    /// assert_eq!(quality.issues()[3].severity(), Severity::Low);
+    /// assert_eq!(
+    ///     quality.issues()[0],
+    ///     QualityIssue::Duplicates(vec!["joan beltran peris".to_string()])
+    /// );
+    /// assert_eq!(quality.score(), 31.25);
+    /// assert!(!quality.is_clean(Severity::High));
+    ///
+    /// println!("{quality}");
+    /// ```
+    pub fn data_quality(&self, roster: Option<&Roster>) -> QualityScore {
+        quality::data_quality(self, roster)
+    }
+
+    /// Checks that the grades of the exam students are within the range of
+    /// the exam, from 0 to the maximum grade, returning an
+    /// [ExamWarning::GradeOutOfRange] for each student that is not, in the
+    /// order of the students. Such grades are truncated by the histogram and
+    /// skew the rest of the statistics.
+    ///
+    /// Exams can be validated when they are parsed with
+    /// [ParseOptions::validate_grades], adding these warnings to the
+    /// [warnings](Exam::warnings) of the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, ExamWarning, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", -1.0),
+    ///     Student::new("David Jiménez Hidalgo", 11.5),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    ///
+    /// assert_eq!(
+    ///     exam.validate(),
+    ///     vec![
+    ///         ExamWarning::GradeOutOfRange {
+    ///             student: "Jose Abad Martínez".to_string(),
+    ///             grade: -1.0,
+    ///         },
+    ///         ExamWarning::GradeOutOfRange {
+    ///             student: "David Jiménez Hidalgo".to_string(),
+    ///             grade: 11.5,
+    ///         },
+    ///     ]
+    /// );
+    ///
+    /// exam.set_max_grade(12.0).unwrap();
+    ///
+    /// assert_eq!(exam.validate().len(), 1);
+    /// ```
+    pub fn validate(&self) -> Vec<ExamWarning> {
+        self.students
+            .iter()
+            .filter(|student| student.grade < 0.0 || student.grade > self.max_grade)
+            .map(|student| ExamWarning::GradeOutOfRange {
+                student: student.name.clone(),
+                grade: student.grade,
+            })
+            .collect()
+    }
+
+    /// Returns the names of the exam students with anomalies that can make
+    /// them silently mismatch the same students in other files, like double
+    /// spaces, stray tabs or a different casing than most names, along with
+    /// the name they would be fixed to by
+    /// [normalize_names](Exam::normalize_names).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, NameAnomaly, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán  Peris", 4.6),
+    ///     Student::new("JOSE ABAD MARTÍNEZ\t", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let issues = exam.name_issues();
+    ///
+    /// assert_eq!(issues.len(), 2);
+    /// assert_eq!(issues[0].anomalies, [NameAnomaly::RepeatedWhitespace]);
+    /// assert_eq!(issues[0].normalized, "Joan Beltrán Peris");
+    /// assert_eq!(
+    ///     issues[1].anomalies,
+    ///     [
+    ///         NameAnomaly::SurroundingWhitespace,
+    ///         NameAnomaly::UnusualWhitespace,
+    ///         NameAnomaly::InconsistentCasing
+    ///     ]
+    /// );
+    /// assert_eq!(issues[1].normalized, "JOSE ABAD MARTÍNEZ");
+    /// ```
+    pub fn name_issues(&self) -> Vec<NameIssue> {
+        names::name_issues(&self.students)
+    }
+
+    /// Fixes the whitespace of the names of the exam students, as reported by
+    /// [name_issues](Exam::name_issues), and returns the issues fixed. Names
+    /// with an [inconsistent casing](NameAnomaly::InconsistentCasing) are
+    /// left as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, NameAnomaly, Student};
+    ///
+    /// let students = &[
+    ///     Student::new(" Joan Beltrán\tPeris", 4.6),
+    ///     Student::new("JOSE DE LA FUENTE", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// let fixed = exam.normalize_names().unwrap();
+    ///
+    /// assert_eq!(fixed.len(), 1);
+    /// assert_eq!(exam.students()[0].name, "Joan Beltrán Peris");
+    /// assert_eq!(exam.students()[1].name, "JOSE DE LA FUENTE");
+    /// assert_eq!(
+    ///     exam.name_issues()[0].anomalies,
+    ///     [NameAnomaly::InconsistentCasing]
+    /// );
+    /// ```
+    pub fn normalize_names(&mut self) -> Result<Vec<NameIssue>, PublishedError> {
+        self.check_not_published("normalizing the students names")?;
+
+        let issues: Vec<NameIssue> = self
+            .name_issues()
+            .into_iter()
+            .filter(|issue| issue.normalized != issue.name)
+            .collect();
+        if issues.is_empty() {
+            return Ok(issues);
+        }
+
+        for student in &mut self.students {
+            student.name = names::normalize(&student.name);
+        }
+
+        self.transformations
+            .push(format!("Normalized the names of {} students", issues.len()));
+
+        Ok(issues)
+    }
+
+    /// Returns a log of the transformations applied to the exam students since
+    /// the exam was created, such as filters or grade conversions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.filter_by_name(&["joan", "david"]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     exam.transformations(),
+    ///     &["Filtered students by name: joan, david"]
+    /// );
+    /// ```
+    pub fn transformations(&self) -> &[String] {
+        &self.transformations
+    }
+
+    /// Returns the problems found in the exam students, such as students that
+    /// appear more than once. They are checked against the current students,
+    /// so they are up to date after filtering them or adding students from
+    /// other exams. Use [ParseOptions::reject_duplicates] to fail to parse
+    /// files with repeated students instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{
+    ///     parse_bytes, parse_bytes_with_options, ExamWarning, FileFormat, ParseOptions,
+    /// };
+    ///
+    /// let content = b"name,grade\nJoan,7.5\nJose,4\nJOAN,6";
+    /// let exam = parse_bytes(FileFormat::Csv, content).unwrap();
+    ///
+    /// assert_eq!(
+    ///     exam.warnings(),
+    ///     &[ExamWarning::DuplicateStudent("JOAN".to_string())]
+    /// );
+    ///
+    /// let mut exam = exam;
+    /// exam.filter_by_name(&["jose"]).unwrap();
+    ///
+    /// assert!(exam.warnings().is_empty());
+    ///
+    /// let options = ParseOptions {
+    ///     reject_duplicates: true,
+    ///     ..Default::default()
+    /// };
+    /// let content = br#"{ "students": { "Joan": 7.5, "Jose": 4, "Joan": 6 } }"#;
+    /// let error = parse_bytes_with_options(FileFormat::Json, content, &options).unwrap_err();
+    ///
+    /// assert_eq!(error.duplicate_student(), Some("Joan"));
+    /// ```
+    pub fn warnings(&self) -> Vec<ExamWarning> {
+        let mut warnings: Vec<ExamWarning> = self
+            .warnings
+            .iter()
+            .filter(|warning| match warning {
+                ExamWarning::DuplicateStudent(name) => {
+                    self.students.iter().any(|student| &student.name == name)
+                }
+                ExamWarning::InferredMaxGrade(max_grade) => *max_grade == self.max_grade,
+                _ => true,
+            })
+            .cloned()
+            .collect();
+
+        add_duplicate_warnings(&mut warnings, &self.students);
+        if self.validate_grades {
+            warnings.extend(self.validate());
+        }
+        if self.check_names {
+            warnings.extend(self.name_issues().into_iter().map(ExamWarning::NameAnomaly));
+        }
+
+        warnings
+    }
+
+    /// Publishes the exam, freezing its results once they have been
+    /// communicated to the students. While the exam is published, the methods
+    /// that modify its students, grades or details, such as
+    /// [set_max_grade](Exam::set_max_grade),
+    /// [apply_curve](Exam::apply_curve) or
+    /// [filter_by_name](Exam::filter_by_name), leave the exam unchanged,
+    /// record the rejected change in the
+    /// [transformations](Exam::transformations) log and return a
+    /// [PublishedError], or a [FilterError] in the case of
+    /// [filter_by_file](Exam::filter_by_file). Sorting the students and the
+    /// display options are still allowed.
+    ///
+    /// Publishing also [pins the standings](Exam::pin_standings) of the
+    /// students, so the ranks and percentiles communicated to them don't
+    /// change after the exam is unpublished and corrected, unless they are
+    /// explicitly [recomputed](Exam::recompute_standings).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::error::FilterError;
+    /// use exms::exam::{CurveMethod, Exam, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.publish();
+    ///
+    /// let error = exam.apply_curve(CurveMethod::Bonus(1.0)).unwrap_err();
+    /// assert_eq!(error.change(), "curving grades");
+    ///
+    /// let error = exam.filter_by_file(&["enrolled.toml"]).unwrap_err();
+    /// assert!(matches!(error, FilterError::Published(_)));
+    ///
+    /// assert!(exam.is_published());
+    /// assert_eq!(exam.find("joan")[0].grade, 4.6);
+    /// assert_eq!(
+    ///     exam.transformations(),
+    ///     &[
+    ///         "Published",
+    ///         "Rejected curving grades: the exam is published",
+    ///         "Rejected filtering students by file: the exam is published"
+    ///     ]
+    /// );
+    ///
+    /// exam.unpublish();
+    /// exam.apply_curve(CurveMethod::Bonus(1.0)).unwrap();
+    ///
+    /// assert_eq!(exam.find("joan")[0].grade, 5.6);
+    /// ```
+    pub fn publish(&mut self) {
+        if !self.published {
+            self.published = true;
+            self.transformations.push("Published".to_string());
+
+            if self.pinned_standings.is_none() {
+                self.pinned_standings =
+                    Some(PinnedStandings::new(&self.students, &self.statistics));
+            }
+        }
+    }
+
+    /// Unpublishes the exam, allowing its results to be modified again. See
+    /// [publish](Exam::publish).
+    pub fn unpublish(&mut self) {
+        if self.published {
+            self.published = false;
+            self.transformations.push("Unpublished".to_string());
+        }
+    }
+
+    /// Returns whether the exam is published. See [publish](Exam::publish).
+    pub fn is_published(&self) -> bool {
+        self.published
+    }
+
+    /// Pins the current rank and percentile of every student, so they are
+    /// kept when the statistics are recomputed, e.g. after adding a student
+    /// with [union](Exam::union). Students that were not in the exam when the
+    /// standings were pinned have no rank or percentile. The standings can
+    /// only be updated with [recompute_standings](Exam::recompute_standings).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.pin_standings();
+    ///
+    /// let late = Exam::new(vec![Student::new("David Jiménez Hidalgo", 7.94)]);
+    /// exam.union(&[&late]).unwrap();
+    ///
+    /// let table = |exam: &Exam| {
+    ///     let mut output = Vec::new();
+    ///     exam.students_to(&mut output, None).unwrap();
+    ///     String::from_utf8(output).unwrap()
+    /// };
+    /// assert!(table(&exam).contains("[1/2]"));
+    ///
+    /// exam.recompute_standings().unwrap();
+    ///
+    /// assert!(table(&exam).contains("[1/3]"));
+    /// assert_eq!(
+    ///     exam.transformations().last().unwrap(),
+    ///     "Recomputed ranks and percentiles"
+    /// );
+    /// ```
+    pub fn pin_standings(&mut self) {
+        self.pinned_standings = Some(PinnedStandings::new(&self.students, &self.statistics));
+        self.transformations
+            .push("Pinned ranks and percentiles".to_string());
+    }
+
+    /// Recomputes the rank and percentile of every student from their current
+    /// grades. If the standings were pinned, the new ones are pinned instead.
+    /// Not allowed while the exam is [published](Exam::publish).
+    pub fn recompute_standings(&mut self) -> Result<(), PublishedError> {
+        self.check_not_published("recomputing ranks and percentiles")?;
+
+        let pinned = self.pinned_standings.take();
+        self.compute_statistics();
+        if pinned.is_some() {
+            self.pinned_standings = Some(PinnedStandings::new(&self.students, &self.statistics));
+        }
+
+        self.transformations
+            .push("Recomputed ranks and percentiles".to_string());
+
+        Ok(())
+    }
+
+    /// Returns whether the ranks and percentiles of the students are pinned.
+    /// See [pin_standings](Exam::pin_standings).
+    pub fn standings_pinned(&self) -> bool {
+        self.pinned_standings.is_some()
+    }
+
+    // Records the rejected change if the exam is published
+    fn check_not_published(&mut self, change: &str) -> Result<(), PublishedError> {
+        if self.published {
+            self.transformations
+                .push(format!("Rejected {change}: the exam is published"));
+            return Err(PublishedError::new(change));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the students of the exam, in their current order, with the rank
+    /// and percentile computed for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// assert_eq!(exam.students().len(), 3);
+    /// assert_eq!(exam.students()[0].rank(), Some(2.0));
+    /// assert_eq!(exam.students()[2].rank(), Some(1.0));
+    /// ```
+    pub fn students(&self) -> &[Student] {
+        &self.students
+    }
+
+    /// Returns the statistics of the exam grades.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let statistics = exam.statistics();
+    ///
+    /// assert_eq!(statistics.total_students(), 3);
+    /// assert_eq!(statistics.passed_students(), 1);
+    /// assert_eq!(statistics.median(), 4.6);
+    /// assert_eq!(statistics.highest_grade(), 7.9);
+    /// ```
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Writes an audit-ready record of the exam into a new folder inside the
+    /// given directory, named after the exam title and the current UTC time.
+    /// Returns the path of the created folder.
+    ///
+    /// The folder contains:
+    ///
+    /// - A copy of the file the exam was parsed from, if any.
+    /// - `statistics.json`: the exam statistics and the computed values of each
+    ///   student (rank, percentile, quartile, decile and band).
+    /// - `students.csv` and `summary.csv`: reports of the students and the exam
+    ///   statistics.
+    /// - `transformations.log`: the [transformations](Exam::transformations)
+    ///   applied to the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::error::Error;
+    ///
+    /// use exms::exam::Exam;
+    ///
+    /// fn main() -> Result<(), Box<dyn Error>> {
+    ///     let mut exam = Exam::from_file("students.toml")?;
+    ///     exam.filter_by_name(&["joan"])?;
+    ///
+    ///     let folder = exam.archive("archive")?;
+    ///     println!("Archived to {}", folder.display());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn archive(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        archive::archive(self, dir.as_ref())
+    }
+
+    /// Returns the number of students with a grade greater than or equal to
+    /// the given grade.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// assert_eq!(exam.count_above(4.6), 2);
+    /// assert_eq!(exam.count_above(8.5), 0);
+    /// ```
+    pub fn count_above(&self, grade: f32) -> usize {
+        self.count_between(grade..)
+    }
+
+    /// Returns the number of students with a grade strictly lower than the
+    /// given grade.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// assert_eq!(exam.count_below(5.0), 2);
+    /// assert_eq!(exam.count_below(3.6), 0);
+    /// ```
+    pub fn count_below(&self, grade: f32) -> usize {
+        self.count_between(..grade)
+    }
+
+    /// Returns the number of students whose grade falls within the given
+    /// range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// assert_eq!(exam.count_between(3.0..5.0), 2);
+    /// assert_eq!(exam.count_between(4.6..=7.94), 2);
+    /// ```
+    pub fn count_between<R: RangeBounds<f32>>(&self, range: R) -> usize {
+        self.students
+            .iter()
+            .filter(|student| range.contains(&student.grade))
+            .count()
+    }
+
+    /// Returns the statistics of each question of the exam, in the order the
+    /// questions first appear in the students. Students without a score in a
+    /// question are not taken into account for it.
+    ///
+    /// The pass rate and the difficulty index of a question are only computed
+    /// if its maximum score has been set with
+    /// [set_question_max_score](Exam::set_question_max_score). Questions with
+    /// a maximum score that nobody answered are listed last.
+    ///
+    /// Questions where every student obtained the same score or that nobody
+    /// answered distort the reliability of the exam, so they are flagged for
+    /// review, see [QuestionStatistics::flag].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.5), ("Q2", 2.0)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 1.5), ("Q2", 3.0)]),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_question_max_score("Q1", 4.0).unwrap();
+    ///
+    /// let questions = exam.question_statistics();
+    ///
+    /// assert_eq!(questions[0].name, "Q1");
+    /// assert_eq!(questions[0].mean, 2.0);
+    /// assert_eq!(questions[0].pass_rate, Some(50.0));
+    /// assert_eq!(questions[0].difficulty, Some(0.5));
+    /// assert_eq!(questions[0].discrimination, Some(-1.0));
+    /// assert_eq!(questions[1].pass_rate, None);
+    /// assert_eq!(questions[1].difficulty, None);
+    /// assert_eq!(questions[1].median, 2.5);
+    /// assert_eq!(questions[1].flag(), None);
+    /// ```
+    ///
+    /// ```
+    /// use exms::exam::{Exam, QuestionFlag, Student};
+    ///
+    /// let students = &[
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.0)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 2.0)]),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_question_max_score("Q2", 4.0).unwrap();
+    ///
+    /// let questions = exam.question_statistics();
+    ///
+    /// assert_eq!(questions[0].flag(), Some(QuestionFlag::ZeroVariance));
+    /// assert_eq!(questions[1].name, "Q2");
+    /// assert_eq!(questions[1].flag(), Some(QuestionFlag::Unattempted));
+    /// ```
+    pub fn question_statistics(&self) -> Vec<QuestionStatistics> {
+        question::question_statistics(&self.students, &self.question_max_scores)
+    }
+
+    /// Writes the statistics of each question of the exam, see
+    /// [question_statistics](Exam::question_statistics), to a CSV file at the
+    /// given path, e.g. to include them in the review of the exam. Each row
+    /// has the name of a question followed by its difficulty index,
+    /// discrimination index, mean and blank rate. Unknown indices are left
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let students = &[
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.5), ("Q2", 2.0)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 1.5), ("Q2", 3.0)]),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// exam.export_item_stats_csv("questions.csv").unwrap();
+    /// ```
+    pub fn export_item_stats_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.item_stats_csv_to(BufWriter::new(File::create(path)?))
+    }
+
+    /// Writes the CSV written by
+    /// [export_item_stats_csv](Exam::export_item_stats_csv) to the given
+    /// writer instead of a file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let students = &[
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.5), ("Q2", 2.0)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 1.5)]),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_question_max_score("Q1", 4.0).unwrap();
+    ///
+    /// let mut csv = Vec::new();
+    /// exam.item_stats_csv_to(&mut csv).unwrap();
+    ///
+    /// let csv = String::from_utf8(csv).unwrap();
+    /// let mut lines = csv.lines();
+    ///
+    /// assert_eq!(
+    ///     lines.next(),
+    ///     Some("question,difficulty,discrimination,mean,blank_rate")
+    /// );
+    /// assert_eq!(lines.next(), Some("Q1,0.5,1,2,0"));
+    /// assert_eq!(lines.next(), Some("Q2,,,2,50"));
+    /// ```
+    pub fn item_stats_csv_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        export::write_item_stats_csv(&self.question_statistics(), writer)
+    }
+
+    /// Prints a histogram of the difficulty indices of the questions of the
+    /// exam, see [QuestionStatistics::difficulty], to assess the balance of
+    /// easy and hard questions at a glance. Questions without a
+    /// [maximum score](Exam::set_question_max_score) are left out. See
+    /// [DifficultyHistogram] for more info.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::with_questions(
+    ///         "Joan Beltrán Peris",
+    ///         [("Q1", 2.5), ("Q2", 0.5), ("Q3", 1.0)],
+    ///     ),
+    ///     Student::with_questions(
+    ///         "Jose Abad Martínez",
+    ///         [("Q1", 1.5), ("Q2", 0.0), ("Q3", 1.0)],
+    ///     ),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_question_max_score("Q1", 4.0).unwrap();
+    /// exam.set_question_max_score("Q2", 2.0).unwrap();
+    ///
+    /// exam.difficulty_histogram();
+    /// ```
+    pub fn difficulty_histogram(&self) {
+        self.difficulty_histogram_data().print();
+    }
+
+    /// Writes the histogram printed by
+    /// [difficulty_histogram](Exam::difficulty_histogram) to the given
+    /// writer instead of the standard output.
+    pub fn difficulty_histogram_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", self.difficulty_histogram_data())
+    }
+
+    /// Returns the [DifficultyHistogram] printed by
+    /// [difficulty_histogram](Exam::difficulty_histogram) without printing
+    /// it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::with_questions(
+    ///         "Joan Beltrán Peris",
+    ///         [("Q1", 2.5), ("Q2", 0.5), ("Q3", 1.0)],
+    ///     ),
+    ///     Student::with_questions(
+    ///         "Jose Abad Martínez",
+    ///         [("Q1", 1.5), ("Q2", 0.0), ("Q3", 1.0)],
+    ///     ),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_question_max_score("Q1", 4.0).unwrap();
+    /// exam.set_question_max_score("Q2", 2.0).unwrap();
+    ///
+    /// let histogram = exam.difficulty_histogram_data();
+    ///
+    /// assert_eq!(histogram.buckets()[1], (0.1..0.2, 1));
+    /// assert_eq!(histogram.buckets()[5], (0.5..0.6, 1));
+    /// assert_eq!(histogram.unknown(), &["Q3"]);
+    /// ```
+    pub fn difficulty_histogram_data(&self) -> DifficultyHistogram {
+        DifficultyHistogram::new(&self.question_statistics())
+    }
+
+    /// Returns the share of students that left each question blank or scored
+    /// zero in it, in the order the questions were asked, to detect whether
+    /// the students ran out of time. The questions with a
+    /// [maximum score](Exam::set_question_max_score) come first, in the order
+    /// their maximum scores were given, followed by the rest in the order they
+    /// first appear. See [Speededness] for more info.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let students = &[
+    ///     Student::with_questions("Joan Beltrán Peris", [("Q1", 2.5), ("Q2", 1.0), ("Q3", 0.0)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Q1", 1.5), ("Q2", 2.0)]),
+    ///     Student::with_questions("David Jiménez Hidalgo", [("Q1", 3.0)]),
+    ///     Student::with_questions("Irene Alcántara Campillo", [("Q1", 0.0), ("Q2", 0.5)]),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let speededness = exam.speededness();
+    ///
+    /// assert_eq!(speededness.questions[0].blank_rate, 0.0);
+    /// assert_eq!(speededness.questions[0].zero_rate, 25.0);
+    /// assert_eq!(speededness.questions[1].blank_rate, 25.0);
+    /// assert_eq!(speededness.questions[2].name, "Q3");
+    /// assert_eq!(speededness.questions[2].blank_rate, 75.0);
+    /// assert_eq!(speededness.blank_trend(), Some(37.5));
+    /// assert_eq!(speededness.unanswered_trend(), Some(37.5));
+    /// ```
+    pub fn speededness(&self) -> Speededness {
+        speededness::speededness(&self.students, &self.question_max_scores)
+    }
+
+    /// Returns how many students chose each wrong option of the multiple-choice
+    /// questions of the exam, split by the [quartile](Student::quartile) of
+    /// the students, to tell the distractors that attract weak students apart
+    /// from the ones that confuse strong students or that nobody chooses.
+    ///
+    /// Only the questions with a correct option in the
+    /// [answer key](Exam::set_answer_key) are analyzed, in the order of the
+    /// key, using the [answers](Student::answers) of the students. Options are
+    /// compared exactly, so `"b"` is not the same option as `"B"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let student = |name: &str, grade: f32, option: &str| {
+    ///     let mut student = Student::new(name, grade);
+    ///     student.answers.insert("Q1".to_string(), option.to_string());
+    ///     student
+    /// };
+    ///
+    /// let exam = Exam::builder(vec![
+    ///     student("Irene Alcántara Campillo", 2.0, "C"),
+    ///     student("Jose Abad Martínez", 3.6, "C"),
+    ///     student("Joan Beltrán Peris", 6.0, "B"),
+    ///     student("David Jiménez Hidalgo", 9.0, "A"),
+    /// ])
+    /// .answer_key("Q1", "A")
+    /// .build();
+    ///
+    /// let analysis = exam.distractor_analysis();
+    /// let distractors = &analysis[0].distractors;
+    ///
+    /// assert_eq!(analysis[0].key, "A");
+    /// assert_eq!(distractors.len(), 2);
+    /// assert_eq!(distractors[0].option, "B");
+    /// assert_eq!(distractors[0].by_quartile, [0, 0, 1, 0]);
+    /// assert_eq!(distractors[1].option, "C");
+    /// assert_eq!(distractors[1].by_quartile, [1, 1, 0, 0]);
+    /// assert_eq!(distractors[1].total(), 2);
+    /// ```
+    pub fn distractor_analysis(&self) -> Vec<QuestionDistractors> {
+        distractor::distractor_analysis(&self.students, &self.answer_key)
+    }
+
+    /// Returns the grade of each student of the exam put on the scale of the
+    /// given exam, for exams given in two forms with some questions in
+    /// common, so the grades of both forms can be compared. The questions with
+    /// the same name in both exams are used as anchors: the difference between
+    /// the anchor scores of the students of each form tells how much of the
+    /// difference between their grades comes from the students and how much
+    /// from the difficulty of the forms. See [EquatingMethod] for the
+    /// available procedures.
+    ///
+    /// Only the students with a breakdown of their grade are taken into
+    /// account to compare the forms, although every student is equated. The
+    /// result is empty if the exams share no question.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{EquatingMethod, Exam, Student};
+    ///
+    /// let form_a = Exam::new(vec![
+    ///     Student::with_questions("Joan Beltrán Peris", [("Anchor", 2.0), ("A1", 3.0)]),
+    ///     Student::with_questions("Jose Abad Martínez", [("Anchor", 4.0), ("A1", 5.0)]),
+    /// ]);
+    /// let form_b = Exam::new(vec![
+    ///     Student::with_questions("David Jiménez Hidalgo", [("Anchor", 1.0), ("B1", 1.0)]),
+    ///     Student::with_questions("Irene Alcántara Campillo", [("Anchor", 2.0), ("B1", 6.0)]),
+    /// ]);
+    ///
+    /// // Form B grades have a mean of 5 and a deviation of 3, their anchor
+    /// // scores a mean of 1.5 and a deviation of 0.5. In form A, the grades
+    /// // have a mean of 7 and a deviation of 2, the anchor scores a mean of 3
+    /// // and a deviation of 1. So a grade x of form B becomes
+    /// // 7 + 2 * (1.5 + 0.5 / 3 * (x - 5) - 3) = 4 + (x - 5) / 3
+    /// let equated = form_b.equate_to(&form_a, EquatingMethod::Linear);
+    ///
+    /// assert_eq!(equated[0].0, "David Jiménez Hidalgo");
+    /// assert!((equated[0].1 - 3.0).abs() < 1e-5);
+    /// assert!((equated[1].1 - 5.0).abs() < 1e-5);
+    ///
+    /// // Mean equating only shifts the grades: 7 + (1.5 + x - 5 - 3) = x + 0.5
+    /// let equated = form_b.equate_to(&form_a, EquatingMethod::Mean);
+    ///
+    /// assert_eq!(equated[0].1, 2.5);
+    /// assert_eq!(equated[1].1, 8.5);
+    /// ```
+    pub fn equate_to(&self, reference: &Exam, method: EquatingMethod) -> Vec<(String, f32)> {
+        equating::equate(&self.students, &reference.students, method).unwrap_or_default()
+    }
+
+    /// Returns the statistics of each group of students of the exam, see
+    /// [Student::group], sorted by group name. The students without a group
+    /// are gathered in a last group with no name, if there are any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let mut students = vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    ///     Student::new("Irene Alcántara Campillo", 6.2),
+    /// ];
+    /// students[0].group = Some("Thursday".to_string());
+    /// students[1].group = Some("Tuesday".to_string());
+    /// students[2].group = Some("Thursday".to_string());
+    ///
+    /// let exam = Exam::new(students);
+    /// let groups = exam.group_statistics();
+    ///
+    /// assert_eq!(groups[0].group.as_deref(), Some("Thursday"));
+    /// assert_eq!(groups[0].statistics.total_students(), 2);
+    /// assert_eq!(groups[0].statistics.pass_rate(), 50.0);
+    /// assert_eq!(groups[1].statistics.mean(), 3.6);
+    /// assert_eq!(groups[2].group, None);
+    ///
+    /// exam.group_summary();
+    /// ```
+    pub fn group_statistics(&self) -> Vec<GroupStatistics> {
+        group::group_statistics(
+            &self.students,
+            self.max_grade,
+            self.pass_grade(),
+            self.rank_method,
+        )
+    }
+
+    /// Prints a table comparing the statistics of each group of students of
+    /// the exam, including how far the mean of each group is from the mean
+    /// of the whole exam. See [group_statistics](Exam::group_statistics).
+    pub fn group_summary(&self) {
+        self.groups_table().printstd();
+    }
+
+    /// Writes the table printed by [group_summary](Exam::group_summary) to
+    /// the given writer instead of the standard output.
+    pub fn group_summary_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.groups_table().print(&mut writer)?;
+        Ok(())
+    }
+
+    /// Splits the exam into one exam per group of students, see
+    /// [Student::group], in the order of
+    /// [group_statistics](Exam::group_statistics). Each exam is a copy like
+    /// the ones returned by [filtered_by_name](Exam::filtered_by_name) with
+    /// only the students of its group, and its title, if any, followed by the
+    /// name of the group. The students without a group are under `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let mut students = vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ];
+    /// students[0].group = Some("Thursday".to_string());
+    /// students[2].group = Some("Thursday".to_string());
+    ///
+    /// let exam = Exam::new(students);
+    /// let groups = exam.split_by_group();
+    ///
+    /// let thursday = &groups[&Some("Thursday".to_string())];
+    /// assert_eq!(thursday.statistics().total_students(), 2);
+    /// assert_eq!(thursday.statistics().highest_grade(), 7.9);
+    /// assert_eq!(groups[&None].find("")[0].name, "Jose Abad Martínez");
+    /// assert_eq!(groups.len(), 2);
+    /// ```
+    pub fn split_by_group(&self) -> IndexMap<Option<String>, Exam> {
+        group::group_names(&self.students)
+            .into_iter()
+            .map(|group| {
+                let exam = self.derive(|exam| {
+                    exam.students.retain(|s| s.group.as_deref() == group);
+                    if let (Some(title), Some(group)) = (&mut exam.title, group) {
+                        *title = format!("{title} - {group}");
+                    }
+                    exam.transformations.push(format!(
+                        "Split by group: {}",
+                        group.unwrap_or("without group")
+                    ));
+                });
+
+                (group.map(str::to_string), exam)
+            })
+            .collect()
+    }
+
+    /// Prints a bar chart of the pass rate of each group of students, along
+    /// with the pass rate of the whole exam. See [PassRateChart] for more
+    /// info.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let mut students = vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    /// ];
+    /// students[0].group = Some("Thursday".to_string());
+    /// students[2].group = Some("Thursday".to_string());
+    ///
+    /// let exam = Exam::new(students);
+    /// exam.pass_rate_chart();
+    /// ```
+    pub fn pass_rate_chart(&self) {
+        self.pass_rate_chart_data().print();
+    }
+
+    /// Writes the chart printed by [pass_rate_chart](Exam::pass_rate_chart)
+    /// to the given writer instead of the standard output.
+    pub fn pass_rate_chart_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", self.pass_rate_chart_data())
+    }
+
+    /// Returns the [PassRateChart] printed by
+    /// [pass_rate_chart](Exam::pass_rate_chart) without printing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let mut students = vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
+    ///     Student::new("Irene Alcántara Campillo", 6.2),
+    /// ];
+    /// students[0].group = Some("Thursday".to_string());
+    /// students[2].group = Some("Thursday".to_string());
+    ///
+    /// let exam = Exam::new(students);
+    /// let chart = exam.pass_rate_chart_data();
+    ///
+    /// assert_eq!(chart.groups()[0], (Some("Thursday".to_string()), 50.0));
+    /// assert_eq!(chart.groups()[1], (None, 50.0));
+    /// assert_eq!(chart.pass_rate(), 50.0);
+    ///
+    /// let rendered = chart.width(40).to_string();
+    /// assert!(rendered.contains("Without group"));
+    /// assert!(rendered.lines().last().unwrap().starts_with("Exam"));
+    /// ```
+    pub fn pass_rate_chart_data(&self) -> PassRateChart {
+        PassRateChart::new(&self.group_statistics(), self.statistics.pass_rate)
+    }
+
+    fn groups_table(&self) -> Table {
+        group::groups_table(&self.group_statistics(), self.statistics.mean)
+    }
+
+    /// Returns the minimum grade needed to be among the `top` best students of
+    /// the exam, together with the students tied at that grade, or `None` if
+    /// the group is empty.
+    ///
+    /// Students tied at the cutoff grade may not all fit in the group, see
+    /// [Cutoff] for more info.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student, Top};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 8.5),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 9.2),
+    ///     Student::new("Irene Alcántara Campillo", 8.5),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let cutoff = exam.cutoff_for_top(Top::Students(2)).unwrap();
+    ///
+    /// assert_eq!(cutoff.grade, 8.5);
+    /// assert_eq!(cutoff.above[0].name, "David Jiménez Hidalgo");
+    /// assert_eq!(cutoff.tied.len(), 2);
+    /// assert_eq!(cutoff.spots_left, 1);
+    ///
+    /// let cutoff = exam.cutoff_for_top(Top::Percent(75.0)).unwrap();
+    ///
+    /// assert_eq!(cutoff.grade, 8.5);
+    /// assert_eq!(cutoff.spots_left, 2);
+    /// ```
+    pub fn cutoff_for_top(&self, top: Top) -> Option<Cutoff<'_>> {
+        cutoff::cutoff(&self.students, top)
+    }
+
+    /// Returns the students whose grades are statistical outliers according
+    /// to the given [OutlierMethod], in the same order as the exam students.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, OutlierMethod, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 6.5),
+    ///     Student::new("Jose Abad Martínez", 7.0),
+    ///     Student::new("David Jiménez Hidalgo", 6.8),
+    ///     Student::new("Irene Alcántara Campillo", 7.2),
+    ///     Student::new("Jorge Rodríguez Martín", 0.5),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// let outliers = exam.outliers(OutlierMethod::Iqr(1.5));
+    /// assert_eq!(outliers.len(), 1);
+    /// assert_eq!(outliers[0].name, "Jorge Rodríguez Martín");
+    ///
+    /// assert!(exam.outliers(OutlierMethod::ZScore(3.0)).is_empty());
+    /// ```
+    pub fn outliers(&self, method: OutlierMethod) -> Vec<&Student> {
+        outlier::outliers(&self.students, &self.statistics, method)
+    }
+
+    /// Draws a reproducible sample of `size` students for double-marking.
+    ///
+    /// Students close to the pass mark and at both ends of the distribution
+    /// are more likely to be selected, according to the given
+    /// [AuditWeights]. Drawing a sample with the same [Seed] always yields the
+    /// same students. The sample is returned in the same order as the exam
+    /// students.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{AuditWeights, Exam, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    ///     Student::new("Irene Alcántara Campillo", 5.1),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let weights = AuditWeights::default();
+    ///
+    /// let sample = exam.audit_sample(2, &weights, 42);
+    /// let names: Vec<&str> = sample.iter().map(|s| s.name.as_str()).collect();
+    ///
+    /// assert_eq!(sample.len(), 2);
+    /// assert_eq!(
+    ///     names,
+    ///     exam.audit_sample(2, &weights, 42)
+    ///         .iter()
+    ///         .map(|s| s.name.as_str())
+    ///         .collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn audit_sample(
+        &self,
+        size: usize,
+        weights: &AuditWeights,
+        seed: impl Into<Seed>,
+    ) -> Vec<&Student> {
+        sample::audit_sample(
+            &self.students,
+            self.max_grade,
+            self.pass_grade(),
+            size,
+            weights,
+            seed.into(),
+        )
+    }
+
+    /// Returns the ranking of the exam students for public posting, listing
+    /// only the rank, an anonymized ID and the band of each student, and
+    /// optionally their grade, but never their names.
+    ///
+    /// Entries are sorted by rank. The ID of each student is the first 16
+    /// hexadecimal digits of the HMAC-SHA256 of their name, keyed with the
+    /// secret of the [AnonymousOptions], so it's always the same for the same
+    /// name and secret, and each student can be told their ID privately.
+    ///
+    /// Returns [AnonymousError::DuplicateId] if two students get the same ID,
+    /// e.g. because they have the same name, as their entries couldn't be told
+    /// apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{AnonymousOptions, Exam, Language, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let options = AnonymousOptions::new("econometrics-2024").unwrap();
+    ///
+    /// let ranking = exam.anonymous_ranking(&options).unwrap();
+    ///
+    /// assert_eq!(ranking[0].rank, Some(1.0));
+    /// assert_eq!(ranking[0].band, "Pass");
+    /// assert_eq!(ranking[0].grade, None);
+    /// assert_eq!(ranking[0].id.len(), 16);
+    /// assert_eq!(
+    ///     ranking[0].id,
+    ///     exam.anonymous_ranking(&options).unwrap()[0].id
+    /// );
+    /// assert_eq!(ranking[2].band, "Fail");
+    ///
+    /// let options = options.language(Language::Spanish);
+    /// let ranking = exam.anonymous_ranking(&options).unwrap();
+    ///
+    /// assert_eq!(ranking[0].band, "Aprobado");
+    /// assert_eq!(ranking[2].band, "Suspenso");
+    /// ```
+    pub fn anonymous_ranking(
+        &self,
+        options: &AnonymousOptions,
+    ) -> Result<Vec<AnonymousEntry>, AnonymousError> {
+        publish::anonymous_ranking(self, options)
+    }
+
+    /// Writes the [anonymous ranking](Exam::anonymous_ranking) of the exam to
+    /// the given writer in the given format, ready to be posted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{AnonymousOptions, Exam, ExportFormat, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let options = AnonymousOptions::new("econometrics-2024")
+    ///     .unwrap()
+    ///     .include_grade();
+    ///
+    /// let mut csv = Vec::new();
+    /// exam.anonymous_ranking_to(&mut csv, &options, ExportFormat::Csv)
+    ///     .unwrap();
+    ///
+    /// let csv = String::from_utf8(csv).unwrap();
+    /// assert_eq!(csv.lines().next(), Some("rank,id,band,grade"));
+    /// assert!(csv.lines().nth(1).unwrap().ends_with(",Pass,7.94"));
+    /// assert!(!csv.contains("David"));
+    /// ```
+    pub fn anonymous_ranking_to<W: Write>(
+        &self,
+        writer: W,
+        options: &AnonymousOptions,
+        format: ExportFormat,
+    ) -> Result<(), AnonymousError> {
+        let ranking = self.anonymous_ranking(options)?;
+        publish::write_anonymous_ranking(&ranking, writer, format)?;
+
+        Ok(())
+    }
+
+    /// Returns a report of the exam as GitHub-flavored Markdown, ready to be
+    /// pasted into a wiki or an issue tracker. It contains the exam title and
+    /// metadata, the summary, the question statistics if there are any, the
+    /// students table in their current order and a textual histogram,
+    /// followed by the [warnings](Exam::warnings) of the exam and the
+    /// histogram, if there are any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_title("Econometrics").unwrap();
+    ///
+    /// let markdown = exam.to_markdown();
+    ///
+    /// assert!(markdown.starts_with("# Econometrics\n"));
+    /// assert!(markdown.contains("| Total Students | 3 |"));
+    /// assert!(markdown.contains("| David Jiménez Hidalgo | 7.94 | 100 | 1/3 |"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        markdown::to_markdown(self, Language::English)
+    }
+
+    /// Same as [to_markdown](Exam::to_markdown), but with the headers, the
+    /// names of the statistics and the warnings of the exam in the given
+    /// [Language]. The labels of the [GradeScale] bands are written as they
+    /// are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Language, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 11.5),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let markdown = exam.to_markdown_in(Language::Spanish);
+    ///
+    /// assert!(markdown.contains("| Total de estudiantes | 3 |"));
+    /// assert!(markdown.contains("## Avisos"));
+    /// assert!(markdown.contains("se han truncado para caber en el histograma"));
+    /// ```
+    pub fn to_markdown_in(&self, language: Language) -> String {
+        markdown::to_markdown(self, language)
+    }
+
+    /// Returns the report of [to_markdown_in](Exam::to_markdown_in) in each
+    /// of the given languages, e.g. to publish the same report in Spanish and
+    /// English at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Language, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let reports = exam.to_markdown_all(&[Language::Spanish, Language::English]);
+    ///
+    /// assert_eq!(reports[0].0, Language::Spanish);
+    /// assert!(reports[0].1.contains("| Total de estudiantes | 3 |"));
+    /// assert!(reports[1].1.contains("| Total Students | 3 |"));
+    /// ```
+    pub fn to_markdown_all(&self, languages: &[Language]) -> Vec<(Language, String)> {
+        languages
+            .iter()
+            .map(|&language| (language, self.to_markdown_in(language)))
+            .collect()
+    }
+
+    /// Print the exam students in a well formatted table with some statistical
+    /// information about each student, like the percentile, the rank, etc...
+    ///
+    /// If a [SortBy] order is given, the table rows are printed in that order
+    /// without modifying the order of the exam students. Otherwise they are
+    /// printed in their current order.
+    ///
+    /// Returns a list of [RenderWarning]s describing any placeholder values
+    /// that had to be displayed, e.g. students without a computed rank.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, SortBy, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// exam.print_students(Some(SortBy::Rank));
+    /// exam.print_students(Some(SortBy::Delta));
+    ///
+    /// let warnings = exam.print_students(None);
+    /// assert!(warnings.is_empty());
+    /// ```
+    pub fn print_students(&self, sort_by: Option<SortBy>) -> Vec<RenderWarning> {
+        let (tables, warnings) = self.students_tables(sort_by);
+        tables.iter().for_each(Table::printstd);
+
+        warnings
+    }
+
+    /// Writes the exam students table printed by
+    /// [print_students](Exam::print_students) to the given writer instead of
+    /// the standard output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, SortBy, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// let mut output = Vec::new();
+    /// exam.students_to(&mut output, Some(SortBy::Name)).unwrap();
+    ///
+    /// assert!(String::from_utf8(output)
+    ///     .unwrap()
+    ///     .contains("Jose Abad Martínez"));
+    /// ```
+    pub fn students_to<W: Write>(
+        &self,
+        mut writer: W,
+        sort_by: Option<SortBy>,
+    ) -> io::Result<Vec<RenderWarning>> {
+        let (tables, warnings) = self.students_tables(sort_by);
+        for table in &tables {
+            table.print(&mut writer)?;
+        }
+
+        Ok(warnings)
+    }
+
+    /// Writes the same output as [students_to](Exam::students_to), but
+    /// writing the students table row by row instead of building it in
+    /// memory first, which keeps the memory used low and the output
+    /// responsive for exams with tens of thousands of students.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, SortBy, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_title("Exam 1").unwrap();
+    ///
+    /// let mut streamed = Vec::new();
+    /// exam.stream_students_to(&mut streamed, Some(SortBy::Grade))
+    ///     .unwrap();
+    ///
+    /// let mut output = Vec::new();
+    /// exam.students_to(&mut output, Some(SortBy::Grade)).unwrap();
+    ///
+    /// assert_eq!(streamed, output);
+    /// ```
+    pub fn stream_students_to<W: Write>(
+        &self,
+        mut writer: W,
+        sort_by: Option<SortBy>,
+    ) -> io::Result<Vec<RenderWarning>> {
+        if let Some(header) = self.header() {
+            header.print(&mut writer)?;
+        }
+
+        let students = self.sorted_students(sort_by);
+        stream::write_students_table(writer, &students, &self.student_columns())
+    }
+
+    /// Prints the students table like [print_students](Exam::print_students),
+    /// but with the students of each [group](Student::group) together, in the
+    /// order of [group_statistics](Exam::group_statistics). Each group is
+    /// followed by a subtotal row with its mean, in the grade column, its
+    /// number of students and its pass rate.
+    ///
+    /// The students are sorted in the given [SortBy] order within each group,
+    /// and their rank and percentile are still the ones in the whole exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, SortBy, Student};
+    ///
+    /// let mut students = vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    /// students[0].group = Some("Thursday".to_string());
+    /// students[1].group = Some("Tuesday".to_string());
+    /// students[2].group = Some("Thursday".to_string());
+    ///
+    /// let exam = Exam::new(students);
+    /// exam.print_students_grouped(Some(SortBy::Grade));
+    ///
+    /// let mut output = Vec::new();
+    /// exam.students_grouped_to(&mut output, Some(SortBy::Grade))
+    ///     .unwrap();
+    /// let output = String::from_utf8(output).unwrap();
+    ///
+    /// let david = output.find("David").unwrap();
+    /// let joan = output.find("Joan").unwrap();
+    /// let thursday = output.find("Subtotal Thursday").unwrap();
+    /// let jose = output.find("Jose").unwrap();
+    /// assert!(david < joan && joan < thursday && thursday < jose);
+    /// assert!(output.contains("2 students, 50% passed"));
+    /// ```
+    pub fn print_students_grouped(&self, sort_by: Option<SortBy>) -> Vec<RenderWarning> {
+        let (tables, warnings) = self.grouped_students_tables(sort_by);
+        tables.iter().for_each(Table::printstd);
+
+        warnings
+    }
+
+    /// Writes the table printed by
+    /// [print_students_grouped](Exam::print_students_grouped) to the given
+    /// writer instead of the standard output.
+    pub fn students_grouped_to<W: Write>(
+        &self,
+        mut writer: W,
+        sort_by: Option<SortBy>,
+    ) -> io::Result<Vec<RenderWarning>> {
+        let (tables, warnings) = self.grouped_students_tables(sort_by);
+        for table in &tables {
+            table.print(&mut writer)?;
+        }
+
+        Ok(warnings)
+    }
+
+    /// Print statistical information about the exam in a well formatted table,
+    /// like the mean, total students, the exam pass percentage etc...
     ///
     /// # Examples
     ///
@@ -37,46 +3674,48 @@ impl Exam {
     ///     Student::new("David Jiménez Hidalgo", 7.94),
     /// ];
     ///
-    /// let exam = Exam::new(students);
+    /// let mut exam = Exam::new(students);
+    /// exam.summary();
     /// ```
-    pub fn new(students: impl Into<Vec<Student>>) -> Self {
-        let mut students = students.into();
-        let statistics = ExamStatistics::new(&mut students, 10.0);
-
-        Self {
-            title: None,
-            max_grade: 10.0,
-            students,
-            statistics,
-        }
+    pub fn summary(&self) {
+        self.summary_tables(false).iter().for_each(Table::printstd);
     }
 
-    /// Creates a new `Exam` from a given file.
-    /// The file formats suppported for the moment are only JSON and TOML files.
-    /// The file should have a student object with all the students and their
-    /// grades as key/value pairs. For more information about wich format
-    /// a file should follow, please see [exms](crate).
+    /// Writes the statistics table printed by [summary](Exam::summary) to the
+    /// given writer instead of the standard output.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// use std::path::Path;
-    ///
-    /// use exms::error::ParseError;
+    /// ```
     /// use exms::exam::Exam;
+    /// use exms::exam::Student;
     ///
-    /// fn main() -> Result<(), ParseError> {
-    ///     let file_path = Path::new("students.json");
-    ///     let exam = Exam::from_file(&file_path)?;
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
     ///
-    ///     Ok(())
-    /// }
+    /// let exam = Exam::new(students);
+    ///
+    /// let mut output = Vec::new();
+    /// exam.summary_to(&mut output).unwrap();
+    ///
+    /// assert!(String::from_utf8(output)
+    ///     .unwrap()
+    ///     .contains("Total Students"));
     /// ```
-    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ParseError> {
-        parse_exam_file(path.as_ref())
+    pub fn summary_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for table in &self.summary_tables(false) {
+            table.print(&mut writer)?;
+        }
+
+        Ok(())
     }
 
-    /// Sets the maximum achievable grade in the exam.
+    /// Print the same table as [summary](Exam::summary), extended with the
+    /// quartiles, the interquartile range, the modes, the skewness and the
+    /// kurtosis of the grades.
     ///
     /// # Examples
     ///
@@ -88,17 +3727,24 @@ impl Exam {
     ///     Student::new("Joan Beltrán Peris", 4.6),
     ///     Student::new("Jose Abad Martínez", 3.6),
     ///     Student::new("David Jiménez Hidalgo", 7.94),
+    ///     Student::new("Jorge Rodríguez Martín", 3.6),
     /// ];
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.set_max_grade(6.0);
+    /// let exam = Exam::new(students);
+    /// exam.summary_extended();
+    ///
+    /// let statistics = exam.statistics();
+    /// assert_eq!(statistics.first_quartile(), 3.6);
+    /// assert_eq!(statistics.modes(), &[3.6]);
+    /// assert!(statistics.skewness() > 0.0);
     /// ```
-    pub fn set_max_grade(&mut self, max_grade: f32) {
-        self.max_grade = max_grade;
-        self.statistics = ExamStatistics::new(&mut self.students, max_grade);
+    pub fn summary_extended(&self) {
+        self.summary_tables(true).iter().for_each(Table::printstd);
     }
 
-    /// Sets the title of the exam.
+    /// Writes the statistics table printed by
+    /// [summary_extended](Exam::summary_extended) to the given writer instead
+    /// of the standard output.
     ///
     /// # Examples
     ///
@@ -112,14 +3758,72 @@ impl Exam {
     ///     Student::new("David Jiménez Hidalgo", 7.94),
     /// ];
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.set_title("Econometrics");
+    /// let exam = Exam::new(students);
+    ///
+    /// let mut output = Vec::new();
+    /// exam.summary_extended_to(&mut output).unwrap();
+    ///
+    /// assert!(String::from_utf8(output)
+    ///     .unwrap()
+    ///     .contains("Interquartile Range"));
     /// ```
-    pub fn set_title(&mut self, title: impl Into<String>) {
-        self.title = Some(title.into())
+    pub fn summary_extended_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for table in &self.summary_tables(true) {
+            table.print(&mut writer)?;
+        }
+
+        Ok(())
     }
 
-    /// Sorts the exam students based on their grade in descending order.
+    /// Print a histogram of the exam grades.
+    ///
+    /// Grades greater than the maximum grade are drawn in the last bucket, and
+    /// reported in the returned list of [RenderWarning]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, RenderWarning, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 11.0),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let warnings = exam.histogram(None);
+    ///
+    /// assert_eq!(
+    ///     warnings,
+    ///     vec![RenderWarning::GradesTruncated(vec![
+    ///         "David Jiménez Hidalgo".to_string()
+    ///     ])]
+    /// );
+    /// ```
+    pub fn histogram(&self, step: Option<f64>) -> Vec<RenderWarning> {
+        let histogram = self.build_histogram(step);
+        histogram.print();
+
+        histogram.warnings()
+    }
+
+    /// Writes the histogram printed by [histogram](Exam::histogram) to the
+    /// given writer instead of the standard output.
+    pub fn histogram_to<W: Write>(
+        &self,
+        mut writer: W,
+        step: Option<f64>,
+    ) -> io::Result<Vec<RenderWarning>> {
+        let histogram = self.build_histogram(step);
+        writeln!(writer, "{histogram}")?;
+
+        Ok(histogram.warnings())
+    }
+
+    /// Prints a histogram of the exam grades with a series for each group of
+    /// students, see [Student::group], using the same buckets as
+    /// [histogram](Exam::histogram). See [GroupedHistogram] for more info.
     ///
     /// # Examples
     ///
@@ -127,29 +3831,42 @@ impl Exam {
     /// use exms::exam::Exam;
     /// use exms::exam::Student;
     ///
-    /// let students = &[
+    /// let mut students = vec![
     ///     Student::new("Joan Beltrán Peris", 4.6),
     ///     Student::new("Jose Abad Martínez", 3.6),
-    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
     /// ];
+    /// students[0].group = Some("Thursday".to_string());
+    /// students[2].group = Some("Thursday".to_string());
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.sort_by_grade();
+    /// let exam = Exam::new(students);
+    /// let warnings = exam.grouped_histogram(Some(5.0));
     ///
-    /// assert_eq!(exam.students[0].grade, 7.94);
-    /// assert_eq!(exam.students[1].grade, 4.6);
-    /// assert_eq!(exam.students[2].grade, 3.6);
+    /// assert!(warnings.is_empty());
     /// ```
-    pub fn sort_by_grade(&mut self) {
-        // Sort students by name so that students with the same grade are sorted
-        // alphabetically
-        Self::sort_by_alphabetic_order(self);
+    pub fn grouped_histogram(&self, step: Option<f64>) -> Vec<RenderWarning> {
+        let histogram = self.grouped_histogram_data(step);
+        histogram.print();
 
-        self.students
-            .sort_by(|a, b| b.grade.partial_cmp(&a.grade).unwrap_or(Ordering::Equal))
+        histogram.warnings()
     }
 
-    /// Sorts the exam students based on their name alphabetically.
+    /// Writes the histogram printed by
+    /// [grouped_histogram](Exam::grouped_histogram) to the given writer
+    /// instead of the standard output.
+    pub fn grouped_histogram_to<W: Write>(
+        &self,
+        mut writer: W,
+        step: Option<f64>,
+    ) -> io::Result<Vec<RenderWarning>> {
+        let histogram = self.grouped_histogram_data(step);
+        writeln!(writer, "{histogram}")?;
+
+        Ok(histogram.warnings())
+    }
+
+    /// Returns the [GroupedHistogram] printed by
+    /// [grouped_histogram](Exam::grouped_histogram) without printing it.
     ///
     /// # Examples
     ///
@@ -157,26 +3874,40 @@ impl Exam {
     /// use exms::exam::Exam;
     /// use exms::exam::Student;
     ///
-    /// let students = &[
+    /// let mut students = vec![
     ///     Student::new("Joan Beltrán Peris", 4.6),
     ///     Student::new("Jose Abad Martínez", 3.6),
-    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    ///     Student::new("David Jiménez Hidalgo", 7.9),
     /// ];
+    /// students[0].group = Some("Thursday".to_string());
+    /// students[2].group = Some("Thursday".to_string());
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.sort_by_alphabetic_order();
+    /// let exam = Exam::new(students);
+    /// let histogram = exam.grouped_histogram_data(Some(5.0));
+    ///
+    /// assert_eq!(histogram.buckets(), &[0.0..5.0, 5.0..10.0]);
+    /// assert_eq!(histogram.series()[0].group.as_deref(), Some("Thursday"));
+    /// assert_eq!(histogram.series()[0].counts, vec![1, 1]);
+    /// assert_eq!(histogram.series()[1].group, None);
+    /// assert_eq!(histogram.series()[1].counts, vec![1, 0]);
     ///
-    /// assert_eq!(exam.students[0].name, "David Jiménez Hidalgo");
-    /// assert_eq!(exam.students[1].name, "Joan Beltrán Peris");
-    /// assert_eq!(exam.students[2].name, "Jose Abad Martínez");
+    /// let rendered = histogram.width(40).to_string();
+    /// assert!(rendered.contains("Thursday"));
+    /// assert!(rendered.contains("Without group"));
     /// ```
-    pub fn sort_by_alphabetic_order(&mut self) {
-        self.students
-            .sort_by_key(|s| unidecode(&s.name.to_lowercase()))
+    pub fn grouped_histogram_data(&self, step: Option<f64>) -> GroupedHistogram {
+        let groups = self
+            .split_by_group()
+            .into_iter()
+            .map(|(group, exam)| (group, exam.build_histogram(step)))
+            .collect();
+
+        GroupedHistogram::new(groups)
     }
 
-    /// Filters the exam students yielding only the students which name contains
-    /// the given query
+    /// Print a box-and-whisker plot of the exam grades, showing the lowest
+    /// and highest grades, the quartiles, the median and the outliers at a
+    /// glance. See [BoxPlot] for more info.
     ///
     /// # Examples
     ///
@@ -190,63 +3921,241 @@ impl Exam {
     ///     Student::new("David Jiménez Hidalgo", 7.94),
     /// ];
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.filter_by_name(&["joan", "jorge", "jim"]);
+    /// let exam = Exam::new(students);
+    /// exam.boxplot();
+    /// ```
+    pub fn boxplot(&self) {
+        self.boxplot_data().print();
+    }
+
+    /// Writes the box plot printed by [boxplot](Exam::boxplot) to the given
+    /// writer instead of the standard output.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(exam.students.len(), 2);
-    /// assert_eq!(exam.students[0].name, "Joan Beltrán Peris");
-    /// assert_eq!(exam.students[1].name, "David Jiménez Hidalgo");
     /// ```
-    pub fn filter_by_name<S: AsRef<str>>(&mut self, query: &[S]) {
-        self.students.retain(|student| {
-            query.iter().any(|name| {
-                student
-                    .name
-                    .to_lowercase()
-                    .contains(&name.as_ref().to_lowercase())
-            })
-        });
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 6.5),
+    ///     Student::new("Jose Abad Martínez", 7.0),
+    ///     Student::new("David Jiménez Hidalgo", 6.8),
+    ///     Student::new("Irene Alcántara Campillo", 7.2),
+    ///     Student::new("Jorge Rodríguez Martín", 0.5),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    ///
+    /// let mut output = Vec::new();
+    /// exam.boxplot_to(&mut output).unwrap();
+    ///
+    /// assert!(String::from_utf8(output).unwrap().contains("Outliers 1"));
+    /// ```
+    pub fn boxplot_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", self.boxplot_data())
     }
 
-    /// Filters the exam students yielding only the students that are in the
-    /// given file. The file format should be the same as the one used in
-    /// [from_file](Exam::from_file).
+    /// Renders the histogram printed by [histogram](Exam::histogram) as an
+    /// image and writes it to the given path, e.g. to include it in slides or
+    /// reports.
+    ///
+    /// Grades greater than the maximum grade are drawn in the last bucket, and
+    /// reported in the returned list of [RenderWarning]s.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use std::path::Path;
+    /// use exms::exam::{Exam, ImageFormat, Student};
     ///
-    /// use exms::error::ParseError;
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// exam.histogram_to_file("histogram.svg", ImageFormat::Svg)
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "image-export")]
+    pub fn histogram_to_file(
+        &self,
+        path: impl AsRef<Path>,
+        format: ImageFormat,
+    ) -> io::Result<Vec<RenderWarning>> {
+        let histogram = self.build_histogram(None);
+        histogram.to_file(path, format)?;
+
+        Ok(histogram.warnings())
+    }
+
+    /// Renders the box plot printed by [boxplot](Exam::boxplot) as an image
+    /// and writes it to the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::exam::{Exam, ImageFormat, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// exam.boxplot_to_file("boxplot.png", ImageFormat::Png)
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "image-export")]
+    pub fn boxplot_to_file(&self, path: impl AsRef<Path>, format: ImageFormat) -> io::Result<()> {
+        self.boxplot_data().to_file(path, format)
+    }
+
+    /// Returns the [BoxPlot] printed by [boxplot](Exam::boxplot) without
+    /// printing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// use exms::exam::Exam;
+    /// use exms::exam::Student;
     ///
-    /// fn main() -> Result<(), ParseError> {
-    ///     let file_path = Path::new("students.json");
-    ///     let mut exam = Exam::from_file(&file_path)?;
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 6.5),
+    ///     Student::new("Jose Abad Martínez", 7.0),
+    ///     Student::new("David Jiménez Hidalgo", 6.8),
+    ///     Student::new("Irene Alcántara Campillo", 7.2),
+    ///     Student::new("Jorge Rodríguez Martín", 0.5),
+    /// ];
     ///
-    ///     let file_path = Path::new("students2.json");
-    ///     exam.filter_by_file(&[file_path])?;
+    /// let exam = Exam::new(students);
+    /// let boxplot = exam.boxplot_data();
     ///
-    ///     Ok(())
-    /// }
+    /// assert_eq!(boxplot.five_numbers(), [6.5, 6.5, 6.8, 7.0, 7.2]);
+    /// assert_eq!(boxplot.outliers(), &[0.5]);
     /// ```
-    pub fn filter_by_file<P: AsRef<Path>>(&mut self, file_paths: &[P]) -> Result<(), ParseError> {
-        for path in file_paths {
-            let exam = parse_exam_file(path.as_ref())?;
-            let students = exam.students;
+    pub fn boxplot_data(&self) -> BoxPlot {
+        BoxPlot::new(&self.students, &self.statistics, self.max_grade)
+    }
 
-            self.students.retain(|student| {
-                students
-                    .iter()
-                    .any(|s| s.name.to_lowercase() == student.name.to_lowercase())
-            });
+    // Tables printed by `students`, preceded by the exam header
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn students_tables(&self, sort_by: Option<SortBy>) -> (Vec<Table>, Vec<RenderWarning>) {
+        let students = self.sorted_students(sort_by);
+        let (table, warnings) = self
+            .statistics
+            .students_table(&students, &self.student_columns());
+
+        (self.header().into_iter().chain([table]).collect(), warnings)
+    }
+
+    // Tables printed by `print_students_grouped`, preceded by the exam header
+    fn grouped_students_tables(&self, sort_by: Option<SortBy>) -> (Vec<Table>, Vec<RenderWarning>) {
+        let students = self.sorted_students(sort_by);
+        let (table, warnings) = group::grouped_students_table(
+            &self.group_statistics(),
+            &students,
+            &self.student_columns(),
+        );
+
+        (self.header().into_iter().chain([table]).collect(), warnings)
+    }
+
+    fn sorted_students(&self, sort_by: Option<SortBy>) -> Vec<&Student> {
+        let mut students: Vec<&Student> = self.students.iter().collect();
+
+        match sort_by {
+            Some(SortBy::Grade) => {
+                students.sort_by_cached_key(|s| unidecode(&s.name.to_lowercase()));
+                students.sort_by(|a, b| b.grade.partial_cmp(&a.grade).unwrap_or(Ordering::Equal));
+            }
+            Some(SortBy::Name) => {
+                students.sort_by_cached_key(|s| unidecode(&s.name.to_lowercase()));
+            }
+            Some(SortBy::Rank) => students.sort_by(|a, b| {
+                a.rank
+                    .unwrap_or(f32::MAX)
+                    .total_cmp(&b.rank.unwrap_or(f32::MAX))
+            }),
+            Some(SortBy::Percentile) => students.sort_by(|a, b| {
+                b.percentile
+                    .partial_cmp(&a.percentile)
+                    .unwrap_or(Ordering::Equal)
+            }),
+            Some(SortBy::Group) => {
+                students.sort_by_cached_key(|s| unidecode(&s.name.to_lowercase()));
+                students.sort_by(|a, b| match (&a.group, &b.group) {
+                    (Some(a), Some(b)) => a.cmp(b),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                });
+            }
+            Some(SortBy::Delta) => {
+                let mean = self.statistics.mean();
+                students.sort_by_cached_key(|s| unidecode(&s.name.to_lowercase()));
+                students.sort_by(|a, b| (b.grade - mean).abs().total_cmp(&(a.grade - mean).abs()));
+            }
+            None => (),
         }
 
-        Ok(())
+        students
     }
 
-    /// Print the exam students in a well formatted table with some statistical
-    /// information about each student, like the percentile, the rank, etc...
+    fn student_columns(&self) -> StudentColumns<'_> {
+        StudentColumns::new(
+            &self.statistics,
+            self.normalized_scale,
+            self.grade_scale.as_ref(),
+            self.outlier_marker
+                .map(|method| method.bounds(&self.statistics)),
+            &self.format_rules,
+        )
+    }
+
+    // Tables printed by `summary`, preceded by the exam header
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn summary_tables(&self, extended: bool) -> Vec<Table> {
+        let band_counts = self
+            .grade_scale
+            .as_ref()
+            .map(|scale| scale.counts(&self.students))
+            .unwrap_or_default();
+
+        let mut tables: Vec<Table> = self.header().into_iter().collect();
+        tables.push(self.statistics.summary_table(&band_counts, extended));
+
+        let questions = self.question_statistics();
+        if !questions.is_empty() {
+            tables.push(questions_table(&questions));
+        }
+
+        tables
+    }
+
+    // Title and metadata of the exam shown on top of every report
+    fn header(&self) -> Option<Table> {
+        let metadata: Vec<(&str, &str)> = [
+            ("Course", &self.course),
+            ("Term", &self.term),
+            ("Instructor", &self.instructor),
+            ("Date", &self.date),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.as_deref().map(|value| (label, value)))
+        .collect();
+
+        header_table(self.title.as_deref(), &metadata)
+    }
+
+    /// Returns the grade range and number of students of each histogram
+    /// bucket, using buckets of width `step`. If no step is given, the buckets
+    /// match the bands of the exam [GradeScale], or have a width of 1 if it
+    /// has none. These are the same buckets printed by
+    /// [histogram](Exam::histogram).
+    ///
+    /// Grades equal to or greater than the maximum grade are counted in the
+    /// last bucket.
     ///
     /// # Examples
     ///
@@ -257,18 +4166,50 @@ impl Exam {
     /// let students = &[
     ///     Student::new("Joan Beltrán Peris", 4.6),
     ///     Student::new("Jose Abad Martínez", 3.6),
-    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    ///     Student::new("David Jiménez Hidalgo", 10.0),
     /// ];
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.students();
+    /// let exam = Exam::new(students);
+    /// let buckets = exam.histogram_buckets(Some(5.0));
+    ///
+    /// assert_eq!(buckets, vec![(0.0..5.0, 2), (5.0..10.0, 1)]);
     /// ```
-    pub fn students(&self) {
-        self.statistics.students(&self.students)
+    pub fn histogram_buckets(&self, step: Option<f64>) -> Vec<(Range<f64>, usize)> {
+        self.build_histogram(step).buckets().to_vec()
     }
 
-    /// Print statistical information about the exam in a well formatted table,
-    /// like the mean, total students, the exam pass percentage etc...
+    /// Returns the [Histogram] printed by [histogram](Exam::histogram)
+    /// without printing it, with the same buckets as
+    /// [histogram_buckets](Exam::histogram_buckets). It can be serialized to
+    /// feed the distribution of the grades into other tools, or rendered later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 10.0),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let histogram = exam.histogram_data(Some(5.0));
+    ///
+    /// assert_eq!(histogram.buckets(), &[(0.0..5.0, 2), (5.0..10.0, 1)]);
+    /// assert_eq!(histogram.step(), Some(5.0));
+    /// assert_eq!(histogram.max_grade(), 10.0);
+    /// assert!(histogram.warnings().is_empty());
+    /// ```
+    pub fn histogram_data(&self, step: Option<f64>) -> Histogram {
+        self.build_histogram(step)
+    }
+
+    /// Returns a one-line sparkline of the grades distribution, with one
+    /// character per histogram bucket. Buckets are chosen in the same way as
+    /// in [histogram_buckets](Exam::histogram_buckets).
     ///
     /// # Examples
     ///
@@ -279,17 +4220,21 @@ impl Exam {
     /// let students = &[
     ///     Student::new("Joan Beltrán Peris", 4.6),
     ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("Diego Alba Gisbert", 3.1),
     ///     Student::new("David Jiménez Hidalgo", 7.94),
     /// ];
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.summary();
+    /// let exam = Exam::new(students);
+    ///
+    /// assert_eq!(exam.sparkline(None), "▁▁▁█▅▁▁▅▁▁");
+    /// assert_eq!(exam.sparkline(Some(5.0)), "█▄");
     /// ```
-    pub fn summary(&self) {
-        self.statistics.summary(&self.title)
+    pub fn sparkline(&self, step: Option<f64>) -> String {
+        self.build_histogram(step).sparkline()
     }
 
-    /// Print a histogram of the exam grades.
+    /// Returns a [HistogramBuilder] to configure a histogram of the exam
+    /// grades that can be rendered to different targets.
     ///
     /// # Examples
     ///
@@ -303,10 +4248,90 @@ impl Exam {
     ///     Student::new("David Jiménez Hidalgo", 7.94),
     /// ];
     ///
-    /// let mut exam = Exam::new(students);
-    /// exam.histogram();
+    /// let exam = Exam::new(students);
+    /// let histogram = exam.histogram_builder().step(0.5).size(80, 24).build();
+    ///
+    /// histogram.print();
+    /// let svg = histogram.to_svg();
+    ///
+    /// assert!(svg.starts_with("<svg"));
     /// ```
-    pub fn histogram(&self, step: Option<f64>) {
-        plot::histogram(&self.students, self.max_grade, step)
+    pub fn histogram_builder(&self) -> HistogramBuilder<'_> {
+        let pass_grade = self.pass_grade();
+        let distinction_grade = self
+            .grade_scale
+            .as_ref()
+            .and_then(|scale| scale.bands().last())
+            .map(|band| band.min_grade)
+            .filter(|&min_grade| min_grade > pass_grade);
+
+        HistogramBuilder::new(&self.students, self.max_grade)
+            .thresholds(pass_grade, distinction_grade)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn build_histogram(&self, step: Option<f64>) -> Histogram {
+        let mut builder = self.histogram_builder();
+        match (step, &self.grade_scale) {
+            (Some(step), _) => builder = builder.step(step),
+            (None, Some(scale)) => builder = builder.edges(scale.edges(self.max_grade)),
+            (None, None) => (),
+        }
+
+        builder.build()
+    }
+}
+
+// Name used to tell whether students of different exams are the same person
+fn normalized_name(name: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    unidecode(&name).to_lowercase()
+}
+
+// Names of the students whose name repeats the name of a previous student,
+// ignoring case, accents and whitespace
+pub(crate) fn repeated_names(students: &[Student]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    students
+        .iter()
+        .filter(|student| !seen.insert(normalized_name(&student.name)))
+        .map(|student| student.name.clone())
+        .collect()
+}
+
+// Adds a warning for each repeated student that is not reported yet
+pub(crate) fn add_duplicate_warnings(warnings: &mut Vec<ExamWarning>, students: &[Student]) {
+    for name in repeated_names(students) {
+        let warning = ExamWarning::DuplicateStudent(name);
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+}
+
+// Key used to match the student with the students of other exams
+pub(crate) fn student_key(student: &Student, match_by: MatchBy) -> Option<String> {
+    match match_by {
+        MatchBy::Name => Some(normalized_name(&student.name)),
+        MatchBy::Id => student.id.as_deref().map(|id| id.trim().to_string()),
     }
 }
+
+impl MatchBy {
+    fn log_suffix(self) -> &'static str {
+        match self {
+            MatchBy::Name => "",
+            MatchBy::Id => " by ID",
+        }
+    }
+}
+
+// Names are matched ignoring case and accents
+fn name_matches(student: &Student, query: &str) -> bool {
+    unidecode(&student.name.to_lowercase()).contains(&unidecode(&query.to_lowercase()))
+}
+
+fn name_matches_fuzzy(student: &Student, query: &str, max_typos: usize) -> bool {
+    let name = unidecode(&student.name.to_lowercase());
+    fuzzy::substring_distance(&unidecode(&query.to_lowercase()), &name) <= max_typos
+}
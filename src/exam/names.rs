@@ -0,0 +1,178 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::exam::{Language, Student};
+
+/// Anomaly found in the name of a student that can make it silently mismatch
+/// the same student in other files, see
+/// [Exam::name_issues](crate::exam::Exam::name_issues).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NameAnomaly {
+    /// The name starts or ends with whitespace.
+    SurroundingWhitespace,
+
+    /// The name contains several whitespace characters in a row, e.g. a
+    /// double space.
+    RepeatedWhitespace,
+
+    /// The name contains whitespace other than spaces, e.g. a stray tab or a
+    /// non-breaking space.
+    UnusualWhitespace,
+
+    /// The name is written in a different case than most names of the exam,
+    /// e.g. in capitals when the rest are not. It's only reported, as the
+    /// right casing of a name can't be told from the name alone, e.g.
+    /// `McDonald` or `de la Fuente`.
+    InconsistentCasing,
+}
+
+/// Name of a student with anomalies, along with the name they would be fixed
+/// to, see [Exam::name_issues](crate::exam::Exam::name_issues).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NameIssue {
+    /// Name of the student, as written in the exam.
+    pub name: String,
+
+    /// Anomalies found in the name.
+    pub anomalies: Vec<NameAnomaly>,
+
+    /// Name of the student once the whitespace anomalies are fixed, the same
+    /// as the name if there are none.
+    pub normalized: String,
+}
+
+// How the letters of a name are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Casing {
+    Upper,
+    Lower,
+    Mixed,
+}
+
+impl NameAnomaly {
+    fn description(self, language: Language) -> &'static str {
+        match (self, language) {
+            (NameAnomaly::SurroundingWhitespace, Language::English) => "surrounding whitespace",
+            (NameAnomaly::SurroundingWhitespace, Language::Spanish) => {
+                "espacios al principio o al final"
+            }
+            (NameAnomaly::RepeatedWhitespace, Language::English) => "repeated whitespace",
+            (NameAnomaly::RepeatedWhitespace, Language::Spanish) => "espacios repetidos",
+            (NameAnomaly::UnusualWhitespace, Language::English) => "tabs or unusual whitespace",
+            (NameAnomaly::UnusualWhitespace, Language::Spanish) => {
+                "tabuladores o espacios inusuales"
+            }
+            (NameAnomaly::InconsistentCasing, Language::English) => "inconsistent casing",
+            (NameAnomaly::InconsistentCasing, Language::Spanish) => "mayúsculas inconsistentes",
+        }
+    }
+}
+
+impl NameIssue {
+    // Description of the issue in the given language, with the anomalies
+    // highlighted
+    pub(crate) fn describe(&self, language: Language, highlight: fn(&str) -> String) -> String {
+        let anomalies: Vec<&str> = self
+            .anomalies
+            .iter()
+            .map(|anomaly| anomaly.description(language))
+            .collect();
+        let anomalies = highlight(&anomalies.join(", "));
+
+        if self.normalized == self.name {
+            format!("{:?}: {anomalies}", self.name)
+        } else {
+            let should_be = match language {
+                Language::English => "should be",
+                Language::Spanish => "debería ser",
+            };
+            format!(
+                "{:?} {should_be} {:?}: {anomalies}",
+                self.name, self.normalized
+            )
+        }
+    }
+}
+
+impl fmt::Display for NameIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(Language::English, |text| text.yellow().to_string()))
+    }
+}
+
+// Names with anomalies, in the order of the students
+pub fn name_issues(students: &[Student]) -> Vec<NameIssue> {
+    let casing = usual_casing(students);
+
+    students
+        .iter()
+        .filter_map(|student| {
+            let name = &student.name;
+            let mut anomalies = Vec::new();
+
+            if name.trim() != name {
+                anomalies.push(NameAnomaly::SurroundingWhitespace);
+            }
+            let inner: Vec<char> = name.trim().chars().collect();
+            if inner
+                .windows(2)
+                .any(|pair| pair[0].is_whitespace() && pair[1].is_whitespace())
+            {
+                anomalies.push(NameAnomaly::RepeatedWhitespace);
+            }
+            if name.chars().any(|c| c.is_whitespace() && c != ' ') {
+                anomalies.push(NameAnomaly::UnusualWhitespace);
+            }
+            if casing.is_some_and(|casing| casing_of(name).is_some_and(|c| c != casing)) {
+                anomalies.push(NameAnomaly::InconsistentCasing);
+            }
+
+            (!anomalies.is_empty()).then(|| NameIssue {
+                name: name.clone(),
+                anomalies,
+                normalized: normalize(name),
+            })
+        })
+        .collect()
+}
+
+// Casing of most names of the exam, or `None` if there are no names with
+// letters
+fn usual_casing(students: &[Student]) -> Option<Casing> {
+    let mut counts = [(Casing::Mixed, 0), (Casing::Upper, 0), (Casing::Lower, 0)];
+    for casing in students.iter().filter_map(|s| casing_of(&s.name)) {
+        if let Some((_, count)) = counts.iter_mut().find(|(c, _)| *c == casing) {
+            *count += 1;
+        }
+    }
+
+    // Ties are won by mixed case, the first one
+    counts
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .min_by_key(|(_, count)| Reverse(*count))
+        .map(|(casing, _)| *casing)
+}
+
+// Name trimmed, with every run of whitespace replaced by a single space
+pub(crate) fn normalize(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn casing_of(name: &str) -> Option<Casing> {
+    let mut letters = name.chars().filter(|c| c.is_alphabetic()).peekable();
+    letters.peek()?;
+
+    let (upper, lower) = letters.fold((false, false), |(upper, lower), c| {
+        (upper || c.is_uppercase(), lower || c.is_lowercase())
+    });
+
+    Some(match (upper, lower) {
+        (true, false) => Casing::Upper,
+        (false, true) => Casing::Lower,
+        _ => Casing::Mixed,
+    })
+}
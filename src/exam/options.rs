@@ -0,0 +1,57 @@
+use serde::Serialize;
+
+use crate::exam::Limits;
+
+/// Options used to parse an exam file, see
+/// [Exam::from_file_with_options](crate::exam::Exam::from_file_with_options).
+///
+/// Every check is disabled by default, which is how
+/// [Exam::from_file](crate::exam::Exam::from_file) parses files.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{parse_bytes_with_options, FileFormat, Limits, ParseOptions};
+///
+/// let options = ParseOptions {
+///     limits: Limits {
+///         max_students: Some(100),
+///         ..Default::default()
+///     },
+///     reject_duplicates: true,
+///     ..Default::default()
+/// };
+/// let content = b"name,grade\nJoan Beltr\xc3\xa1n,7.5\njoan beltran,4";
+/// let error = parse_bytes_with_options(FileFormat::Csv, content, &options).unwrap_err();
+///
+/// assert_eq!(error.duplicate_student(), Some("joan beltran"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct ParseOptions {
+    /// Limits the file has to stay within.
+    pub limits: Limits,
+
+    /// Whether files where the same student appears more than once, ignoring
+    /// case, accents and whitespace, fail to parse, with an error for which
+    /// [ParseError::duplicate_student](crate::error::ParseError::duplicate_student)
+    /// returns their name. Otherwise they are reported by
+    /// [Exam::warnings](crate::exam::Exam::warnings).
+    pub reject_duplicates: bool,
+
+    /// Whether the maximum grade is inferred from the grades when the file
+    /// doesn't set it, see
+    /// [ExamBuilder::infer_max_grade](crate::exam::ExamBuilder::infer_max_grade).
+    pub infer_max_grade: bool,
+
+    /// Whether the grades are validated once the exam is parsed, adding the
+    /// warnings of [Exam::validate](crate::exam::Exam::validate) to
+    /// [Exam::warnings](crate::exam::Exam::warnings).
+    pub validate_grades: bool,
+
+    /// Whether the names of the students are checked once the exam is
+    /// parsed, adding an
+    /// [ExamWarning::NameAnomaly](crate::exam::ExamWarning::NameAnomaly)
+    /// for each issue of [Exam::name_issues](crate::exam::Exam::name_issues)
+    /// to [Exam::warnings](crate::exam::Exam::warnings).
+    pub check_names: bool,
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::exam::{Statistics, Student};
+
+/// Method used to decide which grades of an exam are outliers, see
+/// [Exam::outliers](crate::exam::Exam::outliers).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OutlierMethod {
+    /// Grades further than the given number of interquartile ranges below the
+    /// first quartile or above the third quartile. Tukey's fences use 1.5.
+    Iqr(f32),
+
+    /// Grades further than the given number of standard deviations from the
+    /// mean, usually 2 or 3.
+    ZScore(f32),
+}
+
+impl OutlierMethod {
+    // Lowest and highest grades that are not outliers
+    pub(crate) fn bounds(&self, statistics: &Statistics) -> (f32, f32) {
+        match *self {
+            OutlierMethod::Iqr(factor) => {
+                let margin = factor * statistics.interquartile_range();
+                (
+                    statistics.first_quartile() - margin,
+                    statistics.third_quartile() + margin,
+                )
+            }
+            OutlierMethod::ZScore(threshold) => {
+                let margin = threshold * statistics.std_dev();
+                (statistics.mean() - margin, statistics.mean() + margin)
+            }
+        }
+    }
+}
+
+// Students whose grades are outliers according to the method, in the same
+// order as the given students.
+pub fn outliers<'a>(
+    students: &'a [Student],
+    statistics: &Statistics,
+    method: OutlierMethod,
+) -> Vec<&'a Student> {
+    let (lowest, highest) = method.bounds(statistics);
+
+    students
+        .iter()
+        .filter(|student| student.grade < lowest || student.grade > highest)
+        .collect()
+}
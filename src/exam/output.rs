@@ -0,0 +1,155 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use prettytable::format::{self, TableFormat};
+
+// Style set with `set_output_style`, stored as the discriminants of its enums
+static COLOR_MODE: AtomicU8 = AtomicU8::new(ColorMode::Auto as u8);
+static TABLE_STYLE: AtomicU8 = AtomicU8::new(TableStyle::Box as u8);
+
+/// Whether the output of the crate is colored, see [set_color_mode].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Colored only when writing to a terminal, unless the `NO_COLOR`
+    /// environment variable is set.
+    #[default]
+    Auto,
+
+    /// Always colored, e.g. to keep the colors when piping to a pager.
+    Always,
+
+    /// Never colored.
+    Never,
+}
+
+/// Characters used to draw the borders of the tables and of the histogram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableStyle {
+    /// Box-drawing characters, e.g. `┌─┬─┐`.
+    #[default]
+    Box,
+
+    /// Plain ASCII characters, e.g. `+-+-+`, for files, CI logs and terminals
+    /// that can't show box-drawing characters.
+    Ascii,
+}
+
+impl TableStyle {
+    /// Returns the format of the [prettytable] tables drawn in this style, to
+    /// draw other tables like the ones printed by the crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{output_style, TableStyle};
+    /// use prettytable::{row, Table};
+    ///
+    /// let mut table = Table::new();
+    /// table.add_row(row!["Joan Beltrán Peris", 4.6]);
+    /// table.set_format(TableStyle::Ascii.format());
+    ///
+    /// assert!(table.to_string().starts_with("+----"));
+    ///
+    /// // Same style as the tables of the crate
+    /// table.set_format(output_style().table.format());
+    /// ```
+    pub fn format(self) -> TableFormat {
+        match self {
+            TableStyle::Box => *format::consts::FORMAT_BOX_CHARS,
+            TableStyle::Ascii => *format::consts::FORMAT_DEFAULT,
+        }
+    }
+}
+
+/// Style of everything printed by the crate: the summary, the students table,
+/// the histogram and every other report. It's global, so it's usually set once
+/// at the start of the program, see [set_output_style].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutputStyle {
+    /// Whether the output is colored.
+    pub color: ColorMode,
+
+    /// Characters used to draw the borders of the tables.
+    pub table: TableStyle,
+}
+
+/// Sets the style of everything printed by the crate from now on.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{output_style, set_output_style, ColorMode, OutputStyle, TableStyle};
+/// use exms::exam::{Exam, Student};
+///
+/// set_output_style(OutputStyle {
+///     color: ColorMode::Never,
+///     table: TableStyle::Ascii,
+/// });
+///
+/// let exam = Exam::new(vec![Student::new("Joan Beltrán Peris", 4.6)]);
+///
+/// let mut output = Vec::new();
+/// exam.students_to(&mut output, None).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+///
+/// assert!(output.starts_with("+------"));
+/// assert!(!output.contains('│'));
+/// assert_eq!(output_style().table, TableStyle::Ascii);
+/// ```
+pub fn set_output_style(style: OutputStyle) {
+    set_color_mode(style.color);
+    TABLE_STYLE.store(style.table as u8, Ordering::Relaxed);
+}
+
+/// Sets whether everything printed by the crate from now on is colored,
+/// keeping the rest of the [OutputStyle].
+pub fn set_color_mode(mode: ColorMode) {
+    match mode {
+        ColorMode::Auto => colored::control::unset_override(),
+        ColorMode::Always => colored::control::set_override(true),
+        ColorMode::Never => colored::control::set_override(false),
+    }
+    COLOR_MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+/// Returns the style of everything printed by the crate, the default one
+/// unless it was changed with [set_output_style] or [set_color_mode].
+pub fn output_style() -> OutputStyle {
+    let color = match COLOR_MODE.load(Ordering::Relaxed) {
+        mode if mode == ColorMode::Always as u8 => ColorMode::Always,
+        mode if mode == ColorMode::Never as u8 => ColorMode::Never,
+        _ => ColorMode::Auto,
+    };
+    let table = match TABLE_STYLE.load(Ordering::Relaxed) {
+        style if style == TableStyle::Ascii as u8 => TableStyle::Ascii,
+        _ => TableStyle::Box,
+    };
+
+    OutputStyle { color, table }
+}
+
+// Format of the tables printed with prettytable
+pub(crate) fn table_format() -> TableFormat {
+    output_style().table.format()
+}
+
+// Text drawn with box-drawing characters, redrawn with ASCII characters if
+// that's the style of the tables
+pub(crate) fn with_table_style(text: &str) -> String {
+    match output_style().table {
+        TableStyle::Box => text.to_string(),
+        TableStyle::Ascii => text.chars().map(ascii_border).collect(),
+    }
+}
+
+// ASCII character closest to a box-drawing one, or to the bullet marking
+// the outliers
+fn ascii_border(c: char) -> char {
+    match c {
+        '─' | '━' | '╌' | '┄' => '-',
+        '═' => '=',
+        '│' | '┃' | '║' | '╎' | '┆' => '|',
+        '\u{2500}'..='\u{257F}' => '+',
+        '•' => '*',
+        _ => c,
+    }
+}
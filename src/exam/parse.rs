@@ -1,64 +1,600 @@
+use std::borrow::Cow;
+use std::cell::Cell;
 use std::ffi::OsStr;
 use std::fs;
+use std::io::{self, Read};
 use std::path::Path;
 
-use indexmap::map::IndexMap;
-use serde::Deserialize;
+use serde::de::DeserializeSeed;
 
-use crate::error::{ParseError, ParseErrorKind, WithPath};
-use crate::exam::{Exam, Student};
+use crate::error::{toml_error, ParseError, ParseErrorKind, WithPath};
+use crate::exam::document::{BorrowedDocument, DocumentSeed, StudentEntry, StudentMap};
+#[cfg(feature = "xlsx")]
+use crate::exam::xlsx::{read_xlsx, read_xlsx_bytes, XlsxOptions};
+use crate::exam::{repeated_names, Exam, ExamWarning, GradeScale, Limits, ParseOptions, Student};
 
-#[derive(Deserialize)]
-#[serde(deny_unknown_fields)]
-struct ExamFile {
-    details: Option<Details>,
+/// Format of the content of an exam file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Toml,
+    Json,
+    Yaml,
+    Csv,
+    #[cfg(feature = "xlsx")]
+    Xlsx,
+}
 
-    // Using IndexMap instead of HashMap to preserve the students order of the
-    // original file.
-    students: IndexMap<String, f32>,
+impl FileFormat {
+    /// Returns the format of the files with the given extension, e.g. `"yml"`,
+    /// or `None` if the format is not supported.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "toml" => Some(FileFormat::Toml),
+            "json" => Some(FileFormat::Json),
+            "yaml" | "yml" => Some(FileFormat::Yaml),
+            "csv" => Some(FileFormat::Csv),
+            #[cfg(feature = "xlsx")]
+            "xlsx" => Some(FileFormat::Xlsx),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Deserialize)]
-#[serde(deny_unknown_fields)]
-struct Details {
-    name: Option<String>,
-    max_grade: Option<f32>,
+// Path shown in the errors of exams that weren't read from a file
+pub const INPUT_PATH: &str = "<input>";
+
+/// Creates a new [Exam] from the content of an exam file in the given format,
+/// following the same format as [Exam::from_file].
+///
+/// The content is treated as untrusted: invalid UTF-8, deeply nested values
+/// and numbers that don't fit in a grade are reported as a [ParseError]
+/// instead of panicking, so this is the entry point to use for uploaded files
+/// and for fuzzing. The exam has no title, as there's no file name to take it
+/// from.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{parse_bytes, FileFormat};
+///
+/// let content = b"name,grade\nJoan Bellido Beltr\xc3\xa1n,7.5\nJose Abad Mart\xc3\xadnez,4";
+/// let exam = parse_bytes(FileFormat::Csv, content).unwrap();
+///
+/// assert_eq!(exam.statistics().total_students(), 2);
+///
+/// assert!(parse_bytes(FileFormat::Json, b"\xff\xfe").is_err());
+/// assert!(parse_bytes(FileFormat::Toml, b"[students]\nJoan = 1e39").is_err());
+///
+/// let content = b"[details]\nmax_grade = -5\n[students]\nJoan = 0";
+/// let error = parse_bytes(FileFormat::Toml, content).unwrap_err();
+/// assert!(error.to_string().contains("must be greater than 0"));
+/// ```
+pub fn parse_bytes(format: FileFormat, bytes: &[u8]) -> Result<Exam, ParseError> {
+    parse_bytes_with_options(format, bytes, &ParseOptions::default())
+}
+
+// Reads the whole content of the reader and parses it like `parse_bytes`
+pub fn parse_reader<R: Read>(format: FileFormat, mut reader: R) -> Result<Exam, ParseError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).with_path(INPUT_PATH)?;
+
+    parse_bytes(format, &bytes)
+}
+
+/// Same as [parse_bytes], but failing if the content exceeds any of the given
+/// [Limits].
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{parse_bytes_with_limits, FileFormat, Limits};
+///
+/// let limits = Limits {
+///     max_students: Some(1),
+///     ..Default::default()
+/// };
+/// let content = br#"{ "students": { "Joan": 7.5, "Jose": 4 } }"#;
+/// let error = parse_bytes_with_limits(FileFormat::Json, content, &limits).unwrap_err();
+///
+/// assert!(error.is_limit_exceeded());
+/// ```
+pub fn parse_bytes_with_limits(
+    format: FileFormat,
+    bytes: &[u8],
+    limits: &Limits,
+) -> Result<Exam, ParseError> {
+    let options = ParseOptions {
+        limits: limits.clone(),
+        ..Default::default()
+    };
+
+    parse_bytes_with_options(format, bytes, &options)
+}
+
+/// Same as [parse_bytes], but parsing the content with the given
+/// [ParseOptions].
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{parse_bytes_with_options, FileFormat, ParseOptions};
+///
+/// let options = ParseOptions {
+///     infer_max_grade: true,
+///     ..Default::default()
+/// };
+/// let content = br#"{ "students": { "Joan": 75, "Jose": 40 } }"#;
+/// let exam = parse_bytes_with_options(FileFormat::Json, content, &options).unwrap();
+///
+/// assert_eq!(exam.statistics().max_grade(), 100.0);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn parse_bytes_with_options(
+    format: FileFormat,
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<Exam, ParseError> {
+    let parse = || {
+        options
+            .limits
+            .check_file_size(bytes.len() as u64)
+            .map_err(ParseErrorKind::LimitExceeded)?;
+
+        build_exam(
+            parse_content(format, bytes, &options.limits)?,
+            None,
+            options,
+        )
+    };
+
+    parse().map_err(|kind| ParseError::new(kind, INPUT_PATH))
 }
 
 // Files should follow the required format, see docs for more info.
-pub fn parse_exam_file(path: &Path) -> Result<Exam, ParseError> {
-    let file_content = fs::read_to_string(path).with_path(path)?;
-    let file_extension = path.extension().and_then(OsStr::to_str);
+pub fn parse_exam_file(path: &Path, options: &ParseOptions) -> Result<Exam, ParseError> {
+    // The size is checked before reading the file, so huge files are never
+    // loaded into memory
+    if options.limits.max_file_size.is_some() {
+        let size = fs::metadata(path).with_path(path)?.len();
+        options
+            .limits
+            .check_file_size(size)
+            .map_err(|limit| ParseError::new(ParseErrorKind::LimitExceeded(limit), path))?;
+    }
 
-    let exam_file: ExamFile = match file_extension {
-        Some("toml") => toml::from_str(&file_content).with_path(path)?,
-        Some("json") => serde_json::from_str(&file_content).with_path(path)?,
+    // Excel workbooks are opened by path, so they don't need to be read first
+    #[cfg(feature = "xlsx")]
+    if path.extension().and_then(OsStr::to_str) == Some("xlsx") {
+        return parse_xlsx_file(path, &XlsxOptions::default(), options);
+    }
+
+    let file_content = fs::read(path).with_path(path)?;
+
+    parse_exam_content(&file_content, path, options)
+}
+
+// Parses every file with a supported extension in the directory, sorted by
+// file name. Hidden files and subdirectories are skipped.
+pub fn parse_exam_dir(dir: &Path) -> Result<Vec<Exam>, ParseError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).with_path(dir)? {
+        let path = entry.with_path(dir)?.path();
+
+        let visible = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|name| !name.starts_with('.'));
+        let supported = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .and_then(FileFormat::from_extension)
+            .is_some();
+
+        if path.is_file() && supported && visible {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| parse_exam_file(path, &ParseOptions::default()))
+        .collect()
+}
+
+// Same as `parse_exam_file`, but the file is memory-mapped instead of being
+// read into memory, so only the pages being parsed need to be resident.
+#[cfg(feature = "mmap")]
+pub fn parse_mapped_exam_file(path: &Path, options: &ParseOptions) -> Result<Exam, ParseError> {
+    #[cfg(feature = "xlsx")]
+    if path.extension().and_then(OsStr::to_str) == Some("xlsx") {
+        return parse_xlsx_file(path, &XlsxOptions::default(), options);
+    }
+
+    let file = fs::File::open(path).with_path(path)?;
+
+    // The size of the file is known before mapping it
+    if options.limits.max_file_size.is_some() {
+        let size = file.metadata().with_path(path)?.len();
+        options
+            .limits
+            .check_file_size(size)
+            .map_err(|limit| ParseError::new(ParseErrorKind::LimitExceeded(limit), path))?;
+    }
+
+    // SAFETY: the map is only read while parsing and dropped right after. As
+    // documented in `Exam::from_file_mapped`, the file must not be modified
+    // in the meantime.
+    let map = unsafe { memmap2::Mmap::map(&file) }.with_path(path)?;
+
+    parse_exam_content(&map, path, options)
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))
+)]
+fn parse_exam_content(
+    file_content: &[u8],
+    path: &Path,
+    options: &ParseOptions,
+) -> Result<Exam, ParseError> {
+    let format = match path.extension().and_then(OsStr::to_str) {
+        Some(extension) => FileFormat::from_extension(extension)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::UnsupportedFormat, path))?,
         None => return Err(ParseError::new(ParseErrorKind::MissingFormat, path)),
-        _ => return Err(ParseError::new(ParseErrorKind::UnsupportedFormat, path)),
     };
 
-    let students: Vec<Student> = exam_file
+    let document = parse_content(format, file_content, &options.limits)
+        .map_err(|kind| ParseError::new(kind, path))?;
+
+    build_exam(document, Some(path), options).map_err(|kind| ParseError::new(kind, path))
+}
+
+#[cfg(feature = "xlsx")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))
+)]
+pub fn parse_xlsx_file(
+    path: &Path,
+    xlsx_options: &XlsxOptions,
+    options: &ParseOptions,
+) -> Result<Exam, ParseError> {
+    let document = read_xlsx(path, xlsx_options, &options.limits)
+        .map_err(|kind| ParseError::new(kind, path))?;
+
+    build_exam(document, Some(path), options).map_err(|kind| ParseError::new(kind, path))
+}
+
+// The parsers of every format limit how deeply values can be nested, so
+// malicious content results in an error rather than a stack overflow. The
+// limits on the students are checked while they are read.
+pub fn parse_content<'a>(
+    format: FileFormat,
+    content: &'a [u8],
+    limits: &Limits,
+) -> Result<BorrowedDocument<'a>, ParseErrorKind> {
+    let text = || {
+        std::str::from_utf8(content)
+            .map_err(|err| ParseErrorKind::Io(io::Error::new(io::ErrorKind::InvalidData, err)))
+    };
+
+    let exceeded = Cell::new(None);
+    let seed = DocumentSeed {
+        limits,
+        exceeded: &exceeded,
+    };
+
+    let document = match format {
+        FileFormat::Toml => {
+            let text = text()?;
+            seed.deserialize(toml::Deserializer::new(text))
+                .map_err(|err| toml_error(err, text))
+        }
+        FileFormat::Json => {
+            let mut deserializer = serde_json::Deserializer::from_str(text()?);
+            seed.deserialize(&mut deserializer)
+                .and_then(|document| deserializer.end().map(|_| document))
+                .map_err(ParseErrorKind::Json)
+        }
+        FileFormat::Yaml => seed
+            .deserialize(serde_yaml::Deserializer::from_str(text()?))
+            .map_err(ParseErrorKind::Yaml),
+        FileFormat::Csv => parse_csv(text()?, limits),
+        #[cfg(feature = "xlsx")]
+        FileFormat::Xlsx => read_xlsx_bytes(content, &XlsxOptions::default(), limits),
+    };
+
+    // The limit exceeded while reading the students, instead of the error of
+    // the deserializer that only has its message
+    match exceeded.take() {
+        Some(limit) => Err(ParseErrorKind::LimitExceeded(limit)),
+        None => document,
+    }
+}
+
+// The title of the exam defaults to the name of the file it was read from, if
+// any.
+pub fn build_exam(
+    document: BorrowedDocument<'_>,
+    path: Option<&Path>,
+    options: &ParseOptions,
+) -> Result<Exam, ParseErrorKind> {
+    options
+        .limits
+        .check_students(&document)
+        .map_err(ParseErrorKind::LimitExceeded)?;
+    check_finite(&document)?;
+    check_max_grade(&document)?;
+
+    if options.reject_duplicates {
+        if let Some(student) = document.duplicates.first() {
+            return Err(ParseErrorKind::DuplicateStudent {
+                student: student.clone(),
+            });
+        }
+    }
+
+    let students: Vec<Student> = document
         .students
         .into_iter()
-        .map(|(name, grade)| Student::new(name, grade))
+        .map(|(name, entry)| match entry {
+            StudentEntry::Grade(grade) => Ok(Student::new(name, grade)),
+            StudentEntry::Detailed(record) => {
+                if record.grade.is_none() && record.questions.is_empty() {
+                    return Err(ParseErrorKind::MissingGrade {
+                        student: name.into_owned(),
+                    });
+                }
+
+                let mut student = Student::with_questions(name, record.questions);
+                if let Some(grade) = record.grade {
+                    student.grade = grade;
+                }
+                student.answers = record.answers;
+                student.id = record.id;
+                student.group = record.group;
+                student.email = record.email;
+
+                Ok(student)
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    if options.reject_duplicates {
+        if let Some(student) = repeated_names(&students).into_iter().next() {
+            return Err(ParseErrorKind::DuplicateStudent { student });
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(students = students.len(), "parsed exam file");
+
+    let duplicates = document
+        .duplicates
+        .into_iter()
+        .map(ExamWarning::DuplicateStudent)
         .collect();
+    let mut builder = Exam::builder(students)
+        .warnings(duplicates)
+        .infer_max_grade(options.infer_max_grade);
+
+    if let Some(path) = path {
+        builder = builder.source(path.to_owned());
 
-    let mut exam = Exam::new(students);
-    if let Some(name) = path.file_stem().and_then(OsStr::to_str) {
-        exam.set_title(name)
+        if let Some(name) = path.file_stem().and_then(OsStr::to_str) {
+            builder = builder.title(name);
+        }
     }
+    if let Some(details) = document.details {
+        if let Some(unit) = details.unit {
+            builder = builder.unit(unit);
+        }
 
-    if let Some(details) = exam_file.details {
         if let Some(max_grade) = details.max_grade {
-            exam.set_max_grade(max_grade);
+            builder = builder.max_grade(max_grade);
+        }
+
+        if let Some(pass_grade) = details.pass_grade {
+            builder = builder.pass_threshold(pass_grade);
         }
 
         if let Some(exam_name) = details.name {
-            exam.set_title(exam_name);
+            builder = builder.title(exam_name);
+        }
+
+        if let Some(date) = details.date {
+            builder = builder.date(date);
+        }
+
+        if let Some(course) = details.course {
+            builder = builder.course(course);
+        }
+
+        if let Some(term) = details.term {
+            builder = builder.term(term);
+        }
+
+        if let Some(instructor) = details.instructor {
+            builder = builder.instructor(instructor);
+        }
+
+        if let Some(scale) = details.scale {
+            builder = builder.grade_scale(GradeScale::new(scale));
         }
 
-        return Ok(exam);
+        for (question, max_score) in details.questions.unwrap_or_default() {
+            builder = builder.question_max_score(question, max_score);
+        }
+
+        for (question, option) in details.answer_key.unwrap_or_default() {
+            builder = builder.answer_key(question, option);
+        }
     }
 
+    let mut exam = builder.build();
+    exam.validate_grades = options.validate_grades;
+    exam.check_names = options.check_names;
+
     Ok(exam)
 }
+
+// Grades are rescaled and bucketed by the maximum grade, which makes no sense
+// unless it's positive
+fn check_max_grade(document: &BorrowedDocument) -> Result<(), ParseErrorKind> {
+    match document
+        .details
+        .as_ref()
+        .and_then(|details| details.max_grade)
+    {
+        Some(max_grade) if max_grade <= 0.0 => Err(ParseErrorKind::InvalidMaxGrade { max_grade }),
+        _ => Ok(()),
+    }
+}
+
+// Every number of the file must be finite. Formats like TOML accept `inf` and
+// `nan`, and numbers too big for a grade, e.g. `1e39`, overflow to infinity.
+fn check_finite(document: &BorrowedDocument) -> Result<(), ParseErrorKind> {
+    let check = |value: f32, field: &dyn Fn() -> String| {
+        if value.is_finite() {
+            Ok(())
+        } else {
+            Err(ParseErrorKind::NonFinite { field: field() })
+        }
+    };
+
+    for (name, entry) in &document.students {
+        match entry {
+            StudentEntry::Grade(grade) => check(*grade, &|| format!("grade of `{name}`"))?,
+            StudentEntry::Detailed(record) => {
+                if let Some(grade) = record.grade {
+                    check(grade, &|| format!("grade of `{name}`"))?;
+                }
+                for (question, &score) in &record.questions {
+                    check(score, &|| format!("score of `{name}` in `{question}`"))?;
+                }
+            }
+        }
+    }
+
+    if let Some(details) = &document.details {
+        if let Some(max_grade) = details.max_grade {
+            check(max_grade, &|| "max_grade".to_string())?;
+        }
+        if let Some(pass_grade) = details.pass_grade {
+            check(pass_grade, &|| "pass_grade".to_string())?;
+        }
+        for (question, &max_score) in details.questions.iter().flatten() {
+            check(max_score, &|| format!("maximum score of `{question}`"))?;
+        }
+        for (label, &min_grade) in details.scale.iter().flatten() {
+            check(min_grade, &|| format!("minimum grade of `{label}`"))?;
+        }
+    }
+
+    Ok(())
+}
+
+// CSV files contain one `name,grade` record per line and an optional header.
+// Errors are returned along with the line number where they were found.
+fn parse_csv<'a>(
+    content: &'a str,
+    limits: &Limits,
+) -> Result<BorrowedDocument<'a>, ParseErrorKind> {
+    let csv_error = |line, reason| ParseErrorKind::Csv { line, reason };
+    let mut students = StudentMap::with_limits(limits);
+
+    // Spreadsheet exports often start with a byte order mark
+    let content = content.trim_start_matches('\u{feff}');
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (name, grade) =
+            split_csv_record(line).map_err(|reason| csv_error(line_number, reason))?;
+
+        match grade.trim().parse::<f32>() {
+            Ok(grade) => {
+                let name = match name {
+                    Cow::Borrowed(name) => Cow::Borrowed(name.trim()),
+                    Cow::Owned(name) => Cow::Owned(name.trim().to_string()),
+                };
+                students
+                    .insert(name, StudentEntry::Grade(grade))
+                    .map_err(ParseErrorKind::LimitExceeded)?;
+            }
+
+            // The first record is allowed to be a header
+            Err(_) if index == 0 => continue,
+
+            Err(_) => {
+                return Err(csv_error(
+                    line_number,
+                    format!("invalid grade `{}`", grade.trim()),
+                ))
+            }
+        }
+    }
+
+    Ok(BorrowedDocument {
+        details: None,
+        students: students.students,
+        duplicates: students.duplicates,
+    })
+}
+
+// Splits a CSV line into its name and grade fields. Lines without quotes,
+// which are the vast majority, are split borrowing their fields.
+fn split_csv_record(line: &str) -> Result<(Cow<'_, str>, Cow<'_, str>), String> {
+    let wrong_count = |count| format!("expected 2 fields (name,grade), found {count}");
+
+    if !line.contains('"') {
+        let mut fields = line.split(',');
+        return match (fields.next(), fields.next(), fields.next()) {
+            (Some(name), Some(grade), None) => Ok((Cow::Borrowed(name), Cow::Borrowed(grade))),
+            _ => Err(wrong_count(line.split(',').count())),
+        };
+    }
+
+    let fields = split_csv_line(line)?;
+    match <[String; 2]>::try_from(fields) {
+        Ok([name, grade]) => Ok((Cow::Owned(name), Cow::Owned(grade))),
+        Err(fields) => Err(wrong_count(fields.len())),
+    }
+}
+
+// Splits a CSV line into its fields. Fields can be quoted to include commas,
+// and quotes inside quoted fields are escaped by doubling them.
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("unterminated quoted field".to_string());
+    }
+
+    fields.push(field);
+    Ok(fields)
+}
@@ -0,0 +1,362 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::{toml_error, ParseError, ParseErrorKind, PipelineError, WithPath};
+use crate::exam::{
+    export_time_series, CurveMethod, Exam, ExamWarning, ExportFormat, ParseOptions, RenderWarning,
+    SortBy,
+};
+
+/// Analysis described in a TOML spec file: the exam files to read, how to
+/// filter and curve their students and which reports to write, so the same
+/// processing can be repeated every term and shared with other people.
+///
+/// A spec file looks like this:
+///
+/// ```toml
+/// # Files read, relative to the spec file. Students of later files that are
+/// # not in the first one are added to it, see `Exam::union`.
+/// inputs = ["group_a.toml", "group_b.toml"]
+///
+/// # Optional settings, applied in this order
+/// max_grade = 10
+/// pass_grade = 5
+/// filter_files = ["enrolled.toml"]
+/// filter = ["Joan", "Irene"]
+/// curve = { bonus = 0.5 }
+///
+/// # Reports written, to the standard output unless a path is given
+/// [[outputs]]
+/// kind = "students"
+/// sort = "grade"
+///
+/// [[outputs]]
+/// kind = "summary"
+/// extended = true
+/// path = "summary.txt"
+///
+/// [[outputs]]
+/// kind = "histogram"
+/// step = 0.5
+///
+/// [[outputs]]
+/// kind = "boxplot"
+///
+/// [[outputs]]
+/// kind = "time_series"
+/// format = "csv"
+/// path = "statistics.csv"
+/// ```
+///
+/// The curve can be `{ bonus = points }`, `{ scale_highest_to = grade }` or
+/// `{ normalize = { mean = 6, std_dev = 1.5 } }`, see [CurveMethod]. The
+/// statistics are computed again after filtering the students, so the reports
+/// only describe the students that were kept. The input files are parsed
+/// validating their grades and checking the names of their students, see
+/// [ParseOptions].
+///
+/// # Examples
+///
+/// ```
+/// use std::fs;
+///
+/// use exms::exam::Pipeline;
+///
+/// let dir = std::env::temp_dir().join("exms_pipeline_example");
+/// fs::create_dir_all(&dir).unwrap();
+/// fs::write(
+///     dir.join("exam.toml"),
+///     r#"
+///     [students]
+///     "Joan Beltrán Peris" = 4.6
+///     "Jose Abad Martínez" = 3.6
+///     "David Jiménez Hidalgo" = 7.94
+///     "#,
+/// )
+/// .unwrap();
+/// fs::write(
+///     dir.join("analysis.toml"),
+///     r#"
+///     inputs = ["exam.toml"]
+///     curve = { bonus = 1 }
+///
+///     [[outputs]]
+///     kind = "students"
+///     sort = "grade"
+///     path = "students.txt"
+///     "#,
+/// )
+/// .unwrap();
+///
+/// let pipeline = Pipeline::from_file(dir.join("analysis.toml")).unwrap();
+/// let exam = pipeline.exam().unwrap();
+/// assert_eq!(exam.find("jose")[0].grade, 4.6);
+///
+/// let warnings = pipeline.execute().unwrap();
+/// assert!(warnings.is_empty());
+///
+/// let students = fs::read_to_string(dir.join("students.txt")).unwrap();
+/// assert!(students.contains("David Jiménez Hidalgo"));
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pipeline {
+    inputs: Vec<PathBuf>,
+    max_grade: Option<f32>,
+    pass_grade: Option<f32>,
+    #[serde(default)]
+    filter_files: Vec<PathBuf>,
+    #[serde(default)]
+    filter: Vec<String>,
+    curve: Option<CurveMethod>,
+    #[serde(default)]
+    outputs: Vec<Output>,
+
+    // Directory relative paths are resolved against
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+/// Warnings found while running a [Pipeline], see [Pipeline::execute].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PipelineWarnings {
+    /// Warnings of the exam the reports were written from, see
+    /// [Exam::warnings].
+    pub exam: Vec<ExamWarning>,
+
+    /// Warnings found while rendering the reports.
+    pub render: Vec<RenderWarning>,
+}
+
+// Report written by a pipeline
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+enum Output {
+    Students {
+        path: Option<PathBuf>,
+        sort: Option<SortBy>,
+    },
+    Summary {
+        path: Option<PathBuf>,
+        #[serde(default)]
+        extended: bool,
+    },
+    Histogram {
+        path: Option<PathBuf>,
+        step: Option<f64>,
+    },
+    Boxplot {
+        path: Option<PathBuf>,
+    },
+    TimeSeries {
+        path: Option<PathBuf>,
+        format: ExportFormat,
+    },
+}
+
+impl Pipeline {
+    /// Reads a pipeline from a TOML spec file. Relative paths in the file are
+    /// resolved against the directory that contains it.
+    ///
+    /// Spec files with settings that can't be used fail to parse: a
+    /// `max_grade` or a histogram `step` that is not greater than 0, a
+    /// negative `pass_grade`, or a curve whose parameters are not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs;
+    ///
+    /// use exms::exam::Pipeline;
+    ///
+    /// let dir = std::env::temp_dir().join("exms_pipeline_invalid_example");
+    /// fs::create_dir_all(&dir).unwrap();
+    /// fs::write(
+    ///     dir.join("analysis.toml"),
+    ///     r#"
+    ///     inputs = ["exam.toml"]
+    ///     curve = { normalize = { mean = nan, std_dev = 1 } }
+    ///     "#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(Pipeline::from_file(dir.join("analysis.toml")).is_err());
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).with_path(path)?;
+
+        let mut pipeline: Pipeline = toml::from_str(&content)
+            .map_err(|err| ParseError::new(toml_error(err, &content), path))?;
+        pipeline
+            .validate()
+            .map_err(|kind| ParseError::new(kind, path))?;
+        pipeline.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        Ok(pipeline)
+    }
+
+    // Rejects the settings the exam can't be processed with
+    fn validate(&self) -> Result<(), ParseErrorKind> {
+        let invalid = |field: &str, expected: &'static str| ParseErrorKind::InvalidValue {
+            field: field.to_string(),
+            expected,
+        };
+
+        if self
+            .max_grade
+            .is_some_and(|max_grade| !max_grade.is_finite() || max_grade <= 0.0)
+        {
+            return Err(invalid("max_grade", "a number greater than 0"));
+        }
+        if self
+            .pass_grade
+            .is_some_and(|pass_grade| !pass_grade.is_finite() || pass_grade < 0.0)
+        {
+            return Err(invalid("pass_grade", "a number that is not negative"));
+        }
+        if self.curve.is_some_and(|curve| !curve.is_valid()) {
+            return Err(invalid(
+                "curve",
+                "made of finite numbers, with a standard deviation that is not negative",
+            ));
+        }
+        for output in &self.outputs {
+            if let Output::Histogram {
+                step: Some(step), ..
+            } = output
+            {
+                if !step.is_finite() || *step <= 0.0 {
+                    return Err(invalid("step", "a number greater than 0"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the input files and applies the filters and the curve of the
+    /// pipeline, returning the resulting exam without writing any report.
+    pub fn exam(&self) -> Result<Exam, ParseError> {
+        let mut inputs = self.inputs.iter().map(|input| self.resolve(input));
+        let Some(first) = inputs.next() else {
+            return Ok(Exam::new(Vec::new()));
+        };
+
+        let options = ParseOptions {
+            validate_grades: true,
+            check_names: true,
+            ..Default::default()
+        };
+        let mut exam = Exam::from_file_with_options(first, &options)?;
+        let others = inputs
+            .map(|input| Exam::from_file_with_options(input, &options))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Parsed exams are not published, so none of the changes is rejected
+        let unpublished = "parsed exams are not published";
+        if !others.is_empty() {
+            exam.union(&others.iter().collect::<Vec<_>>())
+                .expect(unpublished);
+        }
+
+        if let Some(max_grade) = self.max_grade {
+            exam.set_max_grade(max_grade).expect(unpublished);
+        }
+        if let Some(pass_grade) = self.pass_grade {
+            exam.set_pass_threshold(pass_grade).expect(unpublished);
+        }
+
+        if !self.filter_files.is_empty() {
+            let files: Vec<PathBuf> = self.filter_files.iter().map(|f| self.resolve(f)).collect();
+            exam.retain_students_in_files(&files)?;
+        }
+        if !self.filter.is_empty() {
+            exam.filter_by_name(&self.filter).expect(unpublished);
+        }
+        exam.compute_statistics();
+
+        if let Some(curve) = self.curve {
+            exam.apply_curve(curve).expect(unpublished);
+        }
+
+        Ok(exam)
+    }
+
+    /// Runs the pipeline, writing every report it describes, and returns the
+    /// warnings of the exam and the ones found while rendering the reports.
+    pub fn execute(&self) -> Result<PipelineWarnings, PipelineError> {
+        let exam = self.exam()?;
+
+        let mut warnings = PipelineWarnings {
+            exam: exam.warnings(),
+            render: Vec::new(),
+        };
+        for output in &self.outputs {
+            let path = output.path().map(|path| self.resolve(path));
+            write_output(&exam, output, path.as_deref(), &mut warnings.render).map_err(|err| {
+                PipelineError::Output {
+                    path: path.unwrap_or_else(|| PathBuf::from("<stdout>")),
+                    err,
+                }
+            })?;
+        }
+
+        Ok(warnings)
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        self.base_dir.join(path)
+    }
+}
+
+fn write_output(
+    exam: &Exam,
+    output: &Output,
+    path: Option<&Path>,
+    warnings: &mut Vec<RenderWarning>,
+) -> io::Result<()> {
+    let mut writer: Box<dyn Write> = match path {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    match output {
+        Output::Students { sort, .. } => warnings.extend(exam.students_to(&mut writer, *sort)?),
+        Output::Summary { extended, .. } => {
+            if *extended {
+                exam.summary_extended_to(&mut writer)?;
+            } else {
+                exam.summary_to(&mut writer)?;
+            }
+        }
+        Output::Histogram { step, .. } => warnings.extend(exam.histogram_to(&mut writer, *step)?),
+        Output::Boxplot { .. } => exam.boxplot_to(&mut writer)?,
+        Output::TimeSeries { format, .. } => {
+            export_time_series(std::slice::from_ref(exam), &mut writer, *format)?
+        }
+    }
+
+    writer.flush()
+}
+
+impl PipelineWarnings {
+    /// Returns `true` if no warning was found.
+    pub fn is_empty(&self) -> bool {
+        self.exam.is_empty() && self.render.is_empty()
+    }
+}
+
+impl Output {
+    fn path(&self) -> Option<&Path> {
+        match self {
+            Output::Students { path, .. }
+            | Output::Summary { path, .. }
+            | Output::Histogram { path, .. }
+            | Output::Boxplot { path }
+            | Output::TimeSeries { path, .. } => path.as_deref(),
+        }
+    }
+}
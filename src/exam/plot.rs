@@ -1,70 +1,1093 @@
-use colored::Colorize;
+use std::fmt::{self, Write};
+use std::ops::Range;
+
+use colored::{Color, Colorize};
+use serde::Serialize;
 use term_size::dimensions_stdout;
-use termplot::{plot::Histogram, Domain, Plot, Size};
+use termplot::{plot, Domain, Plot, Size};
+
+use crate::exam::{
+    with_table_style, GroupStatistics, OutlierMethod, QuestionStatistics, RenderWarning,
+    Statistics, Student,
+};
+
+const SVG_WIDTH: f64 = 640.0;
+const SVG_HEIGHT: f64 = 400.0;
+const SVG_MARGIN: f64 = 40.0;
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const BOXPLOT_MIN_WIDTH: usize = 20;
+const BAR_CHART_MIN_WIDTH: usize = 10;
+const GROUP_COLORS: [Color; 6] = [
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Yellow,
+    Color::Green,
+    Color::Red,
+];
+
+/// Whether the grades of a histogram bucket fail, pass or reach the highest
+/// band of the exam [GradeScale](crate::exam::GradeScale), used to color the
+/// bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BucketTone {
+    /// Grades lower than the pass grade, drawn in red.
+    Fail,
+
+    /// Passing grades, drawn in green.
+    Pass,
+
+    /// Grades in the highest band of the grade scale, drawn in gold.
+    Distinction,
+}
 
-use crate::exam::Student;
+impl BucketTone {
+    // Color used to draw the bucket in the terminal
+    fn color(self) -> Color {
+        match self {
+            BucketTone::Fail => Color::Red,
+            BucketTone::Pass => Color::Green,
+            BucketTone::Distinction => Color::Yellow,
+        }
+    }
 
-pub fn histogram(students: &[Student], mut max_grade: f32, step: Option<f64>) {
-    let mut overflow = false;
+    // Color used to draw the bucket in images, as RGB
+    pub(crate) fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            BucketTone::Fail => (217, 83, 79),
+            BucketTone::Pass => (92, 184, 92),
+            BucketTone::Distinction => (212, 175, 55),
+        }
+    }
+}
 
-    let step = step.unwrap_or(1.0);
+/// Builder used to configure a [Histogram] of the exam grades.
+///
+/// A builder can be obtained with
+/// [Exam::histogram_builder](crate::exam::Exam::histogram_builder).
+#[derive(Debug, Clone)]
+pub struct HistogramBuilder<'a> {
+    students: &'a [Student],
+    max_grade: f32,
+    step: f64,
+    edges: Option<Vec<f64>>,
+    size: Option<(usize, usize)>,
+    pass_grade: f32,
+    distinction_grade: Option<f32>,
+    confidence: Option<f64>,
+}
 
-    let grades: Vec<f64> = students
-        .iter()
-        .map(|s| {
-            if max_grade == 0.0 {
-                max_grade = 0.1;
+impl<'a> HistogramBuilder<'a> {
+    pub(crate) fn new(students: &'a [Student], max_grade: f32) -> Self {
+        Self {
+            students,
+            max_grade,
+            step: 1.0,
+            edges: None,
+            size: None,
+            pass_grade: max_grade / 2.0,
+            distinction_grade: None,
+            confidence: None,
+        }
+    }
+
+    /// Sets the width of each bucket of the histogram. Defaults to 1. Widths
+    /// that are not finite numbers greater than 0 are ignored.
+    pub fn step(mut self, step: f64) -> Self {
+        if step.is_finite() && step > 0.0 {
+            self.step = step;
+        }
+        self
+    }
+
+    /// Uses buckets between the given edges instead of buckets of equal
+    /// width, e.g. `[0.0, 5.0, 7.0, 9.0, 10.0]` to match the Spanish grade
+    /// bands. The edges are sorted, and grades below the first edge or above
+    /// the last one are counted in the first or the last bucket. Less than two
+    /// edges are ignored. The x-axis of the terminal plot is labeled with the
+    /// edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 6.1),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    ///     Student::new("Irene Alcántara Campillo", 9.5),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let histogram = exam
+    ///     .histogram_builder()
+    ///     .edges([0.0, 5.0, 7.0, 9.0, 10.0])
+    ///     .build();
+    ///
+    /// let counts: Vec<usize> = histogram
+    ///     .buckets()
+    ///     .iter()
+    ///     .map(|(_, count)| *count)
+    ///     .collect();
+    ///
+    /// assert_eq!(counts, [1, 1, 1, 1]);
+    /// assert_eq!(histogram.buckets()[1].0, 5.0..7.0);
+    /// assert_eq!(histogram.step(), None);
+    /// ```
+    pub fn edges(mut self, edges: impl Into<Vec<f64>>) -> Self {
+        let mut edges = edges.into();
+        edges.sort_by(f64::total_cmp);
+        edges.dedup();
+
+        self.edges = Some(edges);
+        self
+    }
+
+    // Grades from which buckets are drawn as passing or as distinctions
+    pub(crate) fn thresholds(mut self, pass_grade: f32, distinction_grade: Option<f32>) -> Self {
+        self.pass_grade = pass_grade;
+        self.distinction_grade = distinction_grade;
+        self
+    }
+
+    /// Sets the size, in characters, of the terminal plot. If no size is set,
+    /// it is computed from the terminal dimensions each time the histogram is
+    /// rendered.
+    pub fn size(mut self, width: usize, height: usize) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Computes the Wilson score interval of the share of students in each
+    /// bucket at the given confidence level, e.g. 0.95, so the histograms of
+    /// small cohorts are not over-interpreted. The intervals are shown below
+    /// the terminal plot and as error bars in the SVG image of
+    /// [to_svg](Histogram::to_svg) and the images written by `to_file`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Exam;
+    /// use exms::exam::Student;
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ];
+    ///
+    /// let exam = Exam::new(students);
+    /// let histogram = exam.histogram_builder().step(5.0).confidence(0.95).build();
+    /// let intervals = histogram.intervals().unwrap();
+    ///
+    /// assert!(intervals[0].start < 2.0 / 3.0 && intervals[0].end > 2.0 / 3.0);
+    /// assert!(intervals[1].start > 0.0 && intervals[1].end < 0.9);
+    /// ```
+    pub fn confidence(mut self, level: f64) -> Self {
+        self.confidence = Some(level);
+        self
+    }
+
+    /// Computes the histogram buckets. A maximum grade that is not a positive
+    /// number is drawn as a maximum grade of 0.1, so there's always a bucket
+    /// to put the grades in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let mut exam = Exam::new(vec![Student::new("Joan Beltrán Peris", 4.6)]);
+    /// exam.set_max_grade(-5.0).unwrap();
+    ///
+    /// let histogram = exam.histogram_builder().build();
+    ///
+    /// assert_eq!(histogram.buckets().len(), 1);
+    /// assert_eq!(histogram.buckets()[0].1, 1);
+    /// ```
+    pub fn build(self) -> Histogram {
+        let mut truncated = Vec::new();
+        let step = self.step;
+        let max_grade = if self.max_grade.is_finite() && self.max_grade > 0.0 {
+            self.max_grade
+        } else {
+            0.1
+        };
+
+        let grades: Vec<f64> = self
+            .students
+            .iter()
+            .map(|s| {
+                // We subtract 0.01 to avoid the last grade to be in the next
+                // bucket
+                if s.grade > max_grade {
+                    truncated.push(s.name.clone());
+                    (max_grade - 0.01) as f64
+
+                // We subtract 0.01 to avoid the last grade to be in the next
+                // bucket
+                } else if s.grade == max_grade {
+                    (s.grade - 0.01) as f64
+                } else {
+                    s.grade as f64
+                }
+            })
+            .collect();
+
+        let edges = self.edges.filter(|edges| edges.len() >= 2);
+        let mut buckets: Vec<(Range<f64>, usize)> = match &edges {
+            Some(edges) => edges.windows(2).map(|edge| (edge[0]..edge[1], 0)).collect(),
+            None => {
+                let total_buckets = (max_grade as f64 / step).ceil() as usize;
+                (0..total_buckets)
+                    .map(|i| (i as f64 * step..(i + 1) as f64 * step, 0))
+                    .collect()
+            }
+        };
+
+        for grade in &grades {
+            let bucket = match edges {
+                Some(_) => buckets
+                    .iter()
+                    .rposition(|(range, _)| *grade >= range.start)
+                    .unwrap_or(0),
+                None => (grade / step).floor() as usize,
             };
-            // We subtract 0.01 to avoid the last grade to be in the next
-            // bucket
-            if s.grade > max_grade {
-                overflow = true;
-                (max_grade - 0.01) as f64
-
-            // We subtract 0.01 to avoid the last grade to be in the next
-            // bucket
-            } else if s.grade == max_grade {
-                (s.grade - 0.01) as f64
+            if let Some((_, count)) = buckets.get_mut(bucket) {
+                *count += 1;
+            }
+        }
+
+        // Buckets are classified by their lowest grade
+        let tones = buckets
+            .iter()
+            .map(|(range, _)| {
+                if self
+                    .distinction_grade
+                    .is_some_and(|grade| range.start >= grade as f64)
+                {
+                    BucketTone::Distinction
+                } else if range.start >= self.pass_grade as f64 {
+                    BucketTone::Pass
+                } else {
+                    BucketTone::Fail
+                }
+            })
+            .collect();
+
+        let intervals = self.confidence.map(|level| {
+            let z = z_score(level);
+            buckets
+                .iter()
+                .map(|(_, count)| wilson_interval(*count, grades.len(), z))
+                .collect()
+        });
+
+        Histogram {
+            grades,
+            buckets,
+            tones,
+            confidence: self.confidence,
+            intervals,
+            max_grade: max_grade as f64,
+            step: edges.is_none().then_some(step),
+            truncated,
+            size: self.size,
+        }
+    }
+}
+
+/// Histogram of the exam grades that can be rendered to different targets.
+///
+/// The [Display](fmt::Display) implementation renders the histogram as a
+/// terminal plot, followed by the number of students in each bucket colored by
+/// its [BucketTone]. Serializing it yields its buckets, their tones, maximum
+/// grade, step and truncated students, to be used by other plotting or
+/// reporting tools.
+#[derive(Debug, Clone, Serialize)]
+pub struct Histogram {
+    #[serde(skip)]
+    grades: Vec<f64>,
+    buckets: Vec<(Range<f64>, usize)>,
+    tones: Vec<BucketTone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f64>,
+    // Confidence interval of the share of students in each bucket
+    #[serde(skip_serializing_if = "Option::is_none")]
+    intervals: Option<Vec<Range<f64>>>,
+    max_grade: f64,
+    // None when the buckets don't have the same width
+    step: Option<f64>,
+    // Students whose grade was greater than the maximum grade
+    truncated: Vec<String>,
+    #[serde(skip)]
+    size: Option<(usize, usize)>,
+}
+
+impl Histogram {
+    /// Prints the histogram as a terminal plot.
+    pub fn print(&self) {
+        println!("{self}");
+    }
+
+    /// Returns the [RenderWarning]s found while computing the histogram,
+    /// e.g. grades greater than the maximum grade that had to be truncated.
+    pub fn warnings(&self) -> Vec<RenderWarning> {
+        if self.truncated.is_empty() {
+            Vec::new()
+        } else {
+            vec![RenderWarning::GradesTruncated(self.truncated.clone())]
+        }
+    }
+
+    /// Renders the histogram as an SVG image.
+    pub fn to_svg(&self) -> String {
+        let plot_width = SVG_WIDTH - 2.0 * SVG_MARGIN;
+        let plot_height = SVG_HEIGHT - 2.0 * SVG_MARGIN;
+        let baseline = SVG_HEIGHT - SVG_MARGIN;
+        let total = self.grades.len() as f64;
+        let highest_interval = self
+            .intervals
+            .iter()
+            .flatten()
+            .map(|interval| interval.end * total)
+            .fold(0.0, f64::max);
+        let max_count = (self.max_bucket_size() as f64)
+            .max(highest_interval)
+            .max(1.0);
+        let bar_width = plot_width / self.buckets.len().max(1) as f64;
+
+        let mut svg = String::new();
+
+        // Writing to a String can't fail
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{SVG_HEIGHT}" viewBox="0 0 {SVG_WIDTH} {SVG_HEIGHT}" font-family="sans-serif" font-size="12">"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle" font-size="16">Grades Histogram</text>"#,
+            SVG_WIDTH / 2.0,
+            SVG_MARGIN / 2.0
+        );
+
+        for (i, ((range, count), tone)) in self.buckets.iter().zip(&self.tones).enumerate() {
+            let height = *count as f64 / max_count * plot_height;
+            let x = SVG_MARGIN + i as f64 * bar_width;
+            let (red, green, blue) = tone.rgb();
+
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{x}" y="{}" width="{bar_width}" height="{height}" fill="rgb({red},{green},{blue})" stroke="white"><title>{}-{}: {count}</title></rect>"#,
+                baseline - height,
+                range.start,
+                range.end
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{x}" y="{}" text-anchor="middle">{}</text>"#,
+                baseline + 15.0,
+                range.start
+            );
+        }
+
+        // Error bars with the confidence interval of each bucket
+        for (i, interval) in self.intervals.iter().flatten().enumerate() {
+            let x = SVG_MARGIN + (i as f64 + 0.5) * bar_width;
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="black"/>"#,
+                baseline - interval.start * total / max_count * plot_height,
+                baseline - interval.end * total / max_count * plot_height
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            SVG_MARGIN + plot_width,
+            baseline + 15.0,
+            self.max_grade
+        );
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{SVG_MARGIN}" y1="{baseline}" x2="{}" y2="{baseline}" stroke="black"/>"#,
+            SVG_MARGIN + plot_width
+        );
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+
+    /// Returns the grade range and number of students of each bucket.
+    pub fn buckets(&self) -> &[(Range<f64>, usize)] {
+        &self.buckets
+    }
+
+    /// Returns the [BucketTone] of each bucket, in the same order as the
+    /// [buckets](Histogram::buckets).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{BucketTone, Exam, GradeScale, Student};
+    ///
+    /// let students = &[
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 9.4),
+    /// ];
+    ///
+    /// let mut exam = Exam::new(students);
+    /// exam.set_grade_scale(GradeScale::spanish()).unwrap();
+    /// let histogram = exam.histogram_data(None);
+    ///
+    /// assert_eq!(
+    ///     histogram.tones(),
+    ///     &[
+    ///         BucketTone::Fail,
+    ///         BucketTone::Pass,
+    ///         BucketTone::Pass,
+    ///         BucketTone::Distinction
+    ///     ]
+    /// );
+    /// ```
+    pub fn tones(&self) -> &[BucketTone] {
+        &self.tones
+    }
+
+    /// Returns the confidence interval of the share of students in each bucket,
+    /// from 0 to 1 and in the same order as the [buckets](Histogram::buckets),
+    /// or `None` if no [confidence](HistogramBuilder::confidence) level was
+    /// set.
+    pub fn intervals(&self) -> Option<&[Range<f64>]> {
+        self.intervals.as_deref()
+    }
+
+    /// Returns the maximum grade shown in the histogram.
+    pub fn max_grade(&self) -> f64 {
+        self.max_grade
+    }
+
+    /// Returns the width of the buckets, or `None` if they don't all have the
+    /// same width, e.g. when they match the bands of a
+    /// [GradeScale](crate::exam::GradeScale).
+    pub fn step(&self) -> Option<f64> {
+        self.step
+    }
+
+    /// Renders the histogram as a single line of block characters, one per
+    /// bucket, scaled to the largest bucket.
+    pub fn sparkline(&self) -> String {
+        let max_count = self.max_bucket_size().max(1) as f64;
+        let top_level = (SPARKLINE_LEVELS.len() - 1) as f64;
+
+        self.buckets
+            .iter()
+            .map(|(_, count)| {
+                let level = (*count as f64 / max_count * top_level).ceil() as usize;
+                SPARKLINE_LEVELS[level]
+            })
+            .collect()
+    }
+
+    fn max_bucket_size(&self) -> usize {
+        self.buckets
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (width, height) = self.size.unwrap_or_else(|| {
+            let (term_width, term_height) = dimensions_stdout().unwrap_or((80, 24));
+            (term_width - (term_width / 2), term_height)
+        });
+
+        let ranges = self
+            .buckets
+            .iter()
+            .map(|(range, _)| range.clone())
+            .collect();
+        let hist = plot::Histogram::new(self.grades.clone(), ranges);
+        let x_label = match self.step {
+            Some(step) => format!("X => [Grade Range] (step {step})"),
+            None => {
+                let edges: Vec<String> = self
+                    .buckets
+                    .iter()
+                    .map(|(range, _)| range.start)
+                    .chain(self.buckets.last().map(|(range, _)| range.end))
+                    .map(|edge| edge.to_string())
+                    .collect();
+                format!("X => [Grade Range] (edges {})", edges.join(", "))
+            }
+        };
+
+        let mut plot = Plot::default();
+        plot.set_domain(Domain(0.0..self.max_grade))
+            .set_codomain(Domain(0.0..self.max_bucket_size() as f64))
+            .set_size(Size::new(width, height))
+            .set_title("Grades Histogram")
+            .set_x_label(&x_label)
+            .set_y_label("Y => [Number of Students]")
+            .add_plot(Box::new(hist));
+
+        writeln!(f, "{}", with_table_style(&plot.to_string()))?;
+
+        // The terminal plot can't color each bar, so the counts are colored
+        write!(f, "Students per bucket:")?;
+        for ((_, count), tone) in self.buckets.iter().zip(&self.tones) {
+            write!(f, " {}", count.to_string().color(tone.color()))?;
+        }
+
+        if let Some((confidence, intervals)) = self.confidence.zip(self.intervals.as_ref()) {
+            write!(f, "\n{}% confidence intervals:", confidence * 100.0)?;
+            for (interval, tone) in intervals.iter().zip(&self.tones) {
+                let interval =
+                    format!("{:.0}-{:.0}%", interval.start * 100.0, interval.end * 100.0);
+                write!(f, " {}", interval.color(tone.color()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Wilson score interval of the share of `total` students that are in a bucket
+// with `count` students
+fn wilson_interval(count: usize, total: usize, z: f64) -> Range<f64> {
+    if total == 0 {
+        return 0.0..0.0;
+    }
+
+    let n = total as f64;
+    let p = count as f64 / n;
+    let z2 = z * z;
+
+    let denominator = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denominator;
+    let half_width = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+
+    (center - half_width).max(0.0)..(center + half_width).min(1.0)
+}
+
+// Two-sided z-score of the given confidence level, using the rational
+// approximation 26.2.23 of Abramowitz and Stegun, accurate to 4.5e-4
+fn z_score(level: f64) -> f64 {
+    let tail = (1.0 - level.clamp(0.0, 0.9999)) / 2.0;
+    let t = (-2.0 * tail.ln()).sqrt();
+
+    t - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+        / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t)
+}
+
+/// Histogram of the exam grades with a series of buckets for each group of
+/// students, see [Student::group], so the distributions of the groups can be
+/// compared in a single plot. Every series has the same buckets.
+///
+/// The [Display](fmt::Display) implementation renders the histogram for the
+/// terminal as horizontal bars, one per group in each bucket, each group in
+/// its own color. Serializing it yields its buckets, series and truncated
+/// students.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupedHistogram {
+    buckets: Vec<Range<f64>>,
+    series: Vec<GroupSeries>,
+    // Students whose grade was greater than the maximum grade
+    truncated: Vec<String>,
+    #[serde(skip)]
+    width: Option<usize>,
+}
+
+/// Number of students of a group in each bucket of a [GroupedHistogram].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GroupSeries {
+    /// Name of the group, or `None` for the students without a group.
+    pub group: Option<String>,
+
+    /// Number of students of the group in each bucket, in the same order as
+    /// the [buckets](GroupedHistogram::buckets).
+    pub counts: Vec<usize>,
+}
+
+impl GroupedHistogram {
+    // The histograms of the groups must have been built with the same buckets
+    pub(crate) fn new(groups: Vec<(Option<String>, Histogram)>) -> Self {
+        let buckets = groups.first().map_or_else(Vec::new, |(_, histogram)| {
+            histogram
+                .buckets
+                .iter()
+                .map(|(range, _)| range.clone())
+                .collect()
+        });
+
+        let mut truncated = Vec::new();
+        let series = groups
+            .into_iter()
+            .map(|(group, histogram)| {
+                truncated.extend(histogram.truncated);
+                GroupSeries {
+                    group,
+                    counts: histogram.buckets.iter().map(|(_, count)| *count).collect(),
+                }
+            })
+            .collect();
+
+        Self {
+            buckets,
+            series,
+            truncated,
+            width: None,
+        }
+    }
+
+    /// Sets the width, in characters, of the plot. If no width is set, it is
+    /// computed from the terminal width each time the plot is rendered.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Prints the histogram for the terminal.
+    pub fn print(&self) {
+        println!("{self}");
+    }
+
+    /// Returns the [RenderWarning]s found while computing the histogram,
+    /// e.g. grades greater than the maximum grade that had to be truncated.
+    pub fn warnings(&self) -> Vec<RenderWarning> {
+        if self.truncated.is_empty() {
+            Vec::new()
+        } else {
+            vec![RenderWarning::GradesTruncated(self.truncated.clone())]
+        }
+    }
+
+    /// Returns the grade range of each bucket, shared by every series.
+    pub fn buckets(&self) -> &[Range<f64>] {
+        &self.buckets
+    }
+
+    /// Returns the series of each group, sorted by group name, with the
+    /// students without a group last.
+    pub fn series(&self) -> &[GroupSeries] {
+        &self.series
+    }
+}
+
+impl fmt::Display for GroupedHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Grades Histogram by Group")?;
+
+        let ranges: Vec<String> = self
+            .buckets
+            .iter()
+            .map(|range| format!("{}-{}", range.start, range.end))
+            .collect();
+        let groups: Vec<&str> = self.series.iter().map(|s| group_label(&s.group)).collect();
+        let max_count = self
+            .series
+            .iter()
+            .flat_map(|s| s.counts.iter().copied())
+            .max()
+            .unwrap_or(0);
+
+        let range_width = ranges.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        let group_width = groups.iter().map(|g| g.chars().count()).max().unwrap_or(0);
+        let count_width = max_count.to_string().len();
+        let bar_width = chart_width(self.width)
+            .saturating_sub(range_width + group_width + count_width + 3)
+            .max(1);
+
+        for (bucket, range) in ranges.iter().enumerate() {
+            for (i, (series, group)) in self.series.iter().zip(&groups).enumerate() {
+                let label = if i == 0 { range.as_str() } else { "" };
+                let count = series.counts[bucket];
+                let bar = bar(count as f64 / max_count.max(1) as f64, bar_width);
+                let padding = " ".repeat(bar_width - bar.chars().count());
+
+                write!(
+                    f,
+                    "\n{label:>range_width$} {group:<group_width$} {}{padding} {count}",
+                    bar.color(GROUP_COLORS[i % GROUP_COLORS.len()])
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Bar chart of the pass rate of each group of students, see
+/// [Student::group], followed by the pass rate of the whole exam.
+///
+/// The [Display](fmt::Display) implementation renders the chart for the
+/// terminal as horizontal bars from 0 to 100%, the groups with a pass rate
+/// lower than the one of the exam in red and the rest in green.
+#[derive(Debug, Clone, Serialize)]
+pub struct PassRateChart {
+    groups: Vec<(Option<String>, f32)>,
+    pass_rate: f32,
+    #[serde(skip)]
+    width: Option<usize>,
+}
+
+impl PassRateChart {
+    pub(crate) fn new(groups: &[GroupStatistics], pass_rate: f32) -> Self {
+        Self {
+            groups: groups
+                .iter()
+                .map(|group| (group.group.clone(), group.statistics.pass_rate()))
+                .collect(),
+            pass_rate,
+            width: None,
+        }
+    }
+
+    /// Sets the width, in characters, of the chart. If no width is set, it is
+    /// computed from the terminal width each time the chart is rendered.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Prints the chart for the terminal.
+    pub fn print(&self) {
+        println!("{self}");
+    }
+
+    /// Returns the name and pass rate, from 0 to 100, of each group, sorted by
+    /// group name, with the students without a group last.
+    pub fn groups(&self) -> &[(Option<String>, f32)] {
+        &self.groups
+    }
+
+    /// Returns the pass rate of the whole exam, from 0 to 100.
+    pub fn pass_rate(&self) -> f32 {
+        self.pass_rate
+    }
+}
+
+impl fmt::Display for PassRateChart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pass Rate by Group")?;
+
+        let rows: Vec<(&str, f32)> = self
+            .groups
+            .iter()
+            .map(|(group, pass_rate)| (group_label(group), *pass_rate))
+            .chain([("Exam", self.pass_rate)])
+            .collect();
+        let label_width = rows
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        let bar_width = chart_width(self.width)
+            .saturating_sub(label_width + "100%".len() + 2)
+            .max(1);
+
+        for (i, (label, pass_rate)) in rows.iter().enumerate() {
+            let bar = bar(*pass_rate as f64 / 100.0, bar_width);
+            let padding = " ".repeat(bar_width - bar.chars().count());
+            let bar = if i == self.groups.len() {
+                bar.bold()
+            } else if *pass_rate < self.pass_rate {
+                bar.red()
             } else {
-                s.grade as f64
+                bar.green()
+            };
+
+            write!(f, "\n{label:<label_width$} {bar}{padding} {pass_rate}%")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Histogram of the difficulty indices of the questions of an exam, see
+/// [QuestionStatistics::difficulty], with buckets 0.1 wide from 0 to 1.
+///
+/// The [Display](fmt::Display) implementation renders the histogram for the
+/// terminal as horizontal bars, the hard questions, with an index lower than
+/// 0.3, in red, the easy ones, with an index of 0.8 or more, in yellow, and
+/// the rest in green.
+#[derive(Debug, Clone, Serialize)]
+pub struct DifficultyHistogram {
+    buckets: Vec<(Range<f64>, usize)>,
+    // Questions without a difficulty index
+    unknown: Vec<String>,
+    #[serde(skip)]
+    width: Option<usize>,
+}
+
+impl DifficultyHistogram {
+    pub(crate) fn new(questions: &[QuestionStatistics]) -> Self {
+        let mut buckets: Vec<(Range<f64>, usize)> = (0..10)
+            .map(|i| (i as f64 / 10.0..(i + 1) as f64 / 10.0, 0))
+            .collect();
+        let mut unknown = Vec::new();
+
+        for question in questions {
+            match question.difficulty {
+                // Scores greater than the maximum score make the index
+                // greater than 1, so it's counted as the easiest bucket
+                Some(difficulty) if !difficulty.is_nan() => {
+                    let bucket = (difficulty.clamp(0.0, 1.0) * 10.0).floor() as usize;
+                    buckets[bucket.min(9)].1 += 1;
+                }
+                _ => unknown.push(question.name.clone()),
             }
-        })
-        .collect();
+        }
 
-    let buckets_range = (0..(max_grade as f64 / step).ceil() as usize)
-        .map(|i| i as f64 * step..(i + 1) as f64 * step)
-        .collect();
+        Self {
+            buckets,
+            unknown,
+            width: None,
+        }
+    }
+
+    /// Sets the width, in characters, of the plot. If no width is set, it is
+    /// computed from the terminal width each time the plot is rendered.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Prints the histogram for the terminal.
+    pub fn print(&self) {
+        println!("{self}");
+    }
+
+    /// Returns the range of difficulty indices of each bucket with the number
+    /// of questions in it.
+    pub fn buckets(&self) -> &[(Range<f64>, usize)] {
+        &self.buckets
+    }
+
+    /// Returns the names of the questions left out because their difficulty
+    /// index is not known, e.g. because their maximum score is not.
+    pub fn unknown(&self) -> &[String] {
+        &self.unknown
+    }
+}
+
+impl fmt::Display for DifficultyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Question Difficulty Histogram")?;
+
+        let max_count = self.buckets.iter().map(|(_, count)| *count).max();
+        let max_count = max_count.unwrap_or(0);
+        let count_width = max_count.to_string().len();
+        let bar_width = chart_width(self.width)
+            .saturating_sub("0.0-0.1".len() + count_width + 2)
+            .max(1);
+
+        for (range, count) in &self.buckets {
+            let bar = bar(*count as f64 / max_count.max(1) as f64, bar_width);
+            let padding = " ".repeat(bar_width - bar.chars().count());
+            let color = if range.start < 0.3 {
+                Color::Red
+            } else if range.start < 0.8 {
+                Color::Green
+            } else {
+                Color::Yellow
+            };
+
+            write!(
+                f,
+                "\n{:.1}-{:.1} {}{padding} {count}",
+                range.start,
+                range.end,
+                bar.color(color)
+            )?;
+        }
+
+        write!(f, "\n0 is the hardest and 1 the easiest")?;
+        if !self.unknown.is_empty() {
+            write!(
+                f,
+                "\nQuestions without a known maximum score: {}",
+                self.unknown.join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Name shown for the group in the charts
+fn group_label(group: &Option<String>) -> &str {
+    group.as_deref().unwrap_or("Without group")
+}
+
+// Width of a chart, the given one or most of the terminal width
+fn chart_width(width: Option<usize>) -> usize {
+    width
+        .unwrap_or_else(|| dimensions_stdout().map_or(80, |(width, _)| width - width / 4))
+        .max(BAR_CHART_MIN_WIDTH)
+}
+
+// Bar filling the given share, from 0 to 1, of `width` characters. Bars of a
+// share greater than 0 are at least one character long.
+fn bar(share: f64, width: usize) -> String {
+    let length = (share * width as f64).round() as usize;
+    let length = if share > 0.0 { length.max(1) } else { 0 };
+    "█".repeat(length.min(width))
+}
 
-    let hist = Histogram::new(grades.clone(), buckets_range);
+/// Box-and-whisker plot of the exam grades.
+///
+/// The box spans from the first to the third quartile, with a line at the
+/// median. The whiskers reach the lowest and highest grades that are not
+/// outliers according to [OutlierMethod::Iqr] with a factor of 1.5, and
+/// outliers are drawn as dots beyond them.
+///
+/// The [Display](fmt::Display) implementation renders the plot for the
+/// terminal.
+#[derive(Debug, Clone)]
+pub struct BoxPlot {
+    lower_whisker: f32,
+    first_quartile: f32,
+    median: f32,
+    third_quartile: f32,
+    upper_whisker: f32,
+    outliers: Vec<f32>,
+    max_grade: f32,
+    width: Option<usize>,
+}
 
-    let mut buckets = vec![0; (max_grade as f64 / step).ceil() as usize];
+impl BoxPlot {
+    pub(crate) fn new(students: &[Student], statistics: &Statistics, max_grade: f32) -> Self {
+        let (lowest, highest) = OutlierMethod::Iqr(1.5).bounds(statistics);
+        let (inliers, outliers): (Vec<f32>, Vec<f32>) = students
+            .iter()
+            .map(|s| s.grade)
+            .partition(|&grade| grade >= lowest && grade <= highest);
 
-    let mut max_bucket_size = 0;
-    for grade in &grades {
-        let bucket = (grade / step).floor() as usize;
-        buckets[bucket] += 1;
-        if buckets[bucket] > max_bucket_size {
-            max_bucket_size = buckets[bucket];
+        Self {
+            lower_whisker: inliers.iter().copied().fold(f32::INFINITY, f32::min),
+            first_quartile: statistics.first_quartile(),
+            median: statistics.median(),
+            third_quartile: statistics.third_quartile(),
+            upper_whisker: inliers.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            outliers,
+            max_grade: if max_grade == 0.0 { 0.1 } else { max_grade },
+            width: None,
         }
     }
 
-    let (term_width, term_height) = dimensions_stdout().unwrap_or((80, 24));
+    /// Sets the width, in characters, of the plot. If no width is set, it is
+    /// computed from the terminal width each time the plot is rendered.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Prints the box plot for the terminal.
+    pub fn print(&self) {
+        println!("{self}");
+    }
+
+    /// Returns the grades of the lower whisker, the first quartile, the
+    /// median, the third quartile and the upper whisker, in that order.
+    pub fn five_numbers(&self) -> [f32; 5] {
+        [
+            self.lower_whisker,
+            self.first_quartile,
+            self.median,
+            self.third_quartile,
+            self.upper_whisker,
+        ]
+    }
+
+    /// Returns the grades drawn as outliers.
+    pub fn outliers(&self) -> &[f32] {
+        &self.outliers
+    }
+
+    /// Returns the maximum grade shown in the box plot.
+    pub fn max_grade(&self) -> f32 {
+        self.max_grade
+    }
+}
+
+impl fmt::Display for BoxPlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .width
+            .unwrap_or_else(|| dimensions_stdout().map_or(80, |(width, _)| width - width / 4))
+            .max(BOXPLOT_MIN_WIDTH);
+
+        writeln!(f, "Grades Box Plot")?;
+
+        // Every grade has been an outlier, or there are no grades at all
+        if self.lower_whisker > self.upper_whisker {
+            return write!(f, "No grades to plot");
+        }
+
+        let column = |grade: f32| {
+            let position = (grade / self.max_grade).clamp(0.0, 1.0) * (width - 1) as f32;
+            position.round() as usize
+        };
+        let [lower, first, median, third, upper] = self.five_numbers().map(column);
+
+        let mut top = vec![' '; width];
+        let mut middle = vec![' '; width];
+        let mut bottom = vec![' '; width];
 
-    let mut plot = Plot::default();
-    plot.set_domain(Domain(0.0..max_grade as f64))
-        .set_codomain(Domain(0.0..max_bucket_size as f64))
-        .set_size(Size::new(term_width - (term_width / 2), term_height))
-        .set_title("Grades Histogram")
-        .set_x_label(&format!("X => [Grade Range] (step {})", step))
-        .set_y_label("Y => [Number of Students]")
-        .add_plot(Box::new(hist));
+        middle[lower..=upper].fill('─');
+        middle[lower] = '├';
+        middle[upper] = '┤';
 
-    println!("{plot}");
+        top[first..=third].fill('─');
+        bottom[first..=third].fill('─');
+        middle[first..=third].fill(' ');
+        top[first] = '┌';
+        top[third] = '┐';
+        bottom[first] = '└';
+        bottom[third] = '┘';
+        middle[first] = if lower < first { '┤' } else { '│' };
+        middle[third] = if upper > third { '├' } else { '│' };
+        top[median] = '┬';
+        middle[median] = '│';
+        bottom[median] = '┴';
+
+        for &outlier in &self.outliers {
+            middle[column(outlier)] = '•';
+        }
+
+        let max_label = self.max_grade.to_string();
+        let axis = format!(
+            "0{}{max_label}",
+            " ".repeat(width.saturating_sub(max_label.len() + 1))
+        );
+
+        for line in [top, middle, bottom] {
+            let line: String = line.into_iter().collect();
+            writeln!(f, "{}", with_table_style(line.trim_end()))?;
+        }
+        writeln!(f, "{axis}")?;
+        write!(
+            f,
+            "Min {}  Q1 {}  Median {}  Q3 {}  Max {}",
+            self.lower_whisker,
+            self.first_quartile,
+            self.median,
+            self.third_quartile,
+            self.upper_whisker
+        )?;
+        if !self.outliers.is_empty() {
+            write!(f, "  Outliers {}", self.outliers.len())?;
+        }
 
-    if overflow {
-        let warning = "Some grades were truncated to fit in the histogram as \
-                       they were greater than the maximum grade.\n\
-                       This does not affect other statistics.";
-        println!("{}\n", warning.yellow());
+        Ok(())
     }
 }
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnonymousError;
+use crate::exam::digest::{hmac_sha256, to_hex};
+use crate::exam::export::{csv_field, ExportFormat};
+use crate::exam::student::serialize_rank;
+use crate::exam::{normalized_name, Exam, Language, Statistics, Student};
+
+/// Options of the anonymous ranking of an exam, see
+/// [Exam::anonymous_ranking](crate::exam::Exam::anonymous_ranking).
+///
+/// # Examples
+///
+/// ```
+/// use exms::error::AnonymousError;
+/// use exms::exam::AnonymousOptions;
+///
+/// let options = AnonymousOptions::new("econometrics-2024")
+///     .unwrap()
+///     .include_grade();
+/// assert!(options.include_grade);
+///
+/// let error = AnonymousOptions::new("").unwrap_err();
+/// assert!(matches!(error, AnonymousError::EmptySecret));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnonymousOptions {
+    // Key of the HMAC of the names of the students
+    secret: String,
+
+    /// Whether to include the exact grade of each student.
+    pub include_grade: bool,
+
+    /// Language of the pass and fail labels given to the students when the
+    /// exam has no [GradeScale](crate::exam::GradeScale).
+    pub language: Language,
+}
+
+impl AnonymousOptions {
+    /// Creates the options of a ranking without grades, whose IDs are
+    /// computed with the given secret. Without it anyone could compute the ID
+    /// of a student from their name, so keep it private. Using the same secret
+    /// for every exam of a course keeps the ID of each student.
+    ///
+    /// Returns [AnonymousError::EmptySecret] if the secret is empty.
+    pub fn new(secret: impl Into<String>) -> Result<Self, AnonymousError> {
+        let secret = secret.into();
+        if secret.is_empty() {
+            return Err(AnonymousError::EmptySecret);
+        }
+
+        Ok(Self {
+            secret,
+            include_grade: false,
+            language: Language::English,
+        })
+    }
+
+    /// Makes the ranking include the exact grade of each student as well.
+    pub fn include_grade(mut self) -> Self {
+        self.include_grade = true;
+        self
+    }
+
+    /// Sets the language of the pass and fail labels, English by default.
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+}
+
+/// Entry of the anonymous ranking of an exam.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnonymousEntry {
+    /// Rank of the student, see [Student::rank].
+    #[serde(serialize_with = "serialize_rank")]
+    pub rank: Option<f32>,
+
+    /// Anonymized ID of the student.
+    pub id: String,
+
+    /// Label of the band of the grade in the exam [GradeScale], or "Pass" or
+    /// "Fail" in the language of the [AnonymousOptions] if it has none.
+    ///
+    /// [GradeScale]: crate::exam::GradeScale
+    pub band: String,
+
+    /// Grade of the student, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grade: Option<f32>,
+}
+
+// Ranks and percentiles of the students at the time they were pinned, by
+// normalized name, so recomputing the statistics doesn't change them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PinnedStandings {
+    standings: HashMap<String, (Option<f32>, Option<f32>)>,
+    highest_rank: f32,
+}
+
+impl PinnedStandings {
+    pub fn new(students: &[Student], statistics: &Statistics) -> Self {
+        let standings = students
+            .iter()
+            .map(|s| (normalized_name(&s.name), (s.rank, s.percentile)))
+            .collect();
+
+        Self {
+            standings,
+            highest_rank: statistics.highest_rank,
+        }
+    }
+
+    // Students that were not in the exam when the standings were pinned have
+    // no rank or percentile
+    pub fn apply(&self, students: &mut [Student], statistics: &mut Statistics) {
+        for student in students {
+            let (rank, percentile) = self
+                .standings
+                .get(&normalized_name(&student.name))
+                .copied()
+                .unwrap_or_default();
+
+            student.rank = rank;
+            student.percentile = percentile;
+        }
+
+        statistics.highest_rank = self.highest_rank;
+    }
+}
+
+// Entries of the anonymous ranking, sorted by rank and then by ID so the order
+// of the students with the same grade reveals nothing about them.
+pub fn anonymous_ranking(
+    exam: &Exam,
+    options: &AnonymousOptions,
+) -> Result<Vec<AnonymousEntry>, AnonymousError> {
+    let pass_grade = exam.pass_grade();
+    let labels = options.language.labels();
+
+    // Names by ID, to make sure no two students share one
+    let mut names: HashMap<String, &str> = HashMap::with_capacity(exam.students.len());
+    let mut entries = Vec::with_capacity(exam.students.len());
+
+    for student in &exam.students {
+        let id = anonymous_id(&options.secret, &student.name);
+        if let Some(first) = names.insert(id.clone(), &student.name) {
+            return Err(AnonymousError::DuplicateId {
+                id,
+                first: first.to_string(),
+                second: student.name.clone(),
+            });
+        }
+
+        let band = exam
+            .grade_scale
+            .as_ref()
+            .and_then(|scale| scale.band(student.grade))
+            .map(|band| band.label.clone());
+
+        entries.push(AnonymousEntry {
+            rank: student.rank,
+            id,
+            band: band.unwrap_or_else(|| {
+                if student.grade >= pass_grade {
+                    labels.pass.to_string()
+                } else {
+                    labels.fail.to_string()
+                }
+            }),
+            grade: options.include_grade.then_some(student.grade),
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        let (rank_a, rank_b) = (a.rank.unwrap_or(f32::MAX), b.rank.unwrap_or(f32::MAX));
+        rank_a.total_cmp(&rank_b).then_with(|| a.id.cmp(&b.id))
+    });
+
+    Ok(entries)
+}
+
+pub fn write_anonymous_ranking<W: Write>(
+    entries: &[AnonymousEntry],
+    mut writer: W,
+    format: ExportFormat,
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Json => serde_json::to_writer_pretty(&mut writer, entries)?,
+        ExportFormat::Csv => {
+            let with_grades = entries.iter().any(|entry| entry.grade.is_some());
+
+            write!(writer, "rank,id,band")?;
+            if with_grades {
+                write!(writer, ",grade")?;
+            }
+            writeln!(writer)?;
+
+            for entry in entries {
+                write!(
+                    writer,
+                    "{},{},{}",
+                    entry.rank.map(|rank| rank.to_string()).unwrap_or_default(),
+                    entry.id,
+                    csv_field(&entry.band)
+                )?;
+                if let Some(grade) = entry.grade {
+                    write!(writer, ",{grade}")?;
+                }
+                writeln!(writer)?;
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+// First 16 hexadecimal digits (64 bits) of the HMAC-SHA256 of the name with
+// the secret as key, ignoring case, accents and surrounding whitespace in the
+// name
+fn anonymous_id(secret: &str, name: &str) -> String {
+    let digest = hmac_sha256(secret.as_bytes(), normalized_name(name).as_bytes());
+
+    to_hex(&digest[..8]).to_uppercase()
+}
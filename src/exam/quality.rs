@@ -0,0 +1,192 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use colored::Colorize;
+
+use crate::exam::{Exam, ExamWarning, Roster};
+
+/// How much an issue of the data of an exam affects its trustworthiness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+/// Problem found in the data of an exam, see
+/// [Exam::data_quality](crate::exam::Exam::data_quality).
+#[derive(Debug, Clone, PartialEq)]
+pub enum QualityIssue {
+    /// Some students appear more than once, as reported by
+    /// [Exam::warnings](crate::exam::Exam::warnings). Contains the names of
+    /// the repeated students.
+    Duplicates(Vec<String>),
+
+    /// Some grades are negative or greater than the maximum grade, as reported
+    /// by [Exam::validate](crate::exam::Exam::validate). Contains the names of
+    /// the affected students.
+    OutOfRange(Vec<String>),
+
+    /// Some students are not in the roster. Contains their names.
+    NotEnrolled(Vec<String>),
+
+    /// Some enrolled students don't appear in the exam, which is usually
+    /// because they didn't take it. Contains their names.
+    Missing(Vec<String>),
+}
+
+/// Summary of the issues found in the data of an exam, see
+/// [Exam::data_quality](crate::exam::Exam::data_quality).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityScore {
+    score: f32,
+    issues: Vec<QualityIssue>,
+}
+
+impl QualityIssue {
+    /// Returns the severity of the issue.
+    pub fn severity(&self) -> Severity {
+        match self {
+            QualityIssue::Duplicates(_) | QualityIssue::OutOfRange(_) => Severity::High,
+            QualityIssue::NotEnrolled(_) => Severity::Medium,
+            QualityIssue::Missing(_) => Severity::Low,
+        }
+    }
+
+    /// Returns the names of the students affected by the issue.
+    pub fn students(&self) -> &[String] {
+        match self {
+            QualityIssue::Duplicates(students)
+            | QualityIssue::OutOfRange(students)
+            | QualityIssue::NotEnrolled(students)
+            | QualityIssue::Missing(students) => students,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            QualityIssue::Duplicates(_) => "Students that appear more than once",
+            QualityIssue::OutOfRange(_) => "Grades out of the range of the exam",
+            QualityIssue::NotEnrolled(_) => "Students not in the roster",
+            QualityIssue::Missing(_) => "Enrolled students missing from the exam",
+        }
+    }
+
+    // Share of the score lost for each affected student
+    fn weight(&self) -> f32 {
+        match self.severity() {
+            Severity::High => 1.0,
+            Severity::Medium => 0.5,
+            Severity::Low => 0.25,
+        }
+    }
+}
+
+impl QualityScore {
+    /// Returns the score, from 0 to 100. An exam without issues scores 100.
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// Returns the issues found, from the most to the least severe.
+    pub fn issues(&self) -> &[QualityIssue] {
+        &self.issues
+    }
+
+    /// Returns `true` if no issue of the given severity or higher was found.
+    pub fn is_clean(&self, severity: Severity) -> bool {
+        self.issues.iter().all(|issue| issue.severity() < severity)
+    }
+}
+
+impl fmt::Display for QualityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity() {
+            Severity::High => "HIGH".red(),
+            Severity::Medium => "MEDIUM".yellow(),
+            Severity::Low => "LOW".normal(),
+        };
+
+        write!(
+            f,
+            "[{severity}] {}: {}",
+            self.description(),
+            self.students().join(", ").yellow()
+        )
+    }
+}
+
+impl fmt::Display for QualityScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let score = format!("{:.0}/100", self.score);
+        let score = if self.is_clean(Severity::Medium) {
+            score.green()
+        } else if self.is_clean(Severity::High) {
+            score.yellow()
+        } else {
+            score.red()
+        };
+
+        write!(f, "Data quality: {score}")?;
+        for issue in &self.issues {
+            write!(f, "\n{issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Each affected student takes its share of the score, weighted by the
+// severity of the issue. The issues are the ones reported by the warnings of
+// the exam, its validation and the roster report, so they are never checked
+// differently.
+pub fn data_quality(exam: &Exam, roster: Option<&Roster>) -> QualityScore {
+    let mut issues = Vec::new();
+
+    let duplicates: Vec<String> = exam
+        .warnings()
+        .into_iter()
+        .filter_map(|warning| match warning {
+            ExamWarning::DuplicateStudent(student) => Some(student),
+            _ => None,
+        })
+        .collect();
+    if !duplicates.is_empty() {
+        issues.push(QualityIssue::Duplicates(duplicates));
+    }
+
+    let out_of_range: Vec<String> = exam
+        .validate()
+        .into_iter()
+        .filter_map(|warning| match warning {
+            ExamWarning::GradeOutOfRange { student, .. } => Some(student),
+            _ => None,
+        })
+        .collect();
+    if !out_of_range.is_empty() {
+        issues.push(QualityIssue::OutOfRange(out_of_range));
+    }
+
+    if let Some(roster) = roster {
+        let report = exam.check_against_roster(roster);
+        if !report.not_enrolled.is_empty() {
+            issues.push(QualityIssue::NotEnrolled(report.not_enrolled));
+        }
+        if !report.missing.is_empty() {
+            issues.push(QualityIssue::Missing(report.missing));
+        }
+    }
+
+    issues.sort_by_key(|issue| Reverse(issue.severity()));
+
+    let total = exam.students.len().max(1) as f32;
+    let penalty: f32 = issues
+        .iter()
+        .map(|issue| issue.weight() * issue.students().len() as f32 / total)
+        .sum();
+
+    QualityScore {
+        score: (100.0 * (1.0 - penalty)).max(0.0),
+        issues,
+    }
+}
@@ -0,0 +1,203 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::exam::Student;
+
+/// Statistics of the scores obtained in a single question of an exam.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestionStatistics {
+    /// Name of the question.
+    pub name: String,
+
+    /// Number of students with a score in the question.
+    pub answered: u32,
+
+    /// Mean of the scores obtained in the question.
+    pub mean: f32,
+
+    /// Lowest score obtained in the question.
+    #[serde(default)]
+    pub min: f32,
+
+    /// Median of the scores obtained in the question.
+    #[serde(default)]
+    pub median: f32,
+
+    /// Highest score obtained in the question.
+    #[serde(default)]
+    pub max: f32,
+
+    /// Maximum achievable score in the question, if known.
+    pub max_score: Option<f32>,
+
+    /// Percentage of students with at least half the maximum score of the
+    /// question, ranging from 0 to 100. Only known if the maximum score is
+    /// and someone answered the question.
+    pub pass_rate: Option<f32>,
+
+    /// Difficulty index of the question: the mean score as a share of the
+    /// maximum score, ranging from 0 for the hardest questions to 1 for the
+    /// easiest ones. Only known if the maximum score is and someone answered
+    /// the question.
+    #[serde(default)]
+    pub difficulty: Option<f32>,
+
+    /// Discrimination index of the question: the correlation between the
+    /// scores in the question and the rest of the grade of the students that
+    /// answered it, ranging from -1 to 1. Questions that tell good students
+    /// apart from the rest are closer to 1. Only known if at least two
+    /// students answered the question and neither their scores nor the rest
+    /// of their grades are all the same.
+    #[serde(default)]
+    pub discrimination: Option<f32>,
+
+    /// Percentage of the students of the exam without a score in the
+    /// question, ranging from 0 to 100.
+    #[serde(default)]
+    pub blank_rate: f32,
+}
+
+/// Reason why a question should be reviewed before trusting the item analysis
+/// of an exam, see [QuestionStatistics::flag].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionFlag {
+    /// Every student obtained the same score, so the question doesn't tell
+    /// students apart and distorts reliability coefficients.
+    ZeroVariance,
+
+    /// Nobody has a score in the question, although its maximum score is
+    /// known.
+    Unattempted,
+}
+
+impl QuestionStatistics {
+    /// Returns the reason why the question should be reviewed, or `None` if
+    /// nothing looks wrong with it.
+    pub fn flag(&self) -> Option<QuestionFlag> {
+        if self.answered == 0 {
+            Some(QuestionFlag::Unattempted)
+        } else if self.min == self.max {
+            Some(QuestionFlag::ZeroVariance)
+        } else {
+            None
+        }
+    }
+}
+
+impl QuestionFlag {
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            QuestionFlag::ZeroVariance => "Zero variance",
+            QuestionFlag::Unattempted => "Not attempted",
+        }
+    }
+}
+
+// Statistics of every question found in the students, in the order the
+// questions first appear, followed by the questions with a known maximum score
+// that nobody answered.
+pub fn question_statistics(
+    students: &[Student],
+    max_scores: &IndexMap<String, f32>,
+) -> Vec<QuestionStatistics> {
+    // Score of each student in the question along with the rest of their grade
+    let mut answers: IndexMap<&str, Vec<(f32, f32)>> = IndexMap::new();
+    for student in students {
+        for (question, &score) in &student.questions {
+            answers
+                .entry(question)
+                .or_default()
+                .push((score, student.grade - score));
+        }
+    }
+    for question in max_scores.keys() {
+        answers.entry(question).or_default();
+    }
+
+    answers
+        .into_iter()
+        .map(|(question, answers)| {
+            let discrimination = correlation(&answers);
+            let mut scores: Vec<f32> = answers.into_iter().map(|(score, _)| score).collect();
+            scores.sort_by(f32::total_cmp);
+
+            let answered = scores.len() as u32;
+            let max_score = max_scores.get(question).copied();
+            let blank_rate = if students.is_empty() {
+                0.0
+            } else {
+                (students.len() - scores.len()) as f32 / students.len() as f32 * 100.0
+            };
+            if answered == 0 {
+                return QuestionStatistics {
+                    name: question.to_string(),
+                    answered,
+                    mean: 0.0,
+                    min: 0.0,
+                    median: 0.0,
+                    max: 0.0,
+                    max_score,
+                    pass_rate: None,
+                    difficulty: None,
+                    discrimination: None,
+                    blank_rate,
+                };
+            }
+
+            let mean = scores.iter().sum::<f32>() / answered as f32;
+            let middle = scores.len() / 2;
+            let median = if scores.len().is_multiple_of(2) {
+                (scores[middle - 1] + scores[middle]) / 2.0
+            } else {
+                scores[middle]
+            };
+            let pass_rate = max_score.map(|max_score| {
+                let passed = scores.iter().filter(|&&s| s >= max_score / 2.0).count();
+                passed as f32 / answered as f32 * 100.0
+            });
+            let difficulty = max_score
+                .filter(|&max_score| max_score > 0.0)
+                .map(|max_score| mean / max_score);
+
+            QuestionStatistics {
+                name: question.to_string(),
+                answered,
+                mean,
+                min: scores[0],
+                median,
+                max: scores[scores.len() - 1],
+                max_score,
+                pass_rate,
+                difficulty,
+                discrimination,
+                blank_rate,
+            }
+        })
+        .collect()
+}
+
+// Pearson correlation coefficient of the pairs, or `None` if there are less
+// than two of them or any of their components doesn't vary
+fn correlation(pairs: &[(f32, f32)]) -> Option<f32> {
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|&(x, _)| x as f64).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|&(_, y)| y as f64).sum::<f64>() / n;
+
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for &(x, y) in pairs {
+        let (dx, dy) = (x as f64 - mean_x, y as f64 - mean_y);
+        covariance += dx * dy;
+        variance_x += dx * dx;
+        variance_y += dy * dy;
+    }
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+
+    Some((covariance / (variance_x * variance_y).sqrt()) as f32)
+}
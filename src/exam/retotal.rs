@@ -0,0 +1,131 @@
+use indexmap::map::IndexMap;
+use serde::Serialize;
+
+use crate::exam::Student;
+
+/// How each question score is rounded before adding up the grade of a
+/// student, see [RetotalOptions].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScoreRounding {
+    /// Scores are added up as they are.
+    #[default]
+    None,
+
+    /// Scores are rounded to the nearest multiple of the given step, e.g.
+    /// 0.25 to round to quarter points.
+    Nearest(f32),
+
+    /// Scores are rounded down to a multiple of the given step.
+    Down(f32),
+
+    /// Scores are rounded up to a multiple of the given step.
+    Up(f32),
+}
+
+/// Options used to compute the grades of the students again from their
+/// question scores, see [Exam::retotal](crate::exam::Exam::retotal).
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{RetotalOptions, ScoreRounding};
+///
+/// let options = RetotalOptions {
+///     weights: [("Q2".to_string(), 2.0)].into_iter().collect(),
+///     rounding: ScoreRounding::Nearest(0.5),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(options.tolerance, 0.001);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetotalOptions {
+    /// Weight each question score is multiplied by, by question name.
+    /// Questions without a weight count once.
+    pub weights: IndexMap<String, f32>,
+
+    /// How each weighted question score is rounded before adding them up.
+    pub rounding: ScoreRounding,
+
+    /// Largest difference between the grade of a student and the total of
+    /// their scores that is not reported as a discrepancy. Defaults to 0.001,
+    /// so the rounding errors of adding up the scores are not reported.
+    pub tolerance: f32,
+}
+
+/// Student whose grade doesn't match the total of their question scores, see
+/// [Exam::grade_discrepancies](crate::exam::Exam::grade_discrepancies).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GradeDiscrepancy {
+    /// Name of the student.
+    pub name: String,
+
+    /// Grade of the student before computing it again.
+    pub grade: f32,
+
+    /// Total of the question scores of the student.
+    pub total: f32,
+}
+
+impl Default for RetotalOptions {
+    fn default() -> Self {
+        Self {
+            weights: IndexMap::new(),
+            rounding: ScoreRounding::None,
+            tolerance: 0.001,
+        }
+    }
+}
+
+impl ScoreRounding {
+    // Steps that are not finite or positive leave the score as it is
+    fn round(self, score: f32) -> f32 {
+        let (step, round): (f32, fn(f32) -> f32) = match self {
+            ScoreRounding::None => return score,
+            ScoreRounding::Nearest(step) => (step, f32::round),
+            ScoreRounding::Down(step) => (step, f32::floor),
+            ScoreRounding::Up(step) => (step, f32::ceil),
+        };
+
+        if step.is_finite() && step > 0.0 {
+            round(score / step) * step
+        } else {
+            score
+        }
+    }
+}
+
+// Total of the weighted and rounded question scores of the student, or `None`
+// if they have no question scores
+pub fn total(student: &Student, options: &RetotalOptions) -> Option<f32> {
+    if student.questions.is_empty() {
+        return None;
+    }
+
+    let total = student
+        .questions
+        .iter()
+        .map(|(question, score)| {
+            let weight = options.weights.get(question).copied().unwrap_or(1.0);
+            options.rounding.round(score * weight)
+        })
+        .sum();
+
+    Some(total)
+}
+
+// Students with question scores whose grade differs from their total by more
+// than the tolerance, in the same order as the students
+pub fn discrepancies(students: &[Student], options: &RetotalOptions) -> Vec<GradeDiscrepancy> {
+    students
+        .iter()
+        .filter_map(|student| {
+            let total = total(student, options)?;
+            ((student.grade - total).abs() > options.tolerance).then(|| GradeDiscrepancy {
+                name: student.name.clone(),
+                grade: student.grade,
+                total,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::{ParseError, WithPath};
+use crate::exam::Student;
+
+/// List of the students enrolled in a course.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roster {
+    names: Vec<String>,
+}
+
+/// Result of checking the students of an exam against a [Roster].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RosterReport {
+    /// Enrolled students that don't appear in the exam.
+    pub missing: Vec<String>,
+
+    /// Students that appear in the exam but are not enrolled.
+    pub not_enrolled: Vec<String>,
+}
+
+impl Roster {
+    /// Creates a new roster from a list of student names.
+    pub fn new<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Creates a new roster from a text file containing one student name per
+    /// line. Empty lines and lines starting with `#` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use exms::error::ParseError;
+    /// use exms::exam::Roster;
+    ///
+    /// fn main() -> Result<(), ParseError> {
+    ///     let roster = Roster::from_file("roster.txt")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).with_path(path)?;
+
+        let names = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        Ok(Self::new(names))
+    }
+
+    /// Returns the names of the enrolled students.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub(crate) fn check(&self, students: &[Student]) -> RosterReport {
+        let missing = self
+            .names
+            .iter()
+            .filter(|name| !students.iter().any(|s| same_name(&s.name, name)))
+            .cloned()
+            .collect();
+
+        let not_enrolled = students
+            .iter()
+            .filter(|s| !self.names.iter().any(|name| same_name(&s.name, name)))
+            .map(|s| s.name.clone())
+            .collect();
+
+        RosterReport {
+            missing,
+            not_enrolled,
+        }
+    }
+}
+
+impl RosterReport {
+    /// Returns `true` if every enrolled student appears in the exam and every
+    /// student in the exam is enrolled.
+    pub fn is_consistent(&self) -> bool {
+        self.missing.is_empty() && self.not_enrolled.is_empty()
+    }
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
@@ -0,0 +1,172 @@
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+
+use crate::exam::{Statistics, Student};
+
+/// Rule styling the rows of the students table of the students that meet its
+/// condition, e.g. painting in bold red the students that failed, see
+/// [Exam::set_format_rules](crate::exam::Exam::set_format_rules).
+///
+/// Markdown can't be colored, so in the students table of the
+/// [Markdown report](crate::exam::Exam::to_markdown) the names of the
+/// students are marked with an emoji of the color of the rule instead, e.g. 🔴
+/// for red. HTML outputs can use the [CSS color](RuleColor::hex) of the rule.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::{FormatRule, RuleColor, RuleCondition};
+///
+/// let failed = FormatRule::new(RuleCondition::GradeBelow(4.0), RuleColor::Red).bold();
+/// let podium = FormatRule::new(RuleCondition::RankAtMost(3.0), RuleColor::Gold);
+///
+/// assert!(failed.bold);
+/// assert_eq!(podium.color, Some(RuleColor::Gold));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormatRule {
+    /// Condition a student has to meet for their row to be styled.
+    pub condition: RuleCondition,
+
+    /// Color of the row, or `None` to keep the default one.
+    pub color: Option<RuleColor>,
+
+    /// Whether the row is written in bold.
+    #[serde(default)]
+    pub bold: bool,
+}
+
+/// Condition of a [FormatRule] on a student.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// The grade is lower than the given one.
+    GradeBelow(f32),
+
+    /// The grade is greater than or equal to the given one.
+    GradeAtLeast(f32),
+
+    /// The rank is lower than or equal to the given one, e.g. 3 for the first
+    /// three students.
+    RankAtMost(f32),
+
+    /// The percentile is greater than or equal to the given one.
+    PercentileAtLeast(f32),
+
+    /// The grade is greater than or equal to the pass grade of the exam.
+    Passed,
+
+    /// The grade is lower than the pass grade of the exam.
+    Failed,
+}
+
+/// Color of the rows styled by a [FormatRule].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gold,
+}
+
+impl FormatRule {
+    /// Creates a rule painting the rows of the students that meet the
+    /// condition in the given color.
+    pub fn new(condition: RuleCondition, color: RuleColor) -> Self {
+        Self {
+            condition,
+            color: Some(color),
+            bold: false,
+        }
+    }
+
+    /// Makes the rule write the rows in bold as well.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub(crate) fn matches(&self, student: &Student, statistics: &Statistics) -> bool {
+        match self.condition {
+            RuleCondition::GradeBelow(grade) => student.grade < grade,
+            RuleCondition::GradeAtLeast(grade) => student.grade >= grade,
+            RuleCondition::RankAtMost(rank) => student.rank.is_some_and(|r| r <= rank),
+            RuleCondition::PercentileAtLeast(percentile) => {
+                student.percentile.is_some_and(|p| p >= percentile)
+            }
+            RuleCondition::Passed => student.grade >= statistics.pass_grade,
+            RuleCondition::Failed => student.grade < statistics.pass_grade,
+        }
+    }
+
+    pub(crate) fn style(&self, text: &str) -> ColoredString {
+        let mut styled = text.normal();
+        if let Some(color) = self.color {
+            styled = styled.color(color.terminal());
+        }
+        if self.bold {
+            styled = styled.bold();
+        }
+
+        styled
+    }
+}
+
+impl RuleColor {
+    /// Returns the color as a CSS hexadecimal color, e.g. `#d4af37` for gold,
+    /// to style the rows in HTML outputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::RuleColor;
+    ///
+    /// assert_eq!(RuleColor::Red.hex(), "#ff0000");
+    /// assert_eq!(RuleColor::Gold.hex(), "#d4af37");
+    /// ```
+    pub fn hex(self) -> &'static str {
+        match self {
+            RuleColor::Red => "#ff0000",
+            RuleColor::Green => "#008000",
+            RuleColor::Yellow => "#ffff00",
+            RuleColor::Blue => "#0000ff",
+            RuleColor::Magenta => "#ff00ff",
+            RuleColor::Cyan => "#00ffff",
+            RuleColor::Gold => "#d4af37",
+        }
+    }
+
+    // Emoji marking the rows in Markdown, which can't be colored
+    pub(crate) fn marker(self) -> &'static str {
+        match self {
+            RuleColor::Red => "🔴",
+            RuleColor::Green => "🟢",
+            RuleColor::Yellow => "🟡",
+            RuleColor::Blue => "🔵",
+            RuleColor::Magenta => "🟣",
+            RuleColor::Cyan => "🔷",
+            RuleColor::Gold => "🥇",
+        }
+    }
+
+    // Color used to paint the row in the terminal
+    fn terminal(self) -> Color {
+        match self {
+            RuleColor::Red => Color::Red,
+            RuleColor::Green => Color::Green,
+            RuleColor::Yellow => Color::Yellow,
+            RuleColor::Blue => Color::Blue,
+            RuleColor::Magenta => Color::Magenta,
+            RuleColor::Cyan => Color::Cyan,
+            RuleColor::Gold => Color::TrueColor {
+                r: 212,
+                g: 175,
+                b: 55,
+            },
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+
+use crate::exam::{Seed, Student};
+
+/// Weights used to draw an audit sample from an exam.
+///
+/// Every student starts with the `base` weight. Students whose grade is
+/// within `boundary_margin` of the pass mark get `boundary` added to it, and
+/// students in the lowest or highest `extreme_fraction` of the class get
+/// `extreme` added to it, so those students are more likely to be picked for
+/// double-marking.
+#[derive(Debug, Clone)]
+pub struct AuditWeights {
+    /// Weight given to every student.
+    pub base: f32,
+
+    /// Extra weight given to students close to the pass mark.
+    pub boundary: f32,
+
+    /// Maximum distance to the pass mark, as a fraction of the maximum grade,
+    /// for a student to be considered close to it.
+    pub boundary_margin: f32,
+
+    /// Extra weight given to students at both ends of the distribution.
+    pub extreme: f32,
+
+    /// Fraction of the class, at each end of the distribution, considered to
+    /// be an extreme.
+    pub extreme_fraction: f32,
+}
+
+impl Default for AuditWeights {
+    fn default() -> Self {
+        Self {
+            base: 1.0,
+            boundary: 3.0,
+            boundary_margin: 0.05,
+            extreme: 2.0,
+            extreme_fraction: 0.1,
+        }
+    }
+}
+
+pub fn audit_sample<'a>(
+    students: &'a [Student],
+    max_grade: f32,
+    pass_grade: f32,
+    size: usize,
+    weights: &AuditWeights,
+    seed: Seed,
+) -> Vec<&'a Student> {
+    let margin = weights.boundary_margin * max_grade;
+    let extreme_percentile = weights.extreme_fraction * 100.0;
+    let mut rng = seed.rng();
+
+    // Weighted sampling without replacement (Efraimidis-Spirakis): each student
+    // gets a key `u^(1/w)` and the students with the highest keys are picked.
+    let mut keys: Vec<(usize, f64)> = students
+        .iter()
+        .enumerate()
+        .map(|(index, student)| {
+            let mut weight = weights.base;
+
+            if (student.grade - pass_grade).abs() <= margin {
+                weight += weights.boundary;
+            }
+
+            let percentile = student.percentile.unwrap_or(50.0);
+            if percentile <= extreme_percentile || percentile >= 100.0 - extreme_percentile {
+                weight += weights.extreme;
+            }
+
+            let key = if weight > 0.0 {
+                rng.next_f64().powf(1.0 / weight as f64)
+            } else {
+                0.0
+            };
+
+            (index, key)
+        })
+        .collect();
+
+    keys.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    keys.truncate(size);
+
+    // Return the sample in the same order the students have in the exam
+    keys.sort_by_key(|&(index, _)| index);
+    keys.into_iter()
+        .map(|(index, _)| &students[index])
+        .collect()
+}
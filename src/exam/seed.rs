@@ -0,0 +1,63 @@
+/// Seed of the random number generator used by the randomized features of
+/// the crate, like [Exam::audit_sample](crate::exam::Exam::audit_sample).
+///
+/// Every randomized feature takes a seed, and the same seed always yields the
+/// same results across platforms and crate versions, so results can be
+/// reproduced and tested. Seeds can be created from any `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::Seed;
+///
+/// let seed = Seed::new(42);
+///
+/// assert_eq!(seed, Seed::from(42));
+/// assert_eq!(seed.value(), 42);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Seed(u64);
+
+impl Seed {
+    /// Creates a seed from the given value.
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Returns the value the seed was created from.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    // Generator to draw the random values from
+    pub(crate) fn rng(self) -> SplitMix64 {
+        SplitMix64 { state: self.0 }
+    }
+}
+
+impl From<u64> for Seed {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+// Small deterministic generator so that the results obtained with the same
+// seed are the same across platforms and crate versions.
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform value in the open interval (0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,95 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::exam::statistics::slope;
+use crate::exam::Student;
+
+/// Share of the students of an exam that left each question blank or scored
+/// zero in it, in the order the questions were asked, to detect whether
+/// students ran out of time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Speededness {
+    /// Blank and zero rates of each question, in question order.
+    pub questions: Vec<QuestionCompletion>,
+}
+
+/// Blank and zero rates of a single question of an exam, see [Speededness].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestionCompletion {
+    /// Name of the question.
+    pub name: String,
+
+    /// Percentage of the students of the exam without a score in the
+    /// question, ranging from 0 to 100.
+    pub blank_rate: f32,
+
+    /// Percentage of the students of the exam with a score of zero in the
+    /// question, ranging from 0 to 100.
+    pub zero_rate: f32,
+}
+
+impl Speededness {
+    /// Returns the least-squares slope of the blank rate against the position
+    /// of the questions, in percentage points per question, or `None` if there
+    /// are less than two questions. A clearly positive slope means blanks rise
+    /// toward the end of the exam, a sign that it was too long.
+    pub fn blank_trend(&self) -> Option<f32> {
+        self.trend(|question| question.blank_rate)
+    }
+
+    /// Returns the least-squares slope of the sum of the blank and zero rates
+    /// against the position of the questions, see
+    /// [blank_trend](Speededness::blank_trend). Useful when unanswered
+    /// questions are graded as zero instead of left blank.
+    pub fn unanswered_trend(&self) -> Option<f32> {
+        self.trend(|question| question.blank_rate + question.zero_rate)
+    }
+
+    fn trend(&self, rate: impl Fn(&QuestionCompletion) -> f32) -> Option<f32> {
+        let points: Vec<(f64, f64)> = self
+            .questions
+            .iter()
+            .enumerate()
+            .map(|(i, question)| (i as f64, rate(question) as f64))
+            .collect();
+
+        slope(&points).map(|slope| slope as f32)
+    }
+}
+
+// Blank and zero rates of every question, in the order of the questions with a
+// known maximum score followed by the rest in the order they first appear
+pub fn speededness(students: &[Student], max_scores: &IndexMap<String, f32>) -> Speededness {
+    let mut names: IndexMap<&str, ()> = max_scores.keys().map(|q| (q.as_str(), ())).collect();
+    for student in students {
+        for question in student.questions.keys() {
+            names.entry(question).or_default();
+        }
+    }
+
+    let rate = |count: usize| {
+        if students.is_empty() {
+            0.0
+        } else {
+            count as f32 / students.len() as f32 * 100.0
+        }
+    };
+
+    let questions = names
+        .into_keys()
+        .map(|question| {
+            let scores = students.iter().filter_map(|s| s.questions.get(question));
+            let (answered, zeros) = scores.fold((0, 0), |(answered, zeros), &score| {
+                (answered + 1, zeros + usize::from(score == 0.0))
+            });
+
+            QuestionCompletion {
+                name: question.to_string(),
+                blank_rate: rate(students.len() - answered),
+                zero_rate: rate(zeros),
+            }
+        })
+        .collect();
+
+    Speededness { questions }
+}
@@ -1,40 +1,82 @@
 use std::cmp::Ordering;
 
 use colored::Colorize;
-use prettytable::{format, row, Table};
-
-use crate::exam::Student;
-
-#[derive(Debug, Clone)]
-pub struct ExamStatistics {
-    pub total_students: u32,
-    pub passed_students: u32,
-    pub failed_students: u32,
-    pub pass_rate: f32,
-    pub mean: f32,
-    pub median: f32,
-    pub std_dev: f32,
-    pub max_grade: f32,
-    pub highest_grade: f32,
-    pub lowest_grade: f32,
-    pub highest_rank: u32,
-}
-
-impl ExamStatistics {
-    pub fn new(students: &mut [Student], max_grade: f32) -> Self {
-        attach_rank(students);
-        attach_percentile(students);
+use prettytable::{row, Cell, Row, Table};
+use serde::{Deserialize, Serialize};
+
+use crate::exam::{
+    table_format, FormatRule, GradeScale, QuestionStatistics, RankMethod, RenderWarning, Student,
+};
+
+/// Statistics of the grades of an exam.
+///
+/// The statistics are computed when the exam is created and updated every time
+/// its students or its maximum grade change. They can be obtained with
+/// [Exam::statistics()](crate::exam::Exam::statistics).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    pub(crate) total_students: u32,
+    pub(crate) passed_students: u32,
+    pub(crate) failed_students: u32,
+    pub(crate) pass_rate: f32,
+    pub(crate) mean: f32,
+    pub(crate) median: f32,
+    pub(crate) std_dev: f32,
+    pub(crate) max_grade: f32,
+    pub(crate) pass_grade: f32,
+    pub(crate) highest_grade: f32,
+    pub(crate) lowest_grade: f32,
+    pub(crate) highest_rank: f32,
+    pub(crate) first_quartile: f32,
+    pub(crate) third_quartile: f32,
+    pub(crate) modes: Vec<f32>,
+    pub(crate) skewness: f32,
+    pub(crate) kurtosis: f32,
+}
+
+impl Statistics {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(students = students.len()))
+    )]
+    pub(crate) fn new(
+        students: &mut [Student],
+        max_grade: f32,
+        pass_grade: f32,
+        rank_method: RankMethod,
+    ) -> Self {
+        // Indices of the students sorted by grade in ascending order, shared by
+        // every statistic that depends on the order of the grades
+        let mut sorted: Vec<usize> = (0..students.len()).collect();
+        sorted.sort_unstable_by(|&a, &b| {
+            students[a]
+                .grade
+                .partial_cmp(&students[b].grade)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        attach_rank(students, &sorted, rank_method);
+        attach_percentile(students, &sorted);
 
         let total_students = students.len() as u32;
-        let passed_students = passed_students(students, max_grade);
+        let passed_students = passed_students(students, pass_grade);
         let failed_students = total_students - passed_students;
         let pass_rate = passed_students as f32 / total_students as f32 * 100.0;
         let mean = mean(students);
-        let median = median(students);
+        let median = median(students, &sorted);
         let std_dev = std_deviation(students, mean);
-        let highest_grade = max_student_grade(students);
-        let lowest_grade = min_student_grade(students);
+        let highest_grade = sorted.last().map_or(0.0, |&i| students[i].grade);
+        let lowest_grade = sorted.first().map_or(0.0, |&i| students[i].grade);
         let highest_rank = highest_rank(students);
+        let first_quartile = quantile(students, &sorted, 0.25);
+        let third_quartile = quantile(students, &sorted, 0.75);
+        let modes = modes(students, &sorted);
+        let skewness = standardized_moment(students, mean, std_dev, 3);
+        let kurtosis = if std_dev > 0.0 {
+            standardized_moment(students, mean, std_dev, 4) - 3.0
+        } else {
+            0.0
+        };
 
         Self {
             total_students,
@@ -45,21 +87,114 @@ impl ExamStatistics {
             median,
             std_dev,
             max_grade,
+            pass_grade,
             highest_grade,
             lowest_grade,
             highest_rank,
+            first_quartile,
+            third_quartile,
+            modes,
+            skewness,
+            kurtosis,
         }
     }
 
-    pub fn summary(&self, title: &Option<String>) {
-        if let Some(exam_title) = title {
-            let mut table_title = Table::new();
-            table_title.add_row(row![Fc->exam_title]);
+    /// Returns the number of students of the exam.
+    pub fn total_students(&self) -> u32 {
+        self.total_students
+    }
 
-            table_title.set_format(*format::consts::FORMAT_BOX_CHARS);
-            table_title.printstd();
-        }
+    /// Returns the number of students with a grade of at least the pass grade.
+    pub fn passed_students(&self) -> u32 {
+        self.passed_students
+    }
+
+    /// Returns the number of students with a grade lower than the pass grade.
+    pub fn failed_students(&self) -> u32 {
+        self.failed_students
+    }
+
+    /// Returns the percentage of passed students, ranging from 0 to 100.
+    pub fn pass_rate(&self) -> f32 {
+        self.pass_rate
+    }
+
+    /// Returns the mean of the grades.
+    pub fn mean(&self) -> f32 {
+        self.mean
+    }
+
+    /// Returns the median of the grades.
+    pub fn median(&self) -> f32 {
+        self.median
+    }
+
+    /// Returns the population standard deviation of the grades.
+    pub fn std_dev(&self) -> f32 {
+        self.std_dev
+    }
+
+    /// Returns the maximum achievable grade of the exam.
+    pub fn max_grade(&self) -> f32 {
+        self.max_grade
+    }
+
+    /// Returns the minimum grade needed to pass the exam.
+    pub fn pass_grade(&self) -> f32 {
+        self.pass_grade
+    }
+
+    /// Returns the highest grade obtained by a student.
+    pub fn highest_grade(&self) -> f32 {
+        self.highest_grade
+    }
+
+    /// Returns the lowest grade obtained by a student.
+    pub fn lowest_grade(&self) -> f32 {
+        self.lowest_grade
+    }
+
+    /// Returns the first quartile of the grades, i.e. the grade below which a
+    /// quarter of the grades fall. Interpolates linearly between the two
+    /// closest grades, like the median does.
+    pub fn first_quartile(&self) -> f32 {
+        self.first_quartile
+    }
+
+    /// Returns the third quartile of the grades, i.e. the grade below which
+    /// three quarters of the grades fall.
+    pub fn third_quartile(&self) -> f32 {
+        self.third_quartile
+    }
+
+    /// Returns the interquartile range of the grades, the difference between
+    /// the third and the first quartile.
+    pub fn interquartile_range(&self) -> f32 {
+        self.third_quartile - self.first_quartile
+    }
+
+    /// Returns the most repeated grades in ascending order. It's empty if no
+    /// grade is repeated.
+    pub fn modes(&self) -> &[f32] {
+        &self.modes
+    }
+
+    /// Returns the skewness of the grades. It's positive if most grades are
+    /// low with a few high ones, and negative if most grades are high with a
+    /// few low ones.
+    pub fn skewness(&self) -> f32 {
+        self.skewness
+    }
 
+    /// Returns the excess kurtosis of the grades. It's positive if the grades
+    /// have heavier tails than a normal distribution, and negative if lighter.
+    pub fn kurtosis(&self) -> f32 {
+        self.kurtosis
+    }
+
+    // Table with the statistics of the exam. The extended table also includes
+    // the quartiles, the modes and the shape of the distribution.
+    pub(crate) fn summary_table(&self, band_counts: &[(&str, usize)], extended: bool) -> Table {
         let mut table = Table::new();
         table.add_row(row!["Total Students", self.total_students]);
         table.add_row(row!["Passed Students", self.passed_students]);
@@ -68,35 +203,284 @@ impl ExamStatistics {
         table.add_row(row!["Mean", self.mean]);
         table.add_row(row!["Median", self.median]);
         table.add_row(row!["Standard Deviation", self.std_dev]);
+        if extended {
+            let modes = if self.modes.is_empty() {
+                "-".to_string()
+            } else {
+                self.modes
+                    .iter()
+                    .map(f32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            table.add_row(row!["First Quartile", self.first_quartile]);
+            table.add_row(row!["Third Quartile", self.third_quartile]);
+            table.add_row(row!["Interquartile Range", self.interquartile_range()]);
+            table.add_row(row!["Mode", modes]);
+            table.add_row(row!["Skewness", self.skewness]);
+            table.add_row(row!["Kurtosis", self.kurtosis]);
+        }
         table.add_row(row!["Max Grade", self.highest_grade]);
         table.add_row(row!["Min Grade", self.lowest_grade]);
 
-        table.set_format(*format::consts::FORMAT_BOX_CHARS);
-        table.printstd();
+        for (label, count) in band_counts {
+            table.add_row(row![label, count]);
+        }
+
+        table.set_format(table_format());
+        table
     }
 
-    pub fn students(&self, students: &[Student]) {
+    pub(crate) fn students_table(
+        &self,
+        students: &[&Student],
+        columns: &StudentColumns,
+    ) -> (Table, Vec<RenderWarning>) {
         let mut table = Table::new();
-        table.set_titles(row![c->"Name", c->"Grade", c->"Percentile", c->"Rank"]);
+        table.set_titles(columns.title_row());
 
         for student in students {
-            let colored_grade = if student.grade >= self.max_grade / 2.0 {
-                student.grade.to_string().green()
+            table.add_row(columns.row(student));
+        }
+
+        table.set_format(table_format());
+
+        (table, missing_statistics(students))
+    }
+}
+
+// Columns of the students table. The name is aligned to the left and every
+// other column is centered.
+pub(crate) struct StudentColumns<'a> {
+    statistics: &'a Statistics,
+    normalized_scale: Option<f32>,
+    grade_scale: Option<&'a GradeScale>,
+    outlier_bounds: Option<(f32, f32)>,
+    rules: &'a [FormatRule],
+}
+
+impl<'a> StudentColumns<'a> {
+    pub fn new(
+        statistics: &'a Statistics,
+        normalized_scale: Option<f32>,
+        grade_scale: Option<&'a GradeScale>,
+        outlier_bounds: Option<(f32, f32)>,
+        rules: &'a [FormatRule],
+    ) -> Self {
+        // The normalized grade is only worth showing if it differs from the raw
+        // grade
+        let normalized_scale = normalized_scale.filter(|&scale| scale != statistics.max_grade);
+
+        Self {
+            statistics,
+            normalized_scale,
+            grade_scale,
+            outlier_bounds,
+            rules,
+        }
+    }
+
+    // Title row of the students table
+    pub fn title_row(&self) -> Row {
+        Row::new(
+            self.titles()
+                .iter()
+                .map(|title| Cell::new(title).style_spec("c"))
+                .collect(),
+        )
+    }
+
+    // Row of the student in the students table
+    pub fn row(&self, student: &Student) -> Row {
+        Row::new(
+            self.cells(student)
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    let cell = Cell::new(cell);
+                    if i == 0 {
+                        cell
+                    } else {
+                        cell.style_spec("c")
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    pub fn titles(&self) -> Vec<String> {
+        let mut titles = vec!["Name".to_string(), "Grade".to_string()];
+        if let Some(scale) = self.normalized_scale {
+            if scale == 100.0 {
+                titles.push("Grade (%)".to_string());
             } else {
-                student.grade.to_string().red()
+                titles.push(format!("Grade (/{scale})"));
+            }
+        }
+        if self.grade_scale.is_some() {
+            titles.push("Band".to_string());
+        }
+        titles.push("Percentile".to_string());
+        titles.push("Rank".to_string());
+        if self.outlier_bounds.is_some() {
+            titles.push("Outlier".to_string());
+        }
+
+        titles
+    }
+
+    // Without rules, only the grade is painted depending on whether it passes.
+    // Otherwise, every cell is styled by the first rule the student meets.
+    pub fn cells(&self, student: &Student) -> Vec<String> {
+        let statistics = self.statistics;
+
+        let grade = if !self.rules.is_empty() {
+            student.grade.to_string()
+        } else if student.grade >= statistics.pass_grade {
+            student.grade.to_string().green().to_string()
+        } else {
+            student.grade.to_string().red().to_string()
+        };
+
+        let mut cells = vec![student.name.clone(), grade];
+        if let Some(scale) = self.normalized_scale {
+            let normalized = (student.grade / statistics.max_grade * scale * 100.0).round() / 100.0;
+            cells.push(normalized.to_string());
+        }
+        if let Some(scale) = self.grade_scale {
+            let label = scale.band(student.grade).map_or("-", |band| &band.label);
+            cells.push(label.to_string());
+        }
+        cells.push(student.percentile.unwrap_or(0.).to_string());
+        cells.push(format!(
+            "[{}/{}]",
+            student.rank.unwrap_or(0.0),
+            statistics.highest_rank
+        ));
+        if let Some((lowest, highest)) = self.outlier_bounds {
+            let marker = if student.grade < lowest || student.grade > highest {
+                "*".yellow().to_string()
+            } else {
+                String::new()
             };
+            cells.push(marker);
+        }
 
-            table.add_row(row![
-                student.name,
-                c->colored_grade,
-                c->student.percentile.unwrap_or(0.),
-                c->format!("[{}/{}]", student.rank.unwrap_or(0), self.highest_rank)
-            ]);
+        match self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(student, statistics))
+        {
+            Some(rule) => cells
+                .iter()
+                .map(|cell| rule.style(cell).to_string())
+                .collect(),
+            None => cells,
         }
+    }
+}
+
+// Warning listing the students without a rank or percentile, if any
+pub(crate) fn missing_statistics(students: &[&Student]) -> Vec<RenderWarning> {
+    let missing: Vec<String> = students
+        .iter()
+        .filter(|student| student.rank.is_none() || student.percentile.is_none())
+        .map(|student| student.name.clone())
+        .collect();
 
-        table.set_format(*format::consts::FORMAT_BOX_CHARS);
-        table.printstd()
+    if missing.is_empty() {
+        Vec::new()
+    } else {
+        vec![RenderWarning::MissingStatistics(missing)]
+    }
+}
+
+// Table with the title of the exam followed by its metadata, e.g. the course or
+// the instructor, as `(label, value)` pairs. There's no table if there's
+// neither.
+pub(crate) fn header_table(title: Option<&str>, metadata: &[(&str, &str)]) -> Option<Table> {
+    if title.is_none() && metadata.is_empty() {
+        return None;
     }
+
+    let mut table = Table::new();
+    if let Some(title) = title {
+        table.add_row(row![Fc->title]);
+    }
+    for (label, value) in metadata {
+        table.add_row(Row::new(vec![Cell::new(&format!("{label}: {value}"))]));
+    }
+
+    table.set_format(table_format());
+    Some(table)
+}
+
+// Table with the mean and pass rate of each question of the exam
+pub(crate) fn questions_table(questions: &[QuestionStatistics]) -> Table {
+    let flagged = questions.iter().any(|question| question.flag().is_some());
+
+    let mut table = Table::new();
+    let mut titles = row![
+        c->"Question",
+        c->"Mean",
+        c->"Min",
+        c->"Median",
+        c->"Max",
+        c->"Pass Rate"
+    ];
+    if flagged {
+        titles.add_cell(Cell::new("Review").style_spec("c"));
+    }
+    table.set_titles(titles);
+
+    for question in questions {
+        let pass_rate = question
+            .pass_rate
+            .map_or("-".to_string(), |pass_rate| format!("{pass_rate}%"));
+        let mut row = row![
+            question.name,
+            c->question.mean,
+            c->question.min,
+            c->question.median,
+            c->question.max,
+            c->pass_rate
+        ];
+        if flagged {
+            let flag = question.flag().map_or(String::new(), |flag| {
+                flag.description().yellow().to_string()
+            });
+            row.add_cell(Cell::new(&flag).style_spec("c"));
+        }
+        table.add_row(row);
+    }
+
+    table.set_format(table_format());
+    table
+}
+
+// Least-squares slope of the points, or `None` if there are less than two of
+// them or all of them have the same x
+pub(crate) fn slope(points: &[(f64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / n;
+
+    let (mut covariance, mut variance_x) = (0.0, 0.0);
+    for &(x, y) in points {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x).powi(2);
+    }
+
+    if variance_x == 0.0 {
+        return None;
+    }
+
+    Some(covariance / variance_x)
 }
 
 fn mean(students: &[Student]) -> f32 {
@@ -110,30 +494,26 @@ fn mean(students: &[Student]) -> f32 {
     grades_sum / total_students as f32
 }
 
-fn median(students: &[Student]) -> f32 {
+fn median(students: &[Student], sorted: &[usize]) -> f32 {
     let total_students = students.len();
 
     if total_students == 0 {
         return 0.0;
     }
 
-    let mut grades: Vec<f32> = students.iter().map(|s| s.grade).collect();
-    grades.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let grade = |index: usize| students[sorted[index]].grade;
 
-    if total_students % 2 == 0 {
+    if total_students.is_multiple_of(2) {
         let middle = total_students / 2;
-        (grades[middle - 1] + grades[middle]) / 2.0
+        (grade(middle - 1) + grade(middle)) / 2.0
     } else {
         let middle = total_students / 2;
-        grades[middle]
+        grade(middle)
     }
 }
 
-fn passed_students(students: &[Student], max_grade: f32) -> u32 {
-    return students
-        .iter()
-        .filter(|s| s.grade >= max_grade / 2.0)
-        .count() as u32;
+fn passed_students(students: &[Student], pass_grade: f32) -> u32 {
+    students.iter().filter(|s| s.grade >= pass_grade).count() as u32
 }
 
 fn std_deviation(students: &[Student], mean: f32) -> f32 {
@@ -151,98 +531,121 @@ fn std_deviation(students: &[Student], mean: f32) -> f32 {
     (sum / total_students as f32).sqrt()
 }
 
-fn max_student_grade(students: &[Student]) -> f32 {
-    return students
-        .iter()
-        .map(|s| s.grade)
-        .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-        .unwrap_or(0.0);
+// Grade below which the given fraction of the grades fall, interpolating
+// linearly between the two closest grades.
+fn quantile(students: &[Student], sorted: &[usize], fraction: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let position = fraction * (sorted.len() - 1) as f32;
+    let lower = students[sorted[position.floor() as usize]].grade;
+    let upper = students[sorted[position.ceil() as usize]].grade;
+
+    lower + (upper - lower) * position.fract()
 }
 
-fn min_student_grade(students: &[Student]) -> f32 {
-    students
+// Grades repeated the most times, as long as some grade is repeated
+fn modes(students: &[Student], sorted: &[usize]) -> Vec<f32> {
+    let mut modes = Vec::new();
+    let mut highest_count = 1;
+    let mut index = 0;
+
+    while index < sorted.len() {
+        let grade = students[sorted[index]].grade;
+        let count = sorted[index..]
+            .iter()
+            .take_while(|&&i| students[i].grade == grade)
+            .count();
+
+        if count > highest_count {
+            highest_count = count;
+            modes.clear();
+        }
+        if count == highest_count && count > 1 {
+            modes.push(grade);
+        }
+
+        index += count.max(1);
+    }
+
+    modes
+}
+
+// Mean of the grades deviations raised to the given power, divided by the
+// standard deviation raised to the same power. It's 0 if all grades are equal.
+fn standardized_moment(students: &[Student], mean: f32, std_dev: f32, power: i32) -> f32 {
+    if students.is_empty() || std_dev == 0.0 {
+        return 0.0;
+    }
+
+    let sum: f32 = students
         .iter()
-        .map(|s| s.grade)
-        .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-        .unwrap_or(0.0)
+        .map(|s| ((s.grade - mean) / std_dev).powi(power))
+        .sum();
+
+    sum / students.len() as f32
 }
 
-fn highest_rank(students: &[Student]) -> u32 {
+fn highest_rank(students: &[Student]) -> f32 {
     students
         .iter()
-        .map(|s| s.rank.unwrap_or(0))
-        .max()
-        .unwrap_or(0)
+        .map(|s| s.rank.unwrap_or(0.0))
+        .fold(0.0, f32::max)
 }
 
-fn attach_rank(students: &mut [Student]) {
-    // Create a vector of indices that represent the original order of the students
-    let total_students = students.len();
-    let mut indices: Vec<usize> = (0..total_students).collect();
-
-    // Sort the indices based on the grades of the students
-    indices.sort_by(|&a, &b| {
-        students[b]
-            .grade
-            .partial_cmp(&students[a].grade)
-            .unwrap_or(Ordering::Equal)
-    });
-
-    let mut last_grade = None;
-    let mut last_rank = None;
-    let mut rank = 0;
-
-    for &student_index in &indices {
-        let grade = students[student_index].grade;
-        let rank = match last_grade {
-            Some(last_grade) if grade == last_grade => last_rank.unwrap_or(0),
-            _ => {
-                rank += 1;
-                rank
-            }
+// Ranks from the highest grade, students with the same grade are ranked
+// according to the given method
+fn attach_rank(students: &mut [Student], sorted: &[usize], method: RankMethod) {
+    let descending: Vec<usize> = sorted.iter().rev().copied().collect();
+    let mut dense_rank = 0;
+    let mut start = 0;
+
+    // Each iteration ranks the students with the same grade, which are in
+    // positions `start + 1` to `end` of the ranking. A NaN grade is equal to
+    // no other, so its student is ranked alone.
+    while start < descending.len() {
+        let grade = students[descending[start]].grade;
+        let end = start
+            + descending[start..]
+                .iter()
+                .take_while(|&&i| students[i].grade == grade)
+                .count()
+                .max(1);
+        dense_rank += 1;
+
+        let rank = match method {
+            RankMethod::Dense => dense_rank as f32,
+            RankMethod::Competition => (start + 1) as f32,
+            RankMethod::Fractional => (start + 1 + end) as f32 / 2.0,
         };
-        students[student_index].rank = Some(rank as u32);
-        last_grade = Some(grade);
-        last_rank = Some(rank);
+        for &student_index in &descending[start..end] {
+            students[student_index].rank = Some(rank);
+        }
+
+        start = end;
     }
 }
 
-fn attach_percentile(students: &mut [Student]) {
+// Percentage of the other students with a lower grade. Students with the same
+// grade share the same percentile, and the highest grade is always 100.
+fn attach_percentile(students: &mut [Student], sorted: &[usize]) {
     let total_students = students.len();
-
-    // Create a vector of indices that represent the original order of the students
-    let mut indices: Vec<usize> = (0..total_students).collect();
-
-    let max_grade = students
-        .iter()
-        .map(|s| s.grade)
-        .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-        .unwrap_or(0.0);
-
-    indices.sort_by(|&a, &b| {
-        students[a]
-            .grade
-            .partial_cmp(&students[b].grade)
-            .unwrap_or(Ordering::Equal)
-    });
+    let max_grade = sorted.last().map_or(0.0, |&i| students[i].grade);
 
     let mut last_grade = None;
-    let mut last_percentile = None;
+    let mut last_percentile = 0.0;
 
-    for (index, &student_index) in indices.iter().enumerate() {
+    for (index, &student_index) in sorted.iter().enumerate() {
         let grade = students[student_index].grade;
         let percentile = match last_grade {
-            Some(last_grade) if grade == last_grade => last_percentile.unwrap_or(0.0),
-            _ => {
-                if grade == max_grade {
-                    100.0
-                } else {
-                    index as f32 / (total_students - 1) as f32 * 100.0
-                }
-            }
+            Some(last_grade) if grade == last_grade => last_percentile,
+            _ if grade == max_grade => 100.0,
+            _ => index as f32 / (total_students - 1) as f32 * 100.0,
         };
+
         students[student_index].percentile = Some(percentile);
         last_grade = Some(grade);
-        last_percentile = Some(percentile);
+        last_percentile = percentile;
     }
 }
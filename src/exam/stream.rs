@@ -0,0 +1,138 @@
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::exam::statistics::{missing_statistics, StudentColumns};
+use crate::exam::{output_style, RenderWarning, Student, TableStyle};
+
+// Characters used to draw the table, like the ones of the prettytable formats
+struct Borders {
+    horizontal: char,
+    title_horizontal: char,
+    vertical: char,
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+}
+
+const BOX_BORDERS: Borders = Borders {
+    horizontal: '─',
+    title_horizontal: '─',
+    vertical: '│',
+    top: ['┌', '┬', '┐'],
+    middle: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+};
+
+const ASCII_BORDERS: Borders = Borders {
+    horizontal: '-',
+    title_horizontal: '=',
+    vertical: '|',
+    top: ['+'; 3],
+    middle: ['+'; 3],
+    bottom: ['+'; 3],
+};
+
+// Writes the students table row by row, with the same layout as the one built
+// with prettytable, so no more than a row is kept in memory at once. The cells
+// are computed twice, first to find the width of each column and then to write
+// them.
+pub fn write_students_table<W: Write>(
+    mut writer: W,
+    students: &[&Student],
+    columns: &StudentColumns,
+) -> io::Result<Vec<RenderWarning>> {
+    let titles = columns.titles();
+
+    let mut widths: Vec<usize> = titles.iter().map(|title| display_width(title)).collect();
+    for student in students {
+        for (width, cell) in widths.iter_mut().zip(columns.cells(student)) {
+            *width = (*width).max(display_width(&cell));
+        }
+    }
+
+    let borders = match output_style().table {
+        TableStyle::Box => &BOX_BORDERS,
+        TableStyle::Ascii => &ASCII_BORDERS,
+    };
+
+    write_line(&mut writer, &widths, borders.top, borders.horizontal)?;
+    write_row(&mut writer, &widths, &titles, true, borders.vertical)?;
+    write_line(
+        &mut writer,
+        &widths,
+        borders.middle,
+        borders.title_horizontal,
+    )?;
+    for (i, student) in students.iter().enumerate() {
+        if i > 0 {
+            write_line(&mut writer, &widths, borders.middle, borders.horizontal)?;
+        }
+        let cells = columns.cells(student);
+        write_row(&mut writer, &widths, &cells, false, borders.vertical)?;
+    }
+    write_line(&mut writer, &widths, borders.bottom, borders.horizontal)?;
+    writer.flush()?;
+
+    Ok(missing_statistics(students))
+}
+
+// Horizontal line of the table, with the given left, inner and right
+// junctions
+fn write_line<W: Write>(
+    writer: &mut W,
+    widths: &[usize],
+    junctions: [char; 3],
+    horizontal: char,
+) -> io::Result<()> {
+    let [left, inner, right] = junctions;
+
+    write!(writer, "{left}")?;
+    for (i, width) in widths.iter().enumerate() {
+        if i > 0 {
+            write!(writer, "{inner}")?;
+        }
+        write!(writer, "{}", horizontal.to_string().repeat(width + 2))?;
+    }
+    writeln!(writer, "{right}")
+}
+
+// The first cell of the students is aligned to the left, every other cell is
+// centered
+fn write_row<W: Write>(
+    writer: &mut W,
+    widths: &[usize],
+    cells: &[String],
+    titles: bool,
+    vertical: char,
+) -> io::Result<()> {
+    write!(writer, "{vertical}")?;
+    for (i, (width, cell)) in widths.iter().zip(cells).enumerate() {
+        let fill = width - display_width(cell);
+        let left = if i == 0 && !titles { 0 } else { fill / 2 };
+
+        write!(
+            writer,
+            " {}{cell}{} {vertical}",
+            " ".repeat(left),
+            " ".repeat(fill - left)
+        )?;
+    }
+    writeln!(writer)
+}
+
+// Width of the text on the terminal, ignoring the ANSI escape codes used to
+// color it
+fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = text;
+    while let Some(start) = rest.find('\u{1b}') {
+        width += rest[..start].width();
+        rest = match rest[start..].find('m') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+
+    width + rest.width()
+}
@@ -1,5 +1,14 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::{validate_grade, GradeError};
+
 /// Struct representing a student.
-#[derive(Debug, Clone)]
+///
+/// Students can be serialized and deserialized with [serde], including their
+/// rank and percentile in the exam they belong to. Their quartile and decile
+/// are serialized too, although they are not read back.
+#[derive(Debug, Clone, Deserialize)]
 pub struct Student {
     /// Name of the student.
     pub name: String,
@@ -7,10 +16,50 @@ pub struct Student {
     /// Grade of the student.
     pub grade: f32,
 
-    pub(crate) rank: Option<u32>,
+    /// Scores of the student in each question or section of the exam, in the
+    /// order they were given. Empty if there's no breakdown of the grade.
+    pub questions: IndexMap<String, f32>,
+
+    /// Option chosen by the student in each multiple-choice question of the
+    /// exam, e.g. `"B"`, used for the
+    /// [distractor analysis](crate::exam::Exam::distractor_analysis). Empty if
+    /// the chosen options are unknown.
+    #[serde(default)]
+    pub answers: IndexMap<String, String>,
+
+    /// Identifier of the student, e.g. their NIA, used to match them across
+    /// exams instead of their name.
+    pub id: Option<String>,
+
+    /// Group or class section of the student.
+    pub group: Option<String>,
+
+    /// Email address of the student.
+    pub email: Option<String>,
+
+    pub(crate) rank: Option<f32>,
     pub(crate) percentile: Option<f32>,
 }
 
+// Student as serialized, with the quartile and decile computed from the
+// percentile
+#[derive(Serialize)]
+struct SerializedStudent<'a> {
+    name: &'a str,
+    grade: f32,
+    questions: &'a IndexMap<String, f32>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    answers: &'a IndexMap<String, String>,
+    id: &'a Option<String>,
+    group: &'a Option<String>,
+    email: &'a Option<String>,
+    #[serde(serialize_with = "serialize_rank")]
+    rank: Option<f32>,
+    percentile: Option<f32>,
+    quartile: Option<u8>,
+    decile: Option<u8>,
+}
+
 impl Student {
     /// Creates a new student from a given name and grade.
     ///
@@ -28,8 +77,202 @@ impl Student {
         Student {
             name: name.into(),
             grade,
+            questions: IndexMap::new(),
+            answers: IndexMap::new(),
+            id: None,
+            group: None,
+            email: None,
             rank: None,
             percentile: None,
         }
     }
+
+    /// Creates a new student from a given name and the scores obtained in each
+    /// question of the exam. The grade of the student is the sum of the scores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Student;
+    ///
+    /// let student = Student::with_questions("Joan Beltrán Peris", [("Q1", 2.5), ("Q2", 4.0)]);
+    ///
+    /// assert_eq!(student.grade, 6.5);
+    /// assert_eq!(student.questions["Q2"], 4.0);
+    /// ```
+    pub fn with_questions<T: Into<String>, S: Into<String>>(
+        name: T,
+        questions: impl IntoIterator<Item = (S, f32)>,
+    ) -> Student {
+        let questions: IndexMap<String, f32> = questions
+            .into_iter()
+            .map(|(question, score)| (question.into(), score))
+            .collect();
+
+        Student {
+            grade: questions.values().sum(),
+            questions,
+            ..Student::new(name, 0.0)
+        }
+    }
+
+    /// Creates a new student from a given name and grade, checking that the
+    /// grade is a number between 0 and the given maximum grade.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::error::GradeError;
+    /// use exms::exam::Student;
+    ///
+    /// let student = Student::try_new("Joan Beltrán Peris", 9.5, 10.0).unwrap();
+    /// assert_eq!(student.grade, 9.5);
+    ///
+    /// let student = Student::try_new("Joan Beltrán Peris", 11.0, 10.0);
+    /// assert!(matches!(student, Err(GradeError::AboveMax { .. })));
+    ///
+    /// let student = Student::try_new("Joan Beltrán Peris", -1.0, 10.0);
+    /// assert!(matches!(student, Err(GradeError::Negative(_))));
+    /// ```
+    pub fn try_new<T: Into<String>>(
+        name: T,
+        grade: f32,
+        max_grade: f32,
+    ) -> Result<Student, GradeError> {
+        let grade = validate_grade(grade, max_grade)?;
+        Ok(Student::new(name, grade))
+    }
+
+    /// Sets the grade of the student, checking that it is a number between 0
+    /// and the given maximum grade. The grade is left unchanged if it is not
+    /// valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::Student;
+    ///
+    /// let mut student = Student::new("Joan Beltrán Peris", 9.5);
+    ///
+    /// assert!(student.set_grade(7.0, 10.0).is_ok());
+    /// assert!(student.set_grade(f32::NAN, 10.0).is_err());
+    /// assert_eq!(student.grade, 7.0);
+    /// ```
+    pub fn set_grade(&mut self, grade: f32, max_grade: f32) -> Result<(), GradeError> {
+        self.grade = validate_grade(grade, max_grade)?;
+        Ok(())
+    }
+
+    /// Returns the rank of the student in the exam they belong to, where `1`
+    /// is the highest grade, or `None` if it hasn't been computed. How
+    /// students with the same grade are ranked depends on the
+    /// [RankMethod](crate::exam::RankMethod) of the exam.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let exam = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ]);
+    ///
+    /// assert_eq!(exam.students()[0].rank(), Some(2.0));
+    /// assert_eq!(Student::new("Jose Abad Martínez", 3.6).rank(), None);
+    /// ```
+    pub fn rank(&self) -> Option<f32> {
+        self.rank
+    }
+
+    /// Returns the percentile of the student in the exam they belong to,
+    /// ranging from 0 to 100, or `None` if it hasn't been computed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let exam = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ]);
+    ///
+    /// assert_eq!(exam.students()[1].percentile(), Some(100.0));
+    /// ```
+    pub fn percentile(&self) -> Option<f32> {
+        self.percentile
+    }
+
+    /// Returns the quartile of the exam the student belongs to according to
+    /// their [percentile](Student::percentile), from 1 for the lowest grades
+    /// to 4 for the highest ones, or `None` if it hasn't been computed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{Exam, Student};
+    ///
+    /// let exam = Exam::new(vec![
+    ///     Student::new("Joan Beltrán Peris", 4.6),
+    ///     Student::new("Jose Abad Martínez", 3.6),
+    ///     Student::new("David Jiménez Hidalgo", 7.94),
+    /// ]);
+    ///
+    /// assert_eq!(exam.students()[0].quartile(), Some(3));
+    /// assert_eq!(exam.students()[0].decile(), Some(6));
+    /// assert_eq!(exam.students()[1].quartile(), Some(1));
+    /// assert_eq!(exam.students()[2].quartile(), Some(4));
+    /// assert_eq!(exam.students()[2].decile(), Some(10));
+    /// ```
+    pub fn quartile(&self) -> Option<u8> {
+        self.percentile.map(|percentile| quantile(percentile, 4))
+    }
+
+    /// Returns the decile of the exam the student belongs to according to
+    /// their [percentile](Student::percentile), from 1 for the lowest grades
+    /// to 10 for the highest ones, or `None` if it hasn't been computed. See
+    /// [quartile](Student::quartile).
+    pub fn decile(&self) -> Option<u8> {
+        self.percentile.map(|percentile| quantile(percentile, 10))
+    }
+}
+
+impl Serialize for Student {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedStudent {
+            name: &self.name,
+            grade: self.grade,
+            questions: &self.questions,
+            answers: &self.answers,
+            id: &self.id,
+            group: &self.group,
+            email: &self.email,
+            rank: self.rank,
+            percentile: self.percentile,
+            quartile: self.quartile(),
+            decile: self.decile(),
+        }
+        .serialize(serializer)
+    }
+}
+
+// Which of the `parts` parts of equal size of the exam, numbered from 1, the
+// percentile falls in. The highest percentile is in the last part.
+fn quantile(percentile: f32, parts: u8) -> u8 {
+    let part = (percentile / 100.0 * parts as f32).floor();
+    (part as u8).clamp(0, parts - 1) + 1
+}
+
+// Whole ranks, the only ones unless they are fractional, are written as
+// integers
+pub(crate) fn serialize_rank<S: Serializer>(
+    rank: &Option<f32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match rank {
+        Some(rank) if rank.fract() == 0.0 => serializer.serialize_some(&(*rank as u32)),
+        Some(rank) => serializer.serialize_some(rank),
+        None => serializer.serialize_none(),
+    }
 }
@@ -0,0 +1,74 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::exam::{Exam, Statistics};
+
+// Views share exams across threads, so exams and everything they expose must
+// stay `Send + Sync`.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Exam>();
+    assert_send_sync::<Statistics>();
+    assert_send_sync::<ExamView>();
+};
+
+/// Cheap, read-only handle to an [Exam] that can be shared across threads.
+///
+/// Cloning a view only increments a reference count, so a single loaded exam
+/// can be handed to many threads, e.g. the request handlers of a web server,
+/// without copying its students. A view dereferences to the exam, giving
+/// access to every method that doesn't modify it.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use exms::exam::{Exam, ExamView, Student};
+///
+/// let students = &[
+///     Student::new("Joan Beltrán Peris", 4.6),
+///     Student::new("Jose Abad Martínez", 3.6),
+///     Student::new("David Jiménez Hidalgo", 7.94),
+/// ];
+///
+/// let view = ExamView::from(Exam::new(students));
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let view = view.clone();
+///         thread::spawn(move || view.count_above(5.0))
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), 1);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExamView {
+    exam: Arc<Exam>,
+}
+
+impl From<Exam> for ExamView {
+    fn from(exam: Exam) -> Self {
+        Self {
+            exam: Arc::new(exam),
+        }
+    }
+}
+
+impl From<Arc<Exam>> for ExamView {
+    fn from(exam: Arc<Exam>) -> Self {
+        Self { exam }
+    }
+}
+
+impl Deref for ExamView {
+    type Target = Exam;
+
+    fn deref(&self) -> &Exam {
+        &self.exam
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::exam::{Language, NameIssue};
+
+/// Conditions found while rendering exam output that may make it misleading
+/// without further explanation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderWarning {
+    /// Some students had no computed rank or percentile, so a placeholder
+    /// value of `0` was displayed instead. Contains the names of the affected
+    /// students.
+    MissingStatistics(Vec<String>),
+
+    /// Some grades were greater than the maximum grade and were drawn in the
+    /// last bucket of the histogram. This does not affect other statistics.
+    /// Contains the names of the affected students.
+    GradesTruncated(Vec<String>),
+}
+
+/// Problems found in the students of an exam while parsing or merging it,
+/// which don't prevent using it but may make its statistics misleading, see
+/// [Exam::warnings](crate::exam::Exam::warnings).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExamWarning {
+    /// The student appears more than once, ignoring case, accents and
+    /// whitespace, e.g. because of a typo in the key of a TOML file. Contains
+    /// the name of the repeated student.
+    DuplicateStudent(String),
+
+    /// The maximum grade was not given and was inferred from the grades, see
+    /// [ExamBuilder::infer_max_grade](crate::exam::ExamBuilder::infer_max_grade).
+    /// Contains the inferred maximum grade.
+    InferredMaxGrade(f32),
+
+    /// The grade of the student is negative or greater than the maximum grade,
+    /// see [Exam::validate](crate::exam::Exam::validate).
+    GradeOutOfRange { student: String, grade: f32 },
+
+    /// The name of the student has anomalies that can make it silently
+    /// mismatch the same student in other files, see
+    /// [Exam::name_issues](crate::exam::Exam::name_issues).
+    NameAnomaly(NameIssue),
+}
+
+impl ExamWarning {
+    /// Returns the description of the warning in the given language, without
+    /// colors, e.g. to include it in a report. The [Display](fmt::Display)
+    /// implementation gives the English one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::exam::{ExamWarning, Language};
+    ///
+    /// let warning = ExamWarning::DuplicateStudent("Joan Beltrán Peris".to_string());
+    ///
+    /// assert_eq!(
+    ///     warning.message(Language::Spanish),
+    ///     "Joan Beltrán Peris aparece más de una vez"
+    /// );
+    /// ```
+    pub fn message(&self, language: Language) -> String {
+        self.describe(language, str::to_string)
+    }
+
+    fn describe(&self, language: Language, highlight: fn(&str) -> String) -> String {
+        match (self, language) {
+            (ExamWarning::DuplicateStudent(student), Language::English) => {
+                format!("{} appears more than once", highlight(student))
+            }
+            (ExamWarning::DuplicateStudent(student), Language::Spanish) => {
+                format!("{} aparece más de una vez", highlight(student))
+            }
+            (ExamWarning::InferredMaxGrade(max_grade), Language::English) => format!(
+                "the maximum grade was inferred from the grades as {}",
+                highlight(&max_grade.to_string())
+            ),
+            (ExamWarning::InferredMaxGrade(max_grade), Language::Spanish) => format!(
+                "la nota máxima se ha deducido de las notas como {}",
+                highlight(&max_grade.to_string())
+            ),
+            (ExamWarning::GradeOutOfRange { student, grade }, Language::English) => format!(
+                "{} has a grade of {}, out of the range of the exam",
+                highlight(student),
+                highlight(&grade.to_string())
+            ),
+            (ExamWarning::GradeOutOfRange { student, grade }, Language::Spanish) => format!(
+                "{} tiene una nota de {}, fuera del rango del examen",
+                highlight(student),
+                highlight(&grade.to_string())
+            ),
+            (ExamWarning::NameAnomaly(issue), Language::English) => {
+                format!("the name {}", issue.describe(language, highlight))
+            }
+            (ExamWarning::NameAnomaly(issue), Language::Spanish) => {
+                format!("el nombre {}", issue.describe(language, highlight))
+            }
+        }
+    }
+}
+
+impl RenderWarning {
+    /// Returns the description of the warning in the given language, without
+    /// colors, e.g. to include it in a report. The [Display](fmt::Display)
+    /// implementation gives the English one.
+    pub fn message(&self, language: Language) -> String {
+        self.describe(language, str::to_string)
+    }
+
+    fn describe(&self, language: Language, highlight: fn(&str) -> String) -> String {
+        match (self, language) {
+            (RenderWarning::MissingStatistics(students), Language::English) => format!(
+                "The rank or percentile of the following students could not be computed and is \
+                 displayed as 0: {}",
+                highlight(&students.join(", "))
+            ),
+            (RenderWarning::MissingStatistics(students), Language::Spanish) => format!(
+                "No se ha podido calcular la posición o el percentil de los siguientes \
+                 estudiantes y se muestra 0 en su lugar: {}",
+                highlight(&students.join(", "))
+            ),
+            (RenderWarning::GradesTruncated(students), Language::English) => format!(
+                "The grades of the following students were truncated to fit in the histogram as \
+                 they were greater than the maximum grade: {}",
+                highlight(&students.join(", "))
+            ),
+            (RenderWarning::GradesTruncated(students), Language::Spanish) => format!(
+                "Las notas de los siguientes estudiantes se han truncado para caber en el \
+                 histograma por ser mayores que la nota máxima: {}",
+                highlight(&students.join(", "))
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ExamWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(Language::English, |text| text.yellow().to_string()))
+    }
+}
+
+impl fmt::Display for RenderWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(Language::English, |text| text.yellow().to_string()))
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::error::ParseError;
+use crate::exam::{Exam, ParseOptions};
+
+// Time given to the editor to finish writing the file before reading it
+const SETTLE_TIME: Duration = Duration::from_millis(50);
+
+/// Handle of a file watched with [Exam::watch]. The file stops being watched
+/// when it's dropped.
+#[derive(Debug)]
+pub struct ExamWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+pub fn watch<F>(path: &Path, options: &ParseOptions, mut on_change: F) -> io::Result<ExamWatcher>
+where
+    F: FnMut(Result<Exam, ParseError>) + Send + 'static,
+{
+    let path = fs::canonicalize(path)?;
+
+    // Editors usually save files by replacing them, so the directory is
+    // watched instead of the file itself
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let mut last_version = version(&path);
+    on_change(Exam::from_file_with_options(&path, options));
+
+    let file = path.clone();
+    let options = options.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if !is_change(&event, &file) {
+            return;
+        }
+
+        // A single save can trigger several events, reload only once
+        thread::sleep(SETTLE_TIME);
+        let current_version = version(&file);
+        if current_version.is_some() && current_version != last_version {
+            last_version = current_version;
+            on_change(Exam::from_file_with_options(&file, &options));
+        }
+    })
+    .map_err(io_error)?;
+
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(io_error)?;
+
+    Ok(ExamWatcher { _watcher: watcher })
+}
+
+fn is_change(event: &Event, file: &Path) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == file.file_name())
+}
+
+// Hash of the content of the file, if it exists and is not empty, as it's
+// truncated before being written again. The modification time and size are
+// not enough, as a quick edit that keeps the size can be saved within the
+// resolution of the modification time.
+fn version(path: &Path) -> Option<u64> {
+    let content = fs::read(path).ok().filter(|content| !content.is_empty())?;
+
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn io_error(err: notify::Error) -> io::Error {
+    match err.kind {
+        notify::ErrorKind::Io(err) => err,
+        _ => io::Error::other(err),
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+use calamine::{open_workbook, Data, DataType, Range, Reader, Xlsx, XlsxError};
+use indexmap::map::IndexMap;
+
+use crate::error::ParseErrorKind;
+use crate::exam::document::{
+    BorrowedDocument, ExamDetails, StudentEntry, StudentMap, StudentRecord,
+};
+use crate::exam::{GradeUnit, Limits};
+
+/// Options used to read an exam from an Excel (`.xlsx`) workbook.
+///
+/// The students sheet must contain one student per row, with the name and
+/// the grade of the student in the configured columns, and optionally their
+/// ID, group and email. The first row can optionally be a header.
+///
+/// The details sheet, if present, must contain one `key | value` pair per row,
+/// using the same keys as the `details` field of the other file formats (see
+/// [exms](crate)). The tables `questions` and `scale` take one row per entry
+/// instead, with the name of the question or band in the second column and
+/// its number in the third one, e.g. `questions | Q1 | 4`.
+#[derive(Debug, Clone)]
+pub struct XlsxOptions {
+    /// Name of the sheet containing the students. Defaults to the first sheet
+    /// of the workbook.
+    pub sheet: Option<String>,
+
+    /// Zero-based index of the column containing the student names. Defaults
+    /// to 0.
+    pub name_column: usize,
+
+    /// Zero-based index of the column containing the student grades. Defaults
+    /// to 1.
+    pub grade_column: usize,
+
+    /// Zero-based index of the column containing the student IDs, if any, see
+    /// [Student::id](crate::exam::Student::id). Defaults to none.
+    pub id_column: Option<usize>,
+
+    /// Zero-based index of the column containing the student groups, if any.
+    /// Defaults to none.
+    pub group_column: Option<usize>,
+
+    /// Zero-based index of the column containing the student emails, if any.
+    /// Defaults to none.
+    pub email_column: Option<usize>,
+
+    /// Name of the optional sheet containing the exam details. Defaults to
+    /// `details`.
+    pub details_sheet: String,
+}
+
+impl Default for XlsxOptions {
+    fn default() -> Self {
+        Self {
+            sheet: None,
+            name_column: 0,
+            grade_column: 1,
+            id_column: None,
+            group_column: None,
+            email_column: None,
+            details_sheet: "details".to_string(),
+        }
+    }
+}
+
+pub fn read_xlsx(
+    path: &Path,
+    options: &XlsxOptions,
+    limits: &Limits,
+) -> Result<BorrowedDocument<'static>, ParseErrorKind> {
+    let workbook: Xlsx<_> = open_workbook(path).map_err(ParseErrorKind::Xlsx)?;
+    read_workbook(workbook, options, limits)
+}
+
+pub fn read_xlsx_bytes(
+    bytes: &[u8],
+    options: &XlsxOptions,
+    limits: &Limits,
+) -> Result<BorrowedDocument<'static>, ParseErrorKind> {
+    let workbook = Xlsx::new(Cursor::new(bytes)).map_err(ParseErrorKind::Xlsx)?;
+    read_workbook(workbook, options, limits)
+}
+
+fn read_workbook<RS: Read + Seek>(
+    mut workbook: Xlsx<RS>,
+    options: &XlsxOptions,
+    limits: &Limits,
+) -> Result<BorrowedDocument<'static>, ParseErrorKind> {
+    let sheet_names = workbook.sheet_names();
+
+    let sheet = match &options.sheet {
+        Some(sheet) => sheet.clone(),
+        None => sheet_names
+            .first()
+            .cloned()
+            .ok_or(ParseErrorKind::Xlsx(XlsxError::Unexpected(
+                "the workbook has no sheets",
+            )))?,
+    };
+
+    let range = workbook
+        .worksheet_range(&sheet)
+        .map_err(ParseErrorKind::Xlsx)?;
+    let students = read_students(&range, &sheet, options, limits)?;
+
+    let details = if sheet_names.contains(&options.details_sheet) {
+        let range = workbook
+            .worksheet_range(&options.details_sheet)
+            .map_err(ParseErrorKind::Xlsx)?;
+
+        Some(read_details(&range, &options.details_sheet)?)
+    } else {
+        None
+    };
+
+    Ok(BorrowedDocument {
+        details,
+        students: students.students,
+        duplicates: students.duplicates,
+    })
+}
+
+fn read_students(
+    range: &Range<Data>,
+    sheet: &str,
+    options: &XlsxOptions,
+    limits: &Limits,
+) -> Result<StudentMap<'static>, ParseErrorKind> {
+    let mut students = StudentMap::with_limits(limits);
+
+    for (index, row) in range.rows().enumerate() {
+        let name = row
+            .get(options.name_column)
+            .and_then(DataType::as_string)
+            .unwrap_or_default();
+
+        if name.trim().is_empty() {
+            continue;
+        }
+
+        match row.get(options.grade_column).and_then(DataType::as_f64) {
+            Some(grade) => {
+                let cell = |column: Option<usize>| {
+                    let value = row.get(column?)?.as_string()?;
+                    let value = value.trim();
+                    (!value.is_empty()).then(|| value.to_string())
+                };
+                let (id, group, email) = (
+                    cell(options.id_column),
+                    cell(options.group_column),
+                    cell(options.email_column),
+                );
+
+                let entry = if id.is_none() && group.is_none() && email.is_none() {
+                    StudentEntry::Grade(grade as f32)
+                } else {
+                    StudentEntry::Detailed(Box::new(StudentRecord {
+                        grade: Some(grade as f32),
+                        questions: IndexMap::new(),
+                        answers: IndexMap::new(),
+                        id,
+                        group,
+                        email,
+                    }))
+                };
+
+                students
+                    .insert(name.trim().to_string(), entry)
+                    .map_err(ParseErrorKind::LimitExceeded)?;
+            }
+
+            // The first row is allowed to be a header
+            None if index == 0 => continue,
+
+            None => {
+                return Err(row_error(
+                    range,
+                    sheet,
+                    index,
+                    format!("missing or invalid grade for `{}`", name.trim()),
+                ))
+            }
+        }
+    }
+
+    Ok(students)
+}
+
+fn read_details(range: &Range<Data>, sheet: &str) -> Result<ExamDetails, ParseErrorKind> {
+    let mut details = ExamDetails::default();
+
+    for (index, row) in range.rows().enumerate() {
+        let key = row
+            .first()
+            .and_then(DataType::as_string)
+            .unwrap_or_default();
+        let value = row.get(1).unwrap_or(&Data::Empty);
+        let invalid = |expected: &str| {
+            row_error(
+                range,
+                sheet,
+                index,
+                format!("expected {expected} for `{key}`"),
+            )
+        };
+
+        match key.trim() {
+            "" => continue,
+            "name" => details.name = Some(value.as_string().ok_or_else(|| invalid("a string"))?),
+            "date" => details.date = Some(value.as_string().ok_or_else(|| invalid("a string"))?),
+            "course" => {
+                details.course = Some(value.as_string().ok_or_else(|| invalid("a string"))?)
+            }
+            "term" => details.term = Some(value.as_string().ok_or_else(|| invalid("a string"))?),
+            "instructor" => {
+                details.instructor = Some(value.as_string().ok_or_else(|| invalid("a string"))?)
+            }
+            "max_grade" => {
+                details.max_grade = Some(value.as_f64().ok_or_else(|| invalid("a number"))? as f32)
+            }
+            "pass_grade" => {
+                details.pass_grade = Some(value.as_f64().ok_or_else(|| invalid("a number"))? as f32)
+            }
+            "unit" => {
+                details.unit = match value.as_string().as_deref() {
+                    Some("points") => Some(GradeUnit::Points),
+                    Some("percentage") => Some(GradeUnit::Percentage),
+                    _ => return Err(invalid("`points` or `percentage`")),
+                }
+            }
+            table @ ("questions" | "scale") => {
+                let entry = value.as_string().filter(|name| !name.trim().is_empty());
+                let number = row.get(2).and_then(DataType::as_f64);
+                let (Some(entry), Some(number)) = (entry, number) else {
+                    return Err(invalid("a name followed by a number"));
+                };
+
+                let entries = match table {
+                    "questions" => &mut details.questions,
+                    _ => &mut details.scale,
+                };
+                entries
+                    .get_or_insert_with(IndexMap::new)
+                    .insert(entry.trim().to_string(), number as f32);
+            }
+            other => {
+                return Err(row_error(
+                    range,
+                    sheet,
+                    index,
+                    format!("unknown detail `{other}`"),
+                ))
+            }
+        }
+    }
+
+    Ok(details)
+}
+
+// Error for the given row of a sheet, using the row numbers shown by Excel
+fn row_error(range: &Range<Data>, sheet: &str, index: usize, reason: String) -> ParseErrorKind {
+    let first_row = range.start().map_or(0, |(row, _)| row as usize);
+
+    ParseErrorKind::XlsxRow {
+        sheet: sheet.to_string(),
+        row: first_row + index + 1,
+        reason,
+    }
+}
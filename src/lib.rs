@@ -2,8 +2,8 @@
 //! exam results
 //!
 //! You can create a [Exam](exms::exam::Exam) object from a file. For the
-//! moment the only file formats supported are [JSON](#json) and [TOML](#toml)
-//! files.
+//! moment the only file formats supported are [JSON](#json), [TOML](#toml),
+//! [YAML](#yaml) and [CSV](#csv) files.
 //!
 //! # Examples
 //!
@@ -35,8 +35,55 @@
 //!
 //! - `max_grade` (number): The maximum possible grade of the exam. If no value
 //!   is provided, the maximum grade will default to 10.
+//! - `pass_grade` (number): The minimum grade needed to pass the exam. Defaults
+//!   to half the maximum grade.
+//! - `unit` (string): The unit in which the grades are expressed, either
+//!   `"points"` or `"percentage"`. Defaults to points. Percentages always range
+//!   from 0 to 100, so `max_grade` defaults to 100 for them.
 //! - `name` (string): The name of the exam. If no value is provided, the file
 //!   name will be used as the name.
+//! - `date` (string): The date the exam took place, e.g. `"2024-06-14"`.
+//! - `course` (string): The code of the course the exam belongs to.
+//! - `term` (string): The academic term in which the exam took place.
+//! - `instructor` (string): The name of the instructor of the exam.
+//! - `questions` (table): The maximum score of each question of the exam, used
+//!   to compute the pass rate of the questions.
+//! - `scale` (table): The [GradeScale](exam::GradeScale) used to classify the
+//!   grades, as the lowest grade of each band by its label, e.g. `{ "Suspenso":
+//!   0, "Aprobado": 5, "Notable": 7, "Sobresaliente": 9 }`. The band of each
+//!   student is shown in the students table and the number of students in each
+//!   band in the summary.
+//! - `answer_key` (table): The correct option of each multiple-choice question,
+//!   e.g. `{ Q1 = "B" }`, used for the
+//!   [distractor analysis](exam::Exam::distractor_analysis).
+//!
+//! The date, course, term and instructor are shown below the exam name on top
+//! of the printed reports.
+//!
+//! Instead of a number, a student's value can also be a table with the scores
+//! obtained in each question under `questions` and/or a `grade`. If no grade is
+//! given, the student's grade is the sum of the question scores. Per-question
+//! statistics are shown in the exam summary. The options chosen by the student
+//! in multiple-choice questions can be given under `answers`. The table can
+//! also contain the student's `id`, `group` and `email`, and exams can be
+//! filtered or joined by ID instead of by name, see
+//! [Exam::filter_by_id()](exam::Exam::filter_by_id) and
+//! [MatchBy](exam::MatchBy).
+//!
+//! ```toml
+//! [details.questions]
+//! Q1 = 4
+//! Q2 = 6
+//!
+//! [details.answer_key]
+//! Q3 = "B"
+//!
+//! [students]
+//! "Abad Martinez, Jose" = { questions = { Q1 = 1.5, Q2 = 3.39 } }
+//! "Alba Gisbert, Diego" = { grade = 7.5, questions = { Q1 = 3, Q2 = 4.11 } }
+//! "Alcántara Campillo, Irene" = { grade = 6.2, id = "12345", group = "A" }
+//! "Acevedo Fuenzalida, Ignacio" = { grade = 5.1, answers = { Q3 = "C" } }
+//! ```
 //!
 //!
 //! Here are some examples of valid files:
@@ -73,6 +120,144 @@
 //! "Alcántara Campillo, Irene" = 4.41
 //! ```
 //!
+//! YAML (`.yaml` or `.yml`):
+//!
+//! ```yaml
+//! details: # This field is optional
+//!   name: Exam 1
+//!   max_grade: 10
+//!
+//! students:
+//!   "Abad Martinez, Jose": 4.89
+//!   "Acevedo Fuenzalida, Ignacio Joaquin": 5.79
+//!   "Alba Gisbert, Diego": 7.11
+//!   "Alcántara Campillo, Irene": 4.41
+//! ```
+//!
+//! CSV:
+//!
+//! CSV files can't contain a `details` field. Each line must contain a
+//! student's name and grade separated by a comma, and the first line can
+//! optionally be a header. Names containing commas must be quoted.
+//!
+//! ```csv
+//! name,grade
+//! "Abad Martinez, Jose",4.89
+//! "Acevedo Fuenzalida, Ignacio Joaquin",5.79
+//! "Alba Gisbert, Diego",7.11
+//! "Alcántara Campillo, Irene",4.41
+//! ```
+//!
+//! Excel:
+//!
+//! With the `xlsx` feature enabled, `.xlsx` workbooks can also be read. By
+//! default the first sheet must contain a student's name and grade in the
+//! first two columns of each row, and the first row can optionally be a
+//! header. Columns with the ID, group and email of the students can be read
+//! too. The details can be given in an optional sheet named `details`,
+//! with one key/value pair per row, or one row per entry with its name and
+//! number for the `questions` and `scale` tables, e.g. `questions | Q1 | 4`.
+//! The sheet and columns to read can be changed using
+//! [Exam::from_xlsx()](exam::Exam::from_xlsx).
+//!
+//! Very large files can be memory-mapped instead of read into memory with
+//! `Exam::from_file_mapped()`, available with the `mmap` feature enabled.
+//!
+//! Content that doesn't come from a file on disk, like an HTTP response or the
+//! standard input, can be parsed with
+//! [Exam::from_str()](exam::Exam::from_str) or
+//! [Exam::from_reader()](exam::Exam::from_reader), giving its
+//! [FileFormat](exam::FileFormat) explicitly. Both are built on
+//! [parse_bytes()](exam::parse_bytes), which never panics on malformed input.
+//! The size, number of students and length of the names of untrusted files can
+//! be restricted with [Limits](exam::Limits), and the checks done while
+//! parsing, like rejecting repeated students, enabled with
+//! [ParseOptions](exam::ParseOptions).
+//!
+//! Names typed by hand often contain double spaces, stray tabs or a different
+//! casing than the rest, which make them silently mismatch the same students
+//! in other files. [Exam::name_issues()](exam::Exam::name_issues) reports them
+//! right after parsing, also added to the warnings of the exam with
+//! [ParseOptions::check_names](exam::ParseOptions::check_names), and
+//! [Exam::normalize_names()](exam::Exam::normalize_names) fixes their
+//! whitespace.
+//!
+//! Every format is parsed into the same [ExamDocument](exam::ExamDocument),
+//! which can also be written back to JSON, TOML, YAML or CSV, e.g. with
+//! [Exam::write_to()](exam::Exam::write_to), so exams can be converted between
+//! formats without losing any detail, except for CSV, which only keeps the
+//! name and grade of each student.
+//!
+//! # Image export
+//!
+//! With the `image-export` feature enabled, the histogram and the box plot can
+//! also be rendered to SVG or PNG images, e.g. with
+//! `Exam::histogram_to_file()`.
+//!
+//! # Markdown reports
+//!
+//! [Exam::to_markdown()](exam::Exam::to_markdown) returns the summary, the
+//! students table and a textual histogram as GitHub-flavored Markdown, ready to
+//! be pasted into a wiki or an issue tracker. The report can also be written
+//! in Spanish with [Exam::to_markdown_in()](exam::Exam::to_markdown_in).
+//!
+//! # Diagnostics
+//!
+//! With the `tracing` feature enabled, parsing a file, computing the statistics
+//! and rendering the tables or the histogram are instrumented with
+//! [tracing](https://docs.rs/tracing) spans at the `debug` level, so their
+//! duration can be recorded by any subscriber. Parsing also emits an event with
+//! the number of students read.
+//!
+//! # Watching files
+//!
+//! With the `watch` feature enabled, [Exam::watch()](exam::Exam::watch) parses
+//! an exam file again every time it changes, so the statistics can be kept up
+//! to date while grades are being entered.
+//!
+//! # Command line
+//!
+//! With the `cli` feature enabled, the crate also builds the `exms` binary,
+//! which prints the students table, the summary or the histogram of the given
+//! files, and compares them when several are given:
+//!
+//! ```text
+//! $ cargo install exms --features cli
+//! $ exms first_term.toml second_term.toml --sort grade --filter Joan,Irene
+//! $ exms exam.toml --summary --histogram --step 0.5 --no-color
+//! $ exms exam.toml --summary --watch
+//! $ exms large_exam.csv --summary --cache-dir ~/.cache/exms
+//! $ exms run analysis.toml
+//! ```
+//!
+//! With `--cache-dir`, or the `EXMS_CACHE_DIR` environment variable, files are
+//! loaded with [Exam::from_file_cached()](exam::Exam::from_file_cached), so
+//! the files that didn't change since the last run are neither parsed nor
+//! analyzed again.
+//!
+//! `exms run` executes a [Pipeline](exam::Pipeline) spec file, which describes
+//! the files to read, how to filter and curve their students and the reports
+//! to write, so recurring analyses can be repeated and shared.
+//!
+//! The exit status of `exms` tells scripts whether the results can be
+//! published: `0` if everything went fine, `1` if any warning was found while
+//! rendering the reports, `2` if an exam file couldn't be read or a report
+//! couldn't be written and `3` if the arguments or the pipeline spec file are
+//! invalid.
+//!
+//! # Prelude
+//!
+//! The most commonly used types, like [Exam](exam::Exam),
+//! [Student](exam::Student), [Course](exam::Course) or
+//! [ParseError](error::ParseError), can be imported at once from the
+//! [prelude].
+//!
+//! ```
+//! use exms::prelude::*;
+//!
+//! let exam = Exam::new(vec![Student::new("Joan Beltrán Peris", 4.6)]);
+//! ```
+//!
 //! # Parsing other file formats
 //!
 //! Alternatively you can use your own parsing logic for any file you want to
@@ -99,3 +284,7 @@
 
 pub mod error;
 pub mod exam;
+pub mod prelude;
+pub mod scales;
+#[cfg(feature = "testutil")]
+pub mod testutil;
@@ -0,0 +1,37 @@
+//! Re-exports of the most commonly used types of the crate.
+//!
+//! Importing the prelude brings the exams, their students, courses and the
+//! options used to configure them into scope at once. Only types are
+//! re-exported, and items are only added to it, so glob importing it is safe
+//! across versions.
+//!
+//! # Examples
+//!
+//! ```
+//! use exms::prelude::*;
+//!
+//! let students = &[
+//!     Student::new("Joan Beltrán Peris", 4.6),
+//!     Student::new("David Jiménez Hidalgo", 7.94),
+//! ];
+//!
+//! let mut exam = Exam::new(students);
+//! exam.set_grade_scale(GradeScale::spanish()).unwrap();
+//!
+//! let course = Course::new(vec![exam.clone()]);
+//! let document = ExamDocument::from(&exam);
+//!
+//! assert_eq!(course.exams().len(), 1);
+//! assert_eq!(document.students.len(), 2);
+//! ```
+
+pub use crate::error::{
+    AnonymousError, FilterError, GradeError, ParseError, PipelineError, PublishedError,
+};
+#[cfg(feature = "xlsx")]
+pub use crate::exam::XlsxOptions;
+pub use crate::exam::{
+    AnonymousOptions, Course, CurveMethod, Exam, ExamBuilder, ExamDocument, ExamView, FileFormat,
+    GradeScale, GradeUnit, Histogram, HistogramBuilder, Language, Limits, MatchBy, OutlierMethod,
+    ParseOptions, Pipeline, RenderWarning, Roster, SortBy, Statistics, Student,
+};
@@ -0,0 +1,242 @@
+//! Conversions between some of the most common national grading systems.
+//!
+//! Every conversion goes through the Spanish 0-10 scale, which is the scale
+//! used by default in [Exam](crate::exam::Exam), so that the results of
+//! exchange students can be integrated into a single exam.
+//!
+//! The conversion rules are the following:
+//!
+//! | Spanish      | German     | French       | US GPA    | US letter | UK                   |
+//! |--------------|------------|--------------|-----------|-----------|----------------------|
+//! | 9.0 - 10.0   | 1.0 - 1.6  | 18.0 - 20.0  | 3.7 - 4.0 | A         | First                |
+//! | 7.0 - 9.0    | 1.6 - 2.8  | 14.0 - 18.0  | 3.0 - 3.7 | B         | Upper second (2:1)   |
+//! | 6.0 - 7.0    | 2.8 - 3.4  | 12.0 - 14.0  | 2.0 - 3.0 | C         | Lower second (2:2)   |
+//! | 5.0 - 6.0    | 3.4 - 4.0  | 10.0 - 12.0  | 1.0 - 2.0 | D         | Third                |
+//! | 0.0 - 5.0    | 4.0 - 5.0  | 0.0 - 10.0   | 0.0 - 1.0 | F         | Fail                 |
+//!
+//! - German grades are converted with the modified Bavarian formula, which maps
+//!   passing grades linearly (5.0 → 4.0 and 10.0 → 1.0). Failing Spanish grades
+//!   are mapped linearly to the 4.0-5.0 range. Results are rounded to one
+//!   decimal.
+//! - French grades are converted linearly (Spanish × 2). Results are rounded to
+//!   two decimals.
+//! - US GPA values are converted by linear interpolation between the breaks in
+//!   the table above. Results are rounded to two decimals.
+//! - US letters and UK classifications are converted to Spanish using the
+//!   middle point of their range (e.g. B → 8.0, First → 9.5).
+//!
+//! Spanish grades are always rounded to two decimals.
+//!
+//! # Examples
+//!
+//! ```
+//! use exms::exam::{Exam, Student};
+//! use exms::scales::{Grade, GradeSystem, UkClass};
+//!
+//! let students = &[
+//!     Student::new("Joan Beltrán Peris", 7.5),
+//!     Student::new("Lena Schmidt", Grade::German(1.7).to_spanish()),
+//!     Student::new("Oliver Smith", Grade::Uk(UkClass::UpperSecond).to_spanish()),
+//! ];
+//!
+//! let exam = Exam::new(students);
+//!
+//! assert_eq!(Grade::German(1.7).to_spanish(), 8.83);
+//! assert_eq!(
+//!     Grade::Spanish(8.0).convert(GradeSystem::French),
+//!     Grade::French(16.0)
+//! );
+//! ```
+
+// Breaks of the piecewise linear conversion between Spanish grades and US GPA
+const GPA_BREAKS: [(f32, f32); 6] = [
+    (0.0, 0.0),
+    (5.0, 1.0),
+    (6.0, 2.0),
+    (7.0, 3.0),
+    (9.0, 3.7),
+    (10.0, 4.0),
+];
+
+/// Grading systems supported by the conversions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradeSystem {
+    Spanish,
+    German,
+    French,
+    UsGpa,
+    UsLetter,
+    Uk,
+}
+
+/// US letter grades.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsLetter {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+/// UK degree classifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UkClass {
+    First,
+    UpperSecond,
+    LowerSecond,
+    Third,
+    Fail,
+}
+
+/// A grade expressed in one of the supported grading systems.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Grade {
+    /// Spanish grade, from 0.0 to 10.0. Passing from 5.0.
+    Spanish(f32),
+
+    /// German grade, from 1.0 (best) to 5.0 (worst). Passing up to 4.0.
+    German(f32),
+
+    /// French grade, from 0.0 to 20.0. Passing from 10.0.
+    French(f32),
+
+    /// US grade point average, from 0.0 to 4.0.
+    UsGpa(f32),
+
+    /// US letter grade.
+    UsLetter(UsLetter),
+
+    /// UK degree classification.
+    Uk(UkClass),
+}
+
+impl Grade {
+    /// Converts the grade to the Spanish 0-10 scale.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::scales::{Grade, UsLetter};
+    ///
+    /// assert_eq!(Grade::German(1.0).to_spanish(), 10.0);
+    /// assert_eq!(Grade::German(4.0).to_spanish(), 5.0);
+    /// assert_eq!(Grade::French(13.0).to_spanish(), 6.5);
+    /// assert_eq!(Grade::UsGpa(3.0).to_spanish(), 7.0);
+    /// assert_eq!(Grade::UsLetter(UsLetter::B).to_spanish(), 8.0);
+    /// ```
+    pub fn to_spanish(self) -> f32 {
+        let spanish = match self {
+            Grade::Spanish(grade) => grade,
+            Grade::German(grade) if grade <= 4.0 => 10.0 - (grade - 1.0) * 5.0 / 3.0,
+            Grade::German(grade) => (5.0 - grade) * 5.0,
+            Grade::French(grade) => grade / 2.0,
+            Grade::UsGpa(gpa) => interpolate(gpa, GPA_BREAKS.map(|(spanish, gpa)| (gpa, spanish))),
+            Grade::UsLetter(letter) => match letter {
+                UsLetter::A => 9.5,
+                UsLetter::B => 8.0,
+                UsLetter::C => 6.5,
+                UsLetter::D => 5.5,
+                UsLetter::F => 2.5,
+            },
+            Grade::Uk(class) => match class {
+                UkClass::First => 9.5,
+                UkClass::UpperSecond => 8.0,
+                UkClass::LowerSecond => 6.5,
+                UkClass::Third => 5.5,
+                UkClass::Fail => 2.5,
+            },
+        };
+
+        round(spanish.clamp(0.0, 10.0), 2)
+    }
+
+    /// Converts a Spanish 0-10 grade to the given grading system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::scales::{Grade, GradeSystem, UkClass, UsLetter};
+    ///
+    /// assert_eq!(
+    ///     Grade::from_spanish(7.0, GradeSystem::German),
+    ///     Grade::German(2.8)
+    /// );
+    /// assert_eq!(
+    ///     Grade::from_spanish(3.0, GradeSystem::German),
+    ///     Grade::German(4.4)
+    /// );
+    /// assert_eq!(
+    ///     Grade::from_spanish(9.2, GradeSystem::UsLetter),
+    ///     Grade::UsLetter(UsLetter::A)
+    /// );
+    /// assert_eq!(
+    ///     Grade::from_spanish(6.2, GradeSystem::Uk),
+    ///     Grade::Uk(UkClass::LowerSecond)
+    /// );
+    /// ```
+    pub fn from_spanish(grade: f32, system: GradeSystem) -> Grade {
+        let grade = grade.clamp(0.0, 10.0);
+
+        match system {
+            GradeSystem::Spanish => Grade::Spanish(round(grade, 2)),
+            GradeSystem::German if grade >= 5.0 => {
+                Grade::German(round(1.0 + (10.0 - grade) * 3.0 / 5.0, 1))
+            }
+            GradeSystem::German => Grade::German(round(5.0 - grade / 5.0, 1)),
+            GradeSystem::French => Grade::French(round(grade * 2.0, 2)),
+            GradeSystem::UsGpa => Grade::UsGpa(round(interpolate(grade, GPA_BREAKS), 2)),
+            GradeSystem::UsLetter => Grade::UsLetter(match grade {
+                g if g >= 9.0 => UsLetter::A,
+                g if g >= 7.0 => UsLetter::B,
+                g if g >= 6.0 => UsLetter::C,
+                g if g >= 5.0 => UsLetter::D,
+                _ => UsLetter::F,
+            }),
+            GradeSystem::Uk => Grade::Uk(match grade {
+                g if g >= 9.0 => UkClass::First,
+                g if g >= 7.0 => UkClass::UpperSecond,
+                g if g >= 6.0 => UkClass::LowerSecond,
+                g if g >= 5.0 => UkClass::Third,
+                _ => UkClass::Fail,
+            }),
+        }
+    }
+
+    /// Converts the grade to the given grading system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exms::scales::{Grade, GradeSystem};
+    ///
+    /// let grade = Grade::French(16.0).convert(GradeSystem::German);
+    ///
+    /// assert_eq!(grade, Grade::German(2.2));
+    /// ```
+    pub fn convert(self, system: GradeSystem) -> Grade {
+        Grade::from_spanish(self.to_spanish(), system)
+    }
+}
+
+// Piecewise linear interpolation of `value` between the given sorted breaks.
+fn interpolate(value: f32, breaks: [(f32, f32); 6]) -> f32 {
+    let (first_x, first_y) = breaks[0];
+    if value <= first_x {
+        return first_y;
+    }
+
+    for window in breaks.windows(2) {
+        let ((x0, y0), (x1, y1)) = (window[0], window[1]);
+        if value <= x1 {
+            return y0 + (value - x0) * (y1 - y0) / (x1 - x0);
+        }
+    }
+
+    breaks[breaks.len() - 1].1
+}
+
+fn round(value: f32, decimals: i32) -> f32 {
+    let factor = 10f32.powi(decimals);
+    (value * factor).round() / factor
+}
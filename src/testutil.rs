@@ -0,0 +1,244 @@
+//! [proptest] strategies generating realistic students, exams and exam files,
+//! available with the `testutil` feature.
+//!
+//! They allow crates building on exms to property-test their integrations
+//! without writing their own generators. [Student] and [Exam] also implement
+//! [Arbitrary], so they can be used with `any::<Student>()` and in the
+//! arguments of `proptest!` tests.
+//!
+//! Students get Spanish names made of a first name and two surnames, with
+//! accents, and grades with at most two decimals. To get a single exam outside
+//! of a test, e.g. for an example or a benchmark, see [generate_exam].
+//!
+//! # Examples
+//!
+//! ```
+//! use exms::testutil::exam_strategy;
+//! use proptest::prop_assert;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&exam_strategy(), |exam| {
+//!         let statistics = exam.statistics();
+//!         prop_assert!(statistics.lowest_grade() <= statistics.mean());
+//!         prop_assert!(statistics.mean() <= statistics.highest_grade());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::btree_map;
+use proptest::prelude::*;
+use proptest::sample::select;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+use serde_json::json;
+
+use crate::exam::{Exam, FileFormat, Seed, Student};
+
+const FIRST_NAMES: &[&str] = &[
+    "Joan", "Jose", "David", "Irene", "Jorge", "María", "Lucía", "Álvaro", "Núria", "Sergio",
+    "Marta", "Andrés", "Pilar", "Iñaki", "Carmen", "Raúl",
+];
+
+const SURNAMES: &[&str] = &[
+    "Beltrán",
+    "Abad",
+    "Jiménez",
+    "Alcántara",
+    "Rodríguez",
+    "Martínez",
+    "Peris",
+    "Hidalgo",
+    "Campillo",
+    "Martín",
+    "García",
+    "Fernández",
+    "López",
+    "Sánchez",
+    "Núñez",
+    "Ibáñez",
+];
+
+const MAX_GRADES: &[f32] = &[10.0, 20.0, 100.0];
+
+// Number of students of the generated exams by default
+const DEFAULT_SIZE: Range<usize> = 1..50;
+
+/// Exam file generated by [exam_file_strategy], together with the students it
+/// contains.
+#[derive(Debug, Clone)]
+pub struct ExamFileSample {
+    /// Format of the file.
+    pub format: FileFormat,
+
+    /// Content of the file.
+    pub content: String,
+
+    /// Maximum grade written in the details of the file.
+    pub max_grade: f32,
+
+    /// Students of the file, in the order they are written.
+    pub students: Vec<Student>,
+}
+
+/// Strategy generating realistic student names.
+pub fn name_strategy() -> impl Strategy<Value = String> {
+    (select(FIRST_NAMES), select(SURNAMES), select(SURNAMES)).prop_map(
+        |(name, first_surname, second_surname)| format!("{name} {first_surname} {second_surname}"),
+    )
+}
+
+/// Strategy generating grades between 0 and `max_grade` with at most two
+/// decimals.
+pub fn grade_strategy(max_grade: f32) -> impl Strategy<Value = f32> {
+    (0..=(max_grade * 100.0) as u32).prop_map(|hundredths| hundredths as f32 / 100.0)
+}
+
+/// Strategy generating students with a grade between 0 and `max_grade`.
+pub fn student_strategy(max_grade: f32) -> impl Strategy<Value = Student> {
+    (name_strategy(), grade_strategy(max_grade)).prop_map(|(name, grade)| Student::new(name, grade))
+}
+
+/// Strategy generating lists of students with different names and a grade
+/// between 0 and `max_grade`. The number of students is within `size`, unless
+/// not enough different names can be generated.
+pub fn students_strategy(
+    max_grade: f32,
+    size: Range<usize>,
+) -> impl Strategy<Value = Vec<Student>> {
+    btree_map(name_strategy(), grade_strategy(max_grade), size).prop_map(|grades| {
+        grades
+            .into_iter()
+            .map(|(name, grade)| Student::new(name, grade))
+            .collect()
+    })
+}
+
+/// Strategy generating exams with a maximum grade of 10, 20 or 100 and between
+/// 1 and 49 students.
+pub fn exam_strategy() -> impl Strategy<Value = Exam> {
+    select(MAX_GRADES).prop_flat_map(|max_grade| {
+        students_strategy(max_grade, DEFAULT_SIZE)
+            .prop_map(move |students| Exam::builder(students).max_grade(max_grade).build())
+    })
+}
+
+/// Generates an exam like the ones of [exam_strategy], always the same one for
+/// the same [Seed].
+///
+/// # Examples
+///
+/// ```
+/// use exms::testutil::generate_exam;
+///
+/// let exam = generate_exam(42);
+/// let again = generate_exam(42);
+///
+/// assert_eq!(exam.statistics(), again.statistics());
+/// assert_eq!(exam.students()[0].name, again.students()[0].name);
+/// ```
+pub fn generate_exam(seed: impl Into<Seed>) -> Exam {
+    let mut rng = seed.into().rng();
+    let bytes: Vec<u8> = (0..4).flat_map(|_| rng.next_u64().to_le_bytes()).collect();
+    let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &bytes);
+    let mut runner = TestRunner::new_with_rng(Config::default(), rng);
+
+    // The strategy has no filters, so generating a value can't fail
+    match exam_strategy().new_tree(&mut runner) {
+        Ok(tree) => tree.current(),
+        Err(_) => Exam::new(Vec::new()),
+    }
+}
+
+/// Strategy generating TOML, JSON, YAML and CSV exam files like the ones
+/// [Exam::from_file] reads. Every file but the CSV ones has a `details`
+/// section with its maximum grade.
+///
+/// # Examples
+///
+/// ```
+/// use exms::exam::parse_bytes;
+/// use exms::testutil::exam_file_strategy;
+/// use proptest::prop_assert_eq;
+/// use proptest::test_runner::TestRunner;
+///
+/// let mut runner = TestRunner::default();
+/// runner
+///     .run(&exam_file_strategy(), |file| {
+///         let exam = parse_bytes(file.format, file.content.as_bytes()).unwrap();
+///         prop_assert_eq!(
+///             exam.statistics().total_students() as usize,
+///             file.students.len()
+///         );
+///         Ok(())
+///     })
+///     .unwrap();
+/// ```
+pub fn exam_file_strategy() -> impl Strategy<Value = ExamFileSample> {
+    let formats = [
+        FileFormat::Toml,
+        FileFormat::Json,
+        FileFormat::Yaml,
+        FileFormat::Csv,
+    ];
+
+    (select(formats.to_vec()), select(MAX_GRADES)).prop_flat_map(|(format, max_grade)| {
+        students_strategy(max_grade, DEFAULT_SIZE).prop_map(move |students| ExamFileSample {
+            format,
+            content: file_content(format, max_grade, &students),
+            max_grade,
+            students,
+        })
+    })
+}
+
+fn file_content(format: FileFormat, max_grade: f32, students: &[Student]) -> String {
+    let grades: BTreeMap<&str, f32> = students
+        .iter()
+        .map(|student| (student.name.as_str(), student.grade))
+        .collect();
+    let file = json!({
+        "details": { "max_grade": max_grade },
+        "students": grades,
+    });
+
+    // Serializing these values can't fail
+    match format {
+        FileFormat::Toml => toml::to_string(&file).unwrap_or_default(),
+        FileFormat::Json => file.to_string(),
+        FileFormat::Yaml => serde_yaml::to_string(&file).unwrap_or_default(),
+        // CSV, the only other generated format
+        _ => std::iter::once("name,grade".to_string())
+            .chain(
+                students
+                    .iter()
+                    .map(|student| format!("{},{}", student.name, student.grade)),
+            )
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+impl Arbitrary for Student {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Student>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        student_strategy(10.0).boxed()
+    }
+}
+
+impl Arbitrary for Exam {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Exam>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        exam_strategy().boxed()
+    }
+}